@@ -47,6 +47,10 @@ pub enum Subcommand {
 
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
+
+	/// The custom benchmark subcommand benchmarking runtime pallets.
+	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
+	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
 
 /// Command for exporting the genesis state of the parachain
@@ -116,7 +120,8 @@ pub struct RunCmd {
 	pub sealing: Sealing,
 
 	// Special thank Moonbeam for this ideal
-	/// Public identity for participating in staking and receiving rewards
+	/// Public identity for participating in staking and receiving rewards. Collators no longer
+	/// need it: they author as the account the `nmbs` key in their keystore is mapped to
 	#[structopt(long, parse(try_from_str = parse_acc32))]
 	pub author_id: Option<AccountId32>,
 }
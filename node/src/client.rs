@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use runtime_primitives::{Block, AccountId, Nonce, Balance, BlakeTwo256};
+use runtime_primitives::{Block, AccountId, Nonce, Balance, BlockNumber, BlakeTwo256};
 use sp_runtime::{
 	generic::{BlockId, SignedBlock},
 	traits::{Block as BlockT},
@@ -275,6 +275,9 @@ sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
 + sp_session::SessionKeys<Block>
 + fp_rpc::EthereumRuntimeRPCApi<Block>
 + cumulus_primitives_core::CollectCollationInfo<Block>
++ pallet_crowdloan_rewards_rpc_runtime_api::CrowdloanRewardsApi<Block, AccountId, AccountId, Balance, BlockNumber>
++ pallet_author_filter_runtime_api::AuthorFilterApi<Block, AccountId, u32>
++ pallet_author_mapping_runtime_api::AuthorMappingApi<Block, author_inherent::NimbusId, AccountId>
 	where
 		<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {}
@@ -290,6 +293,9 @@ impl<Api> RuntimeApiCollection for Api
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
 		+ fp_rpc::EthereumRuntimeRPCApi<Block>
-		+ cumulus_primitives_core::CollectCollationInfo<Block>,
+		+ cumulus_primitives_core::CollectCollationInfo<Block>
+		+ pallet_crowdloan_rewards_rpc_runtime_api::CrowdloanRewardsApi<Block, AccountId, AccountId, Balance, BlockNumber>
+		+ pallet_author_filter_runtime_api::AuthorFilterApi<Block, AccountId, u32>
+		+ pallet_author_mapping_runtime_api::AuthorMappingApi<Block, author_inherent::NimbusId, AccountId>,
 <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {}
@@ -236,6 +236,31 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		}
+		Some(Subcommand::Benchmark(cmd)) => {
+			if cfg!(feature = "runtime-benchmarks") {
+				let runner = cli.create_runner(cmd)?;
+				let chain_spec = &runner.config().chain_spec;
+				if chain_spec.is_polkafoundry() {
+					#[cfg(feature = "polkafoundry")]
+						return runner.sync_run(|config| cmd.run::<Block, service::PolkaFoundryExecutor>(config));
+					#[cfg(not(feature = "polkafoundry"))]
+						return Err("PolkaFoundry runtime is not available. Please compile the node with `--features polkafoundry` to enable it.".into());
+				} else if chain_spec.is_polkasmith() {
+					#[cfg(feature = "polkasmith")]
+						return runner.sync_run(|config| cmd.run::<Block, service::PolkaSmithExecutor>(config));
+					#[cfg(not(feature = "polkasmith"))]
+						return Err("PolkaSmith runtime is not available. Please compile the node with `--features polkasmith` to enable it.".into());
+				} else {
+					#[cfg(feature = "halongbay")]
+						return runner.sync_run(|config| cmd.run::<Block, service::HalongbayExecutor>(config));
+					#[cfg(not(feature = "halongbay"))]
+						return Err("Halongbay runtime is not available. Please compile the node with `--features halongbay` to enable it.".into());
+				}
+			} else {
+				Err("Benchmarking wasn't enabled when building the node. \
+				You can enable it with `--features runtime-benchmarks`.".into())
+			}
+		}
 		Some(Subcommand::ExportGenesisState(params)) => {
 			let mut builder = sc_cli::LoggerBuilder::new("");
 			builder.with_profiling(sc_tracing::TracingReceiver::Log, "");
@@ -282,9 +307,6 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(&*cli.run)?;
 			let collator = cli.run.base.validator || cli.collator;
 			let author_id: Option<AccountId32> = cli.run.author_id.clone();
-			if collator && author_id.is_none() {
-				return Err("Collator nodes must specify an author account id".into());
-			}
 
 			runner.run_node_until_exit(|config| async move {
 				let key = sp_core::Pair::generate().0;
@@ -19,7 +19,7 @@ use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApi};
 
 use fc_rpc_core::types::{PendingTransactions, FilterPool};
 use jsonrpc_pubsub::manager::SubscriptionManager;
-use runtime_primitives::{Hash, AccountId, Index, Block, Balance};
+use runtime_primitives::{Hash, AccountId, Index, Block, Balance, BlockNumber};
 use crate::cli;
 
 /// Full client dependencies.
@@ -58,10 +58,12 @@ pub fn create_full<C, P, BE>(
 	C::Api: BlockBuilder<Block>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	C::Api: pallet_crowdloan_rewards_rpc::CrowdloanRewardsRuntimeApi<Block, AccountId, AccountId, Balance, BlockNumber>,
 	P: TransactionPool<Block=Block> + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use pallet_crowdloan_rewards_rpc::{CrowdloanRewards, CrowdloanRewardsApi};
 	use fc_rpc::{
 		EthApi, EthApiServer, EthFilterApi, EthFilterApiServer, NetApi, NetApiServer,
 		EthPubSubApi, EthPubSubApiServer, Web3Api, Web3ApiServer,
@@ -86,6 +88,9 @@ pub fn create_full<C, P, BE>(
 	io.extend_with(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
+	io.extend_with(
+		CrowdloanRewardsApi::to_delegate(CrowdloanRewards::new(client.clone()))
+	);
 
 	let signers = Vec::new();
 
@@ -3,7 +3,9 @@
 use std::{sync::{Arc, Mutex}, collections::{HashMap, BTreeMap}};
 
 use sp_core::{H256};
-use sp_runtime::traits::BlakeTwo256;
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT, Header as HeaderT};
 use sp_trie::PrefixedMemoryDB;
 use sp_inherents::{InherentIdentifier, InherentData, InherentDataProvider};
 use sp_timestamp::InherentError;
@@ -32,12 +34,14 @@ use cumulus_primitives_core::PersistedValidationData;
 use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
 use cumulus_primitives_core::ParaId;
 use cumulus_client_consensus_aura::{build_aura_consensus, BuildAuraConsensusParams, SlotProportion};
-use cumulus_client_consensus_common::ParachainConsensus;
+use cumulus_client_consensus_common::{ParachainCandidate, ParachainConsensus};
+use pallet_author_filter_runtime_api::AuthorFilterApi;
+use pallet_author_mapping_runtime_api::AuthorMappingApi;
 
 use futures::{Stream, StreamExt};
 use fc_rpc_core::types::{FilterPool, PendingTransactions};
 use fc_consensus::FrontierBlockImport;
-use runtime_primitives::{Block, Hash};
+use runtime_primitives::{AccountId, Block, Hash};
 use polkadot_primitives::v0::CollatorPair;
 
 use crate::cli::Sealing;
@@ -149,6 +153,61 @@ impl InherentDataProvider for MockParachainInherentDataProvider {
 	}
 }
 
+/// Skips authoring in the relay-parent slots the node is not eligible for, so the collators left
+/// out by the runtime's author filter don't build blocks it would reject. The node authors as the
+/// account its keystore's `NimbusId` is mapped to; without a mapped key it authors unfiltered.
+struct AuthorFilteredConsensus<Client> {
+	inner: Box<dyn ParachainConsensus<Block>>,
+	client: Arc<Client>,
+	keystore: SyncCryptoStorePtr,
+}
+
+impl<Client> Clone for AuthorFilteredConsensus<Client> {
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			client: self.client.clone(),
+			keystore: self.keystore.clone(),
+		}
+	}
+}
+
+#[async_trait::async_trait]
+impl<Client> ParachainConsensus<Block> for AuthorFilteredConsensus<Client>
+	where
+		Client: ProvideRuntimeApi<Block> + Send + Sync + 'static,
+		Client::Api: AuthorFilterApi<Block, AccountId, u32>
+			+ AuthorMappingApi<Block, author_inherent::NimbusId, AccountId>,
+{
+	async fn produce_candidate(
+		&mut self,
+		parent: &<Block as BlockT>::Header,
+		relay_parent: polkadot_primitives::v1::Hash,
+		validation_data: &PersistedValidationData,
+	) -> Option<ParachainCandidate<Block>> {
+		let at = BlockId::Hash(parent.hash());
+		let author = author_inherent::keystore_author_id(&*self.keystore)
+			.and_then(|author_id| self.client.runtime_api().account_id(&at, author_id).ok().flatten());
+		let author = match author {
+			Some(author) => author,
+			None => return self.inner.produce_candidate(parent, relay_parent, validation_data).await,
+		};
+
+		let slot = validation_data.relay_parent_number;
+		match self.client.runtime_api().can_author(&at, author.clone(), slot) {
+			Ok(true) => self.inner.produce_candidate(parent, relay_parent, validation_data).await,
+			Ok(false) => {
+				log::debug!("Skipping relay-parent slot {}: {} is not an eligible author", slot, author);
+				None
+			}
+			Err(e) => {
+				log::warn!("Failed to check the author eligibility for relay-parent slot {}: {:?}", slot, e);
+				None
+			}
+		}
+	}
+}
+
 type MaybeSelectChain = Option<LongestChain<FullBackend, Block>>;
 
 /// Starts a `ServiceBuilder` for a full service.
@@ -494,6 +553,11 @@ async fn start_node_impl<RB, RuntimeApi, Executor, BIC>(
 			params.keystore_container.sync_keystore(),
 			force_authoring,
 		)?;
+		let parachain_consensus: Box<dyn ParachainConsensus<Block>> = Box::new(AuthorFilteredConsensus {
+			inner: parachain_consensus,
+			client: client.clone(),
+			keystore: params.keystore_container.sync_keystore(),
+		});
 
 		let spawner = task_manager.spawn_handle();
 
@@ -569,10 +633,11 @@ pub async fn start_node<RuntimeApi, Executor>(
 
 			let relay_chain_backend = relay_chain_node.backend.clone();
 			let relay_chain_client = relay_chain_node.client.clone();
+			let author_keystore = keystore.clone();
 			Ok(build_aura_consensus::<AuraPair, _, _, _, _, _, _, _, _, _>(
 				BuildAuraConsensusParams {
 					proposer_factory,
-					create_inherent_data_providers: move |_, (relay_parent, validation_data)| {
+					create_inherent_data_providers: move |parent, (relay_parent, validation_data)| {
 						let parachain_inherent =
 							cumulus_primitives_parachain_inherent::ParachainInherentData::create_at_with_client(
 								relay_parent,
@@ -581,6 +646,11 @@ pub async fn start_node<RuntimeApi, Executor>(
 								&validation_data,
 								id,
 							);
+						// Prove the block author with the session key, bound to this block's slot.
+						let author = author_inherent::InherentDataProvider::from_keystore(
+							&*author_keystore,
+							&author_inherent::signing_payload(parent, validation_data.relay_parent_number),
+						);
 						async move {
 							let time = sp_timestamp::InherentDataProvider::from_system_time();
 
@@ -593,7 +663,7 @@ pub async fn start_node<RuntimeApi, Executor>(
 								Box::<dyn std::error::Error + Send + Sync>::from("Failed to create parachain inherent")
 							})?;
 
-							Ok((time, slot, parachain_inherent))
+							Ok((time, slot, parachain_inherent, author))
 						}
 					},
 					block_import: client.clone(),
@@ -661,6 +731,7 @@ pub fn start_dev(
 	let prometheus_registry = config.prometheus_registry().cloned();
 
 	if validator {
+		let keystore = keystore_container.sync_keystore();
 		let env = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -702,10 +773,17 @@ pub fn start_dev(
 				commands_stream,
 				select_chain,
 				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, _| async move {
-					let time = sp_timestamp::InherentDataProvider::from_system_time();
+				create_inherent_data_providers: move |parent, _| {
+					// The mock parachain inherent always builds on relay-parent slot 0.
+					let author = author_inherent::InherentDataProvider::from_keystore(
+						&*keystore,
+						&author_inherent::signing_payload(parent, 0u32),
+					);
+					async move {
+						let time = sp_timestamp::InherentDataProvider::from_system_time();
 
-					Ok((time, MockParachainInherentDataProvider))
+						Ok((time, MockParachainInherentDataProvider, author))
+					}
 				},
 			}),
 		);
@@ -0,0 +1,16 @@
+//! Runtime API definition for the author filter pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuthorFilterApi<AccountId, Slot> where
+		AccountId: Codec,
+		Slot: Codec,
+	{
+		/// Whether `author` is eligible to author the block built on top of the current block
+		/// at relay-parent `slot`.
+		fn can_author(author: AccountId, slot: Slot) -> bool;
+	}
+}
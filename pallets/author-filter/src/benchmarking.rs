@@ -0,0 +1,24 @@
+//! Author filter pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as AuthorFilter;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+use sp_runtime::Percent;
+
+benchmarks! {
+	set_eligible {
+		let ratio = Percent::from_percent(34);
+	}: _(RawOrigin::Root, ratio)
+	verify {
+		assert_eq!(EligibleRatio::<T>::get(), ratio);
+	}
+}
+
+impl_benchmark_test_suite!(
+	AuthorFilter,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -0,0 +1,175 @@
+//! # Author Filter Pallet
+//!
+//! Narrows the set of potential block authors down to a pseudo-random subset for every
+//! relay-parent slot, so the selected collators don't all race to author the same slot.
+//!
+//! - The subset holds `EligibleRatio` of the potential authors, rounded up. Governance adjusts
+//!   the ratio with `set_eligible`.
+//! - The subset is derived from the slot and a seed taken from `RandomnessSource` at the end of
+//!   every block. Reading the seed from the parent block lets a collator check its eligibility
+//!   ahead of authoring through the `AuthorFilterApi` runtime API and get the same answer the
+//!   runtime gets while importing the block.
+//!
+//! The pallet implements `author_inherent::CanAuthor` and is meant to be plugged into the author
+//! inherent's `CanAuthor` in place of the staking pallet, which becomes its `PotentialAuthors`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+
+#[cfg(test)]
+pub(crate) mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[pallet]
+pub mod pallet {
+	use codec::Decode;
+	use frame_support::pallet_prelude::*;
+	use frame_support::traits::Randomness;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{BlockNumberProvider, Hash, Zero};
+	use sp_runtime::Percent;
+	use sp_std::prelude::*;
+	use crate::WeightInfo;
+
+	pub type SlotOf<T> = <<T as Config>::SlotProvider as BlockNumberProvider>::BlockNumber;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Source of the seed the eligible subset is derived from.
+		type RandomnessSource: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// The collators eligible authors are picked from.
+		type PotentialAuthors: Get<Vec<Self::AccountId>>;
+
+		/// The current relay-parent slot.
+		type SlotProvider: BlockNumberProvider;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultEligibleRatio() -> Percent {
+		Percent::from_percent(50)
+	}
+
+	/// Share of the potential authors that is eligible in each slot.
+	#[pallet::storage]
+	#[pallet::getter(fn eligible_ratio)]
+	pub type EligibleRatio<T: Config> = StorageValue<_, Percent, ValueQuery, DefaultEligibleRatio>;
+
+	/// Seed for the eligible subsets of the next block, taken at the end of the previous one.
+	#[pallet::storage]
+	#[pallet::getter(fn randomness_seed)]
+	pub type RandomnessSeed<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An empty eligible subset would halt the chain.
+		RatioCannotBeZero,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The eligible ratio was changed. \[new_ratio\]
+		EligibleUpdated(Percent),
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub eligible_ratio: Percent,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self {
+				eligible_ratio: DefaultEligibleRatio(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			assert!(!self.eligible_ratio.is_zero(), "Eligible ratio cannot be zero.");
+			EligibleRatio::<T>::put(self.eligible_ratio);
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// Covers the seed update in `on_finalize`.
+			T::DbWeight::get().writes(1)
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			let (seed, _) = T::RandomnessSource::random(b"author-filter");
+			RandomnessSeed::<T>::put(seed);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the share of the potential authors that is eligible in each slot.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// Emits `EligibleUpdated` if successful.
+		#[pallet::weight(T::WeightInfo::set_eligible())]
+		pub fn set_eligible(origin: OriginFor<T>, new: Percent) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(!new.is_zero(), Error::<T>::RatioCannotBeZero);
+
+			EligibleRatio::<T>::put(new);
+
+			Self::deposit_event(Event::EligibleUpdated(new));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The authors eligible in `slot`, in the order they were drawn.
+		pub fn eligible_authors(slot: SlotOf<T>) -> Vec<T::AccountId> {
+			let mut candidates = T::PotentialAuthors::get();
+			let num_eligible =
+				EligibleRatio::<T>::get().mul_ceil(candidates.len() as u32) as usize;
+			let seed = RandomnessSeed::<T>::get();
+
+			let mut eligible = Vec::with_capacity(num_eligible);
+			for draw in 0..num_eligible as u32 {
+				let entropy = T::Hashing::hash_of(&(&seed, &slot, draw));
+				let index = u32::decode(&mut entropy.as_ref())
+					.expect("hashes are at least 4 bytes long") as usize % candidates.len();
+				eligible.push(candidates.swap_remove(index));
+			}
+			eligible
+		}
+
+		/// Whether `account` is eligible to author in `slot`.
+		pub fn can_author_at(account: &T::AccountId, slot: SlotOf<T>) -> bool {
+			Self::eligible_authors(slot).contains(account)
+		}
+	}
+
+	impl<T: Config> author_inherent::CanAuthor<T::AccountId> for Pallet<T> {
+		fn can_author(account: &T::AccountId) -> bool {
+			Self::can_author_at(account, T::SlotProvider::current_block_number())
+		}
+	}
+}
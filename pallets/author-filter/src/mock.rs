@@ -0,0 +1,118 @@
+use crate::{self as pallet_author_filter, Config};
+use frame_support::{construct_runtime, parameter_types, traits::{Get, Randomness}};
+use sp_core::H256;
+use sp_io;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+};
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+thread_local! {
+	static ENTROPY: RefCell<u64> = RefCell::new(0);
+	static CANDIDATES: RefCell<Vec<AccountId>> = RefCell::new((1..=10).collect());
+}
+
+/// Randomness derived from a counter the tests control.
+pub struct MockRandomness;
+impl Randomness<H256, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let entropy = ENTROPY.with(|e| *e.borrow());
+		(BlakeTwo256::hash_of(&(subject, entropy)), System::block_number())
+	}
+}
+
+/// Change the randomness returned from now on.
+pub(crate) fn set_entropy(entropy: u64) {
+	ENTROPY.with(|e| *e.borrow_mut() = entropy);
+}
+
+/// Accounts 1 to 10 are the potential authors unless changed with `set_candidates`.
+pub struct Candidates;
+impl Get<Vec<AccountId>> for Candidates {
+	fn get() -> Vec<AccountId> {
+		CANDIDATES.with(|c| c.borrow().clone())
+	}
+}
+
+pub(crate) fn set_candidates(candidates: Vec<AccountId>) {
+	CANDIDATES.with(|c| *c.borrow_mut() = candidates);
+}
+
+impl Config for Test {
+	type Event = Event;
+	type RandomnessSource = MockRandomness;
+	type PotentialAuthors = Candidates;
+	type SlotProvider = System;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		AuthorFilter: pallet_author_filter::{Pallet, Call, Storage, Config, Event<T>},
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap();
+
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub(crate) fn events() -> Vec<super::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::pallet_author_filter(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>()
+}
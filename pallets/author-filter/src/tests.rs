@@ -0,0 +1,83 @@
+use crate::*;
+use author_inherent::CanAuthor;
+use frame_support::{assert_noop, assert_ok, traits::OnFinalize};
+use sp_runtime::{traits::{BadOrigin, Zero}, Percent};
+use mock::*;
+
+fn sorted_eligible(slot: u64) -> Vec<AccountId> {
+	let mut eligible = AuthorFilter::eligible_authors(slot);
+	eligible.sort();
+	eligible
+}
+
+#[test]
+fn eligible_subset_has_ratio_size() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(AuthorFilter::eligible_ratio(), Percent::from_percent(50));
+		let eligible = sorted_eligible(1);
+		assert_eq!(eligible.len(), 5);
+		// Authors are drawn without replacement from the candidates.
+		let mut deduped = eligible.clone();
+		deduped.dedup();
+		assert_eq!(deduped, eligible);
+		assert!(eligible.iter().all(|a| (1..=10).contains(a)));
+
+		// The size is rounded up.
+		set_candidates(vec![1, 2, 3]);
+		assert_eq!(AuthorFilter::eligible_authors(1).len(), 2);
+		set_candidates(vec![]);
+		assert!(AuthorFilter::eligible_authors(1).is_empty());
+	});
+}
+
+#[test]
+fn full_ratio_makes_everyone_eligible() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AuthorFilter::set_eligible(Origin::root(), Percent::from_percent(100)));
+		assert_eq!(sorted_eligible(7), (1..=10).collect::<Vec<_>>());
+	});
+}
+
+#[test]
+fn subset_changes_with_slot_and_seed() {
+	new_test_ext().execute_with(|| {
+		let first = sorted_eligible(1);
+		assert_eq!(sorted_eligible(1), first);
+		assert!((2..20).any(|slot| sorted_eligible(slot) != first));
+
+		// The seed is refreshed at the end of every block.
+		let seed = AuthorFilter::randomness_seed();
+		set_entropy(1);
+		AuthorFilter::on_finalize(1);
+		assert_ne!(AuthorFilter::randomness_seed(), seed);
+	});
+}
+
+#[test]
+fn can_author_uses_current_slot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		for account in 1..=10 {
+			assert_eq!(
+				<AuthorFilter as CanAuthor<_>>::can_author(&account),
+				sorted_eligible(3).contains(&account)
+			);
+		}
+		assert!(!<AuthorFilter as CanAuthor<_>>::can_author(&11));
+	});
+}
+
+#[test]
+fn set_eligible_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(AuthorFilter::set_eligible(Origin::signed(1), Percent::from_percent(20)), BadOrigin);
+		assert_noop!(
+			AuthorFilter::set_eligible(Origin::root(), Percent::zero()),
+			Error::<Test>::RatioCannotBeZero,
+		);
+		assert_ok!(AuthorFilter::set_eligible(Origin::root(), Percent::from_percent(20)));
+		assert_eq!(AuthorFilter::eligible_ratio(), Percent::from_percent(20));
+		assert_eq!(AuthorFilter::eligible_authors(1).len(), 2);
+		assert_eq!(events(), vec![crate::Event::EligibleUpdated(Percent::from_percent(20))]);
+	});
+}
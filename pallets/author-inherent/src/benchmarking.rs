@@ -0,0 +1,31 @@
+//! Author inherent pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as AuthorInherent;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	set_author {
+		let author_id = T::AuthorId::generate_pair(None);
+		let author: T::AccountId = account("author", 0, SEED);
+		T::AccountLookup::set_account(&author_id, &author);
+		T::CanAuthor::make_eligible(&author);
+		let signature = author_id
+			.sign(&AuthorInherent::<T>::signing_payload())
+			.ok_or("author id cannot sign")?;
+	}: _(RawOrigin::None, author_id, signature)
+	verify {
+		assert_eq!(Author::<T>::get(), Some(author));
+	}
+}
+
+impl_benchmark_test_suite!(
+	AuthorInherent,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -15,23 +15,50 @@
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Pallet that allows block authors to include their identity in a block via an inherent.
-//! Currently the author does not _prove_ their identity, just states it. So it should not be used,
-//! for things like equivocation slashing that require authenticated authorship information.
+//! The author states the session key they author with and proves it by signing the parent block
+//! hash together with the relay-parent slot the block is built at, so the proof cannot be replayed
+//! in a sibling block built for another slot. The key is then resolved to the account that gets credited for the block through
+//! `Config::AccountLookup`, typically backed by the `author-mapping` pallet.
+//!
+//! The inherent is required in every block: a block without it fails `check_inherents` on
+//! import.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{
-	log, decl_error, decl_module, decl_storage, ensure,
-	traits::FindAuthor,
-	weights::{DispatchClass, Weight},
-	inherent::{InherentData, ProvideInherent, InherentIdentifier},
-};
-use frame_system::{ensure_none, Config as System};
+use frame_support::{pallet, inherent::InherentIdentifier};
 use parity_scale_codec::{Decode, Encode};
-use sp_inherents::{IsFatalError};
-use sp_runtime::{ConsensusEngineId, DigestItem, RuntimeString};
+use sp_inherents::IsFatalError;
+use sp_runtime::{traits::BlockNumberProvider, ConsensusEngineId, RuntimeAppPublic, RuntimeString};
 use sp_std::vec::Vec;
-use sp_core::H160;
+use sp_core::{crypto::KeyTypeId, H160};
+pub use pallet::*;
+
+#[cfg(test)]
+pub(crate) mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod migrations;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// Key type of the session keys collators sign their blocks with.
+pub const NIMBUS_KEY_TYPE: KeyTypeId = KeyTypeId(*b"nmbs");
+
+mod nimbus_crypto {
+	use sp_application_crypto::{app_crypto, sr25519};
+	app_crypto!(sr25519, crate::NIMBUS_KEY_TYPE);
+}
+
+/// An sr25519 session key used by collators to author blocks.
+pub type NimbusId = nimbus_crypto::Public;
+
+/// A signature made with a `NimbusId`.
+pub type NimbusSignature = nimbus_crypto::Signature;
+
+/// The signature type of the configured author id.
+pub type AuthorSignatureOf<T> = <<T as Config>::AuthorId as RuntimeAppPublic>::Signature;
 
 /// The given account ID is the author of the current block.
 pub trait EventHandler<Author> {
@@ -45,6 +72,11 @@ impl<T> EventHandler<T> for () {
 /// Permissions for what block author can be set in this pallet
 pub trait CanAuthor<AccountId> {
 	fn can_author(account: &AccountId) -> bool;
+
+	/// Make `account` eligible, so `set_author` can be benchmarked. Implementations that are not
+	/// permissive by default must override it to be used in a benchmarked runtime.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn make_eligible(_account: &AccountId) {}
 }
 
 /// Default implementation where anyone can author, see `stake` and `author-filter` pallets for
@@ -55,53 +87,135 @@ impl<T> CanAuthor<T> for () {
 	}
 }
 
-pub trait Config: System {
-	/// Other pallets that want to be informed about block authorship
-	type EventHandler: EventHandler<Self::AccountId>;
-}
+/// Resolves the session key a block was authored with to the account credited for it.
+pub trait AccountLookup<AuthorId, AccountId> {
+	fn lookup_account(author: &AuthorId) -> Option<AccountId>;
 
-decl_error! {
-	pub enum Error for Module<T: Config> {
-		/// Author already set in block.
-		AuthorAlreadySet,
-		/// The author in the inherent is not an eligible author.
-		CannotBeAuthor,
+	/// Whether any author id resolves to an account. Until one does, blocks don't need the
+	/// inherent, so a chain keeps authoring with Aura alone while its authors register their keys.
+	fn has_accounts() -> bool {
+		true
 	}
+
+	/// Make `author` resolve to `account`, so `set_author` can be benchmarked.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_account(_author: &AuthorId, _account: &AccountId) {}
+}
+
+/// Maps the account credited for a block to the address reported as the EVM block coinbase.
+pub trait AccountToEvmAddress<AccountId> {
+	fn evm_address(account: &AccountId) -> H160;
 }
 
-decl_storage! {
-	trait Store for Module<T: Config> as Author {
-		/// Author of current block.
-		Author: Option<T::AccountId>;
+/// Takes the first 20 bytes of the encoded account id, which recovers the address of accounts
+/// derived from an H160 by `EnsureAddressTruncated`. Shorter account ids are zero padded.
+pub struct TruncateAccountId;
+impl<AccountId: Encode> AccountToEvmAddress<AccountId> for TruncateAccountId {
+	fn evm_address(account: &AccountId) -> H160 {
+		let encoded = account.encode();
+		let len = encoded.len().min(20);
+		let mut address = H160::zero();
+		address.as_bytes_mut()[..len].copy_from_slice(&encoded[..len]);
+		address
 	}
 }
 
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		type Error = Error<T>;
+#[pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::log;
+	use frame_support::pallet_prelude::*;
+	use frame_support::inherent::{InherentData, ProvideInherent};
+	use frame_support::traits::FindAuthor;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{DigestItem, RuntimeDebug};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The session key block authors identify themselves with
+		type AuthorId: Member + Parameter + RuntimeAppPublic;
+		/// Maps an author id to the account that authored the block
+		type AccountLookup: AccountLookup<Self::AuthorId, Self::AccountId>;
+		/// Other pallets that want to be informed about block authorship
+		type EventHandler: EventHandler<Self::AccountId>;
+		/// Checks if an account is eligible to author the current block
+		type CanAuthor: CanAuthor<Self::AccountId>;
+		/// Maps the author to the EVM block coinbase
+		type EvmAddress: AccountToEvmAddress<Self::AccountId>;
+		/// The relay-parent slot the current block is built at, bound into the author's signature
+		type SlotProvider: BlockNumberProvider;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
 
-		fn on_initialize() -> Weight {
+	// A value placed in storage that represents the current version of the author inherent
+	// storage. This value is used by the `on_runtime_upgrade` logic to determine whether we run
+	// storage migration logic.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+	pub enum Releases {
+		V1_0_0,
+		V2_0_0,
+	}
+
+	impl Default for Releases {
+		fn default() -> Self {
+			Releases::V1_0_0
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
 			<Author<T>>::kill();
-			0
+			T::DbWeight::get().writes(1)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V1_0_0 {
+				crate::migrations::migrate_to_v2::<T>()
+			} else {
+				T::DbWeight::get().reads(1)
+			}
 		}
+	}
 
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
 		/// Inherent to set the author of a block
-		#[weight = (
-			0,
-			DispatchClass::Mandatory
-		)]
-		fn set_author(origin, author: T::AccountId) {
+		///
+		/// `signature` must be made by `author_id` over `signing_payload`.
+		///
+		/// Emits `AuthorSet` if successful.
+		#[pallet::weight((T::WeightInfo::set_author(), DispatchClass::Mandatory))]
+		pub fn set_author(
+			origin: OriginFor<T>,
+			author_id: T::AuthorId,
+			signature: AuthorSignatureOf<T>,
+		) -> DispatchResultWithPostInfo {
 			log::trace!(target:"author-inherent", "In the author inherent dispatchable");
 
 			ensure_none(origin)?;
 			ensure!(<Author<T>>::get().is_none(), Error::<T>::AuthorAlreadySet);
+			ensure!(
+				author_id.verify(&Self::signing_payload(), &signature),
+				Error::<T>::BadSignature
+			);
+			let author = T::AccountLookup::lookup_account(&author_id)
+				.ok_or(Error::<T>::NoAccountId)?;
+			ensure!(T::CanAuthor::can_author(&author), Error::<T>::CannotBeAuthor);
 
 			// Update storage
 			log::trace!(
 				target:"author-inherent",
 				"Passed ensures. About to write claimed author to storage."
 			);
-			Author::<T>::put(&author);
+			<Author<T>>::put(&author);
+			T::EventHandler::note_author(author.clone());
 
 			// Add a digest item so Apps can detect the block author
 			// For now we use the Consensus digest item.
@@ -110,32 +224,136 @@ decl_module! {
 				ENGINE_ID,
 				author.encode(),
 			));
+
+			Self::deposit_event(Event::AuthorSet(author_id, author));
+			Ok(().into())
 		}
+	}
 
-		fn on_finalize(_n: T::BlockNumber) {
-			log::trace!(
-				target:"author-inherent",
-				"In author inherent's on finalize. About to assert author was set"
-			);
-			assert!(Author::<T>::get().is_some(), "No valid author set in block");
-			log::trace!(
-				target:"author-inherent",
-				"Finished asserting author was set (apparently it was)"
-			);
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Author already set in block.
+		AuthorAlreadySet,
+		/// The author in the inherent is not an eligible author.
+		CannotBeAuthor,
+		/// The signature does not match the author id, the parent block hash and the slot.
+		BadSignature,
+		/// No account is registered for the author id.
+		NoAccountId,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The author of the block was set. \[author_id, account\]
+		AuthorSet(T::AuthorId, T::AccountId),
+	}
+
+	/// Author of current block.
+	#[pallet::storage]
+	#[pallet::getter(fn author)]
+	pub type Author<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			// New chains start out with the current storage layout.
+			StorageVersion::<T>::put(Releases::V2_0_0);
 		}
 	}
-}
 
-impl<T: Config> FindAuthor<H160> for Module<T> {
-	fn find_author<'a, I>(_digests: I) -> Option<H160>
-	where
-		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
-	{
-		// We don't use the digests at all.
-		// This will only return the correct author _after_ the authorship inherent is processed.
-		<Author<T>>::get()
-			.map(|authority_id| H160::from_slice(&authority_id.encode()[4..24]))
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = InherentError;
+		const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
+
+		fn is_inherent_required(_: &InherentData) -> Result<Option<Self::Error>, Self::Error> {
+			// Once authors are registered, every block must carry the inherent, whether or not the
+			// author provided the data for it. A block without it is rejected on import instead of
+			// panicking at the end of its execution.
+			if !T::AccountLookup::has_accounts() {
+				return Ok(None);
+			}
+			Ok(Some(InherentError::Other(
+				sp_runtime::RuntimeString::Borrowed("AuthorInherentRequired"),
+			)))
+		}
+
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			// Nobody could be credited for the block, and the mandatory call would fail it.
+			if !T::AccountLookup::has_accounts() {
+				return None;
+			}
+
+			// Grab the Vec<u8> labelled with "author__" from the map of all inherent data
+			let author_raw = data.get_data::<InherentType>(&INHERENT_IDENTIFIER).ok()??;
+
+			// Decode the Vec<u8> into the author id and its signature over the signing payload
+			let (author_id, signature) =
+				<(T::AuthorId, AuthorSignatureOf<T>)>::decode(&mut &author_raw[..]).ok()?;
+
+			Some(Call::set_author(author_id, signature))
+		}
+
+		fn check_inherent(call: &Self::Call, _data: &InherentData) -> Result<(), Self::Error> {
+			// We only care to check the inherent provided by this pallet. The signature is
+			// verified when the inherent is dispatched.
+			if let Self::Call::set_author(author_id, _) = call {
+				log::trace!(
+					target:"author-inherent",
+					"In the author inherent's `check_inherent` impl"
+				);
+				let claimed_author = T::AccountLookup::lookup_account(author_id).ok_or(
+					InherentError::Other(sp_runtime::RuntimeString::Borrowed("No Account Id"))
+				)?;
+				ensure!(
+					T::CanAuthor::can_author(&claimed_author),
+					InherentError::Other(sp_runtime::RuntimeString::Borrowed("Cannot Be Author"))
+				);
+			}
+
+			Ok(())
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::set_author(..))
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The message block authors sign: the encoded parent block hash and relay-parent slot.
+		pub fn signing_payload() -> Vec<u8> {
+			signing_payload(
+				frame_system::Pallet::<T>::parent_hash(),
+				T::SlotProvider::current_block_number(),
+			)
+		}
 	}
+
+	impl<T: Config> FindAuthor<H160> for Pallet<T> {
+		fn find_author<'a, I>(_digests: I) -> Option<H160>
+		where
+			I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+		{
+			// We don't use the digests at all.
+			// This will only return the correct author _after_ the authorship inherent is processed.
+			<Author<T>>::get().map(|author| T::EvmAddress::evm_address(&author))
+		}
+	}
+}
+
+/// The message the author of a block built on `parent_hash` at relay-parent `slot` signs.
+pub fn signing_payload<Hash: Encode, Slot: Encode>(parent_hash: Hash, slot: Slot) -> Vec<u8> {
+	(parent_hash, slot).encode()
 }
 
 // Can I express this as `*b"auth"` like we do for the inherent id?
@@ -170,21 +388,62 @@ impl InherentError {
 }
 
 /// The type of data that the inherent will contain.
-/// Just a byte array. It will be decoded to an actual account id later.
+/// Just a byte array. It will be decoded to an author id and signature pair later.
 pub type InherentType = Vec<u8>;
 
 /// The thing that the outer node will use to actually inject the inherent data
+///
+/// It holds no data when the node has no `NimbusId` to sign with. Runtimes without this pallet
+/// don't need it, and blocks of runtimes with it are then rejected on import.
+#[cfg(feature = "std")]
+pub struct InherentDataProvider(pub Option<InherentType>);
+
+/// The first `NimbusId` found in the keystore, the one the node signs the inherent with.
+#[cfg(feature = "std")]
+pub fn keystore_author_id(keystore: &dyn sp_keystore::SyncCryptoStore) -> Option<NimbusId> {
+	sp_keystore::SyncCryptoStore::sr25519_public_keys(keystore, NIMBUS_KEY_TYPE)
+		.into_iter()
+		.next()
+		.map(NimbusId::from)
+}
+
 #[cfg(feature = "std")]
-pub struct InherentDataProvider(pub InherentType);
+impl InherentDataProvider {
+	/// Sign `payload`, as built by `signing_payload`, with the first `NimbusId` found in the
+	/// keystore.
+	pub fn from_keystore(keystore: &dyn sp_keystore::SyncCryptoStore, payload: &[u8]) -> Self {
+		InherentDataProvider(Self::sign(keystore, payload))
+	}
+
+	fn sign(keystore: &dyn sp_keystore::SyncCryptoStore, payload: &[u8]) -> Option<InherentType> {
+		use sp_core::crypto::Public as _;
+		use sp_keystore::SyncCryptoStore;
+
+		let public = keystore_author_id(keystore)?;
+		let signature = SyncCryptoStore::sign_with(
+			keystore,
+			NIMBUS_KEY_TYPE,
+			&public.to_public_crypto_pair(),
+			payload,
+		)
+		.ok()?;
+		let signature = NimbusSignature::decode(&mut &signature[..]).ok()?;
+
+		Some((public, signature).encode())
+	}
+}
 
 #[cfg(feature = "std")]
 #[async_trait::async_trait]
 impl sp_inherents::InherentDataProvider for InherentDataProvider {
 	fn provide_inherent_data(
 		&self,
-		inherent_data: &mut InherentData,
+		inherent_data: &mut frame_support::inherent::InherentData,
 	) -> Result<(), sp_inherents::Error> {
-		inherent_data.put_data(INHERENT_IDENTIFIER, &self.0)
+		match &self.0 {
+			Some(data) => inherent_data.put_data(INHERENT_IDENTIFIER, data),
+			None => Ok(()),
+		}
 	}
 
 	async fn try_handle_error(
@@ -201,151 +460,3 @@ impl sp_inherents::InherentDataProvider for InherentDataProvider {
 		Some(Err(sp_inherents::Error::Application(Box::from(format!("{:?}", error)))))
 	}
 }
-
-impl<T: Config> ProvideInherent for Module<T> {
-	type Call = Call<T>;
-	type Error = InherentError;
-	const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
-
-	fn is_inherent_required(_: &InherentData) -> Result<Option<Self::Error>, Self::Error> {
-		// Return Ok(Some(_)) unconditionally because this inherent is required in every block
-		// If it is not found, throw an AuthorInherentRequired error.
-		Ok(Some(InherentError::Other(
-			sp_runtime::RuntimeString::Borrowed("AuthorInherentRequired"),
-		)))
-	}
-
-	fn create_inherent(data: &InherentData) -> Option<Self::Call> {
-		// Grab the Vec<u8> labelled with "author__" from the map of all inherent data
-		let author_raw = data
-			.get_data::<InherentType>(&INHERENT_IDENTIFIER)
-			.expect("Gets and decodes authorship inherent data")?;
-
-		//TODO we need to make the author _prove_ their identity, not just claim it.
-		// we should have them sign something here. Best idea so far: parent block hash.
-
-		// Decode the Vec<u8> into an account Id
-		let author =
-			T::AccountId::decode(&mut &author_raw[..]).expect("Decodes author raw inherent data");
-
-		Some(Call::set_author(author))
-	}
-
-	fn check_inherent(call: &Self::Call, _data: &InherentData) -> Result<(), Self::Error> {
-		// We only care to check the inherent provided by this pallet.
-		if let Self::Call::set_author(claimed_author) = call {
-			log::trace!(
-				target:"author-inherent",
-				"In the author inherent's `check_inherent` impl"
-			);
-		}
-
-		Ok(())
-	}
-
-	fn is_inherent(call: &Self::Call) -> bool {
-		matches!(call, Call::set_author(_))
-	}
-}
-
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use crate as author_inherent;
-
-	use frame_support::{
-		assert_noop, assert_ok, parameter_types,
-		traits::{OnFinalize, OnInitialize},
-	};
-	use sp_core::H256;
-	use sp_io::TestExternalities;
-	use sp_runtime::{
-		testing::Header,
-		traits::{BlakeTwo256, IdentityLookup},
-	};
-
-	pub fn new_test_ext() -> TestExternalities {
-		let t = frame_system::GenesisConfig::default()
-			.build_storage::<Test>()
-			.unwrap();
-		TestExternalities::new(t)
-	}
-
-	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
-	type Block = frame_system::mocking::MockBlock<Test>;
-
-	// Configure a mock runtime to test the pallet.
-	frame_support::construct_runtime!(
-		pub enum Test where
-			Block = Block,
-			NodeBlock = Block,
-			UncheckedExtrinsic = UncheckedExtrinsic,
-		{
-			System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-			AuthorInherent: author_inherent::{Pallet, Call, Storage, Inherent},
-		}
-	);
-
-	parameter_types! {
-		pub const BlockHashCount: u64 = 250;
-	}
-	impl frame_system::Config for Test {
-		type BaseCallFilter = ();
-		type BlockWeights = ();
-		type BlockLength = ();
-		type DbWeight = ();
-		type Origin = Origin;
-		type Index = u64;
-		type BlockNumber = u64;
-		type Call = Call;
-		type Hash = H256;
-		type Hashing = BlakeTwo256;
-		type AccountId = u64;
-		type Lookup = IdentityLookup<Self::AccountId>;
-		type Header = Header;
-		type Event = ();
-		type BlockHashCount = BlockHashCount;
-		type Version = ();
-		type PalletInfo = PalletInfo;
-		type AccountData = ();
-		type OnNewAccount = ();
-		type OnKilledAccount = ();
-		type SystemWeightInfo = ();
-		type SS58Prefix = ();
-	}
-	impl Config for Test {
-		type EventHandler = ();
-		type PreliminaryCanAuthor = ();
-		type FinalCanAuthor = ();
-	}
-
-	pub fn roll_to(n: u64) {
-		while System::block_number() < n {
-			System::on_finalize(System::block_number());
-			System::set_block_number(System::block_number() + 1);
-			System::on_initialize(System::block_number());
-			AuthorInherent::on_initialize(System::block_number());
-		}
-	}
-
-	#[test]
-	fn set_author_works() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(AuthorInherent::set_author(Origin::none(), 1));
-			roll_to(1);
-			assert_ok!(AuthorInherent::set_author(Origin::none(), 1));
-			roll_to(2);
-		});
-	}
-
-	#[test]
-	fn double_author_fails() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(AuthorInherent::set_author(Origin::none(), 1));
-			assert_noop!(
-				AuthorInherent::set_author(Origin::none(), 1),
-				Error::<Test>::AuthorAlreadySet
-			);
-		});
-	}
-}
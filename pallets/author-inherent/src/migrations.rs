@@ -0,0 +1,32 @@
+//! Storage migrations of the author inherent pallet.
+
+use crate::pallet::{Config, Pallet, Releases, StorageVersion};
+use frame_support::{
+	log,
+	storage::migration::move_pallet,
+	traits::{Get, PalletInfo},
+	weights::Weight,
+};
+
+/// Prefix the `decl_storage!` version of the pallet kept its storage under.
+pub const OLD_PREFIX: &[u8] = b"Author";
+
+/// Move the storage of `V1_0_0` from the `Author` prefix to the pallet name the runtime uses.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	let pallet_name = <T as frame_system::Config>::PalletInfo::name::<Pallet<T>>()
+		.unwrap_or("AuthorInherent")
+		.as_bytes();
+
+	if pallet_name != OLD_PREFIX {
+		move_pallet(OLD_PREFIX, pallet_name);
+	}
+	StorageVersion::<T>::put(Releases::V2_0_0);
+
+	log::info!(
+		target: "runtime::author-inherent",
+		"migrated storage from the Author prefix",
+	);
+
+	// The old prefix holds at most the author of the previous block.
+	T::DbWeight::get().reads_writes(2, 3)
+}
@@ -0,0 +1,160 @@
+use crate::{self as author_inherent, AccountLookup, CanAuthor, Config};
+use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
+use sp_core::H256;
+use sp_io;
+use sp_runtime::{
+	testing::{Header, UintAuthorityId},
+	traits::{BlakeTwo256, BlockNumberProvider, IdentityLookup},
+};
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+thread_local! {
+	static ELIGIBLE: RefCell<Vec<AccountId>> = RefCell::new(vec![1, 2]);
+	static EXTRA_ACCOUNTS: RefCell<Vec<(u64, AccountId)>> = RefCell::new(vec![]);
+	static SLOT: RefCell<u32> = RefCell::new(0);
+	static REGISTERED: RefCell<bool> = RefCell::new(true);
+}
+
+/// The relay-parent slot, moved with `set_slot`.
+pub struct MockSlot;
+impl BlockNumberProvider for MockSlot {
+	type BlockNumber = u32;
+
+	fn current_block_number() -> u32 {
+		SLOT.with(|s| *s.borrow())
+	}
+}
+
+pub(crate) fn set_slot(slot: u32) {
+	SLOT.with(|s| *s.borrow_mut() = slot);
+}
+
+/// Makes no author id resolve to an account, as on a chain whose authors haven't registered.
+pub(crate) fn unregister_all() {
+	REGISTERED.with(|r| *r.borrow_mut() = false);
+}
+
+/// Author ids below 10 map to the account with the same number, others are unregistered.
+pub struct SameAccount;
+impl AccountLookup<UintAuthorityId, AccountId> for SameAccount {
+	fn lookup_account(author: &UintAuthorityId) -> Option<AccountId> {
+		if !Self::has_accounts() {
+			None
+		} else if author.0 < 10 {
+			Some(author.0)
+		} else {
+			EXTRA_ACCOUNTS.with(|a| {
+				a.borrow().iter().find(|(id, _)| *id == author.0).map(|(_, account)| *account)
+			})
+		}
+	}
+	fn has_accounts() -> bool {
+		REGISTERED.with(|r| *r.borrow())
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_account(author: &UintAuthorityId, account: &AccountId) {
+		EXTRA_ACCOUNTS.with(|a| a.borrow_mut().push((author.0, *account)));
+	}
+}
+
+/// Only accounts 1 and 2 can author.
+pub struct TwoAuthors;
+impl CanAuthor<AccountId> for TwoAuthors {
+	fn can_author(account: &AccountId) -> bool {
+		ELIGIBLE.with(|e| e.borrow().contains(account))
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn make_eligible(account: &AccountId) {
+		ELIGIBLE.with(|e| e.borrow_mut().push(*account));
+	}
+}
+
+impl Config for Test {
+	type Event = Event;
+	type AuthorId = UintAuthorityId;
+	type AccountLookup = SameAccount;
+	type EventHandler = ();
+	type CanAuthor = TwoAuthors;
+	type EvmAddress = author_inherent::TruncateAccountId;
+	type SlotProvider = MockSlot;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		AuthorInherent: author_inherent::{Pallet, Call, Storage, Config, Event<T>, Inherent},
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap();
+
+	<author_inherent::GenesisConfig as GenesisBuild<Test>>::assimilate_storage(
+		&Default::default(),
+		&mut t,
+	)
+	.unwrap();
+
+	set_slot(0);
+	REGISTERED.with(|r| *r.borrow_mut() = true);
+
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub(crate) fn events() -> Vec<super::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::author_inherent(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>()
+}
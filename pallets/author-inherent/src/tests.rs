@@ -0,0 +1,196 @@
+use crate::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	inherent::{InherentData, ProvideInherent},
+	traits::{FindAuthor, OnFinalize, OnInitialize},
+};
+use mock::*;
+use sp_core::H160;
+use sp_runtime::testing::{TestSignature, UintAuthorityId};
+
+fn roll_to(n: u64) {
+	while System::block_number() < n {
+		System::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		System::on_initialize(System::block_number());
+		AuthorInherent::on_initialize(System::block_number());
+	}
+}
+
+fn sign(author: u64) -> TestSignature {
+	UintAuthorityId(author)
+		.sign(&AuthorInherent::signing_payload())
+		.unwrap()
+}
+
+fn set_author(author: u64) -> frame_support::dispatch::DispatchResultWithPostInfo {
+	AuthorInherent::set_author(Origin::none(), UintAuthorityId(author), sign(author))
+}
+
+#[test]
+fn set_author_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_author(1));
+		assert_eq!(AuthorInherent::author(), Some(1));
+		assert_eq!(events(), vec![crate::Event::AuthorSet(UintAuthorityId(1), 1)]);
+		roll_to(2);
+		assert_eq!(AuthorInherent::author(), None);
+		assert_ok!(set_author(1));
+		roll_to(3);
+	});
+}
+
+#[test]
+fn find_author_maps_account_to_evm_address() {
+	new_test_ext().execute_with(|| {
+		let no_digests: Vec<(sp_runtime::ConsensusEngineId, &[u8])> = vec![];
+		assert_eq!(AuthorInherent::find_author(no_digests.clone()), None);
+
+		assert_ok!(set_author(2));
+		let mut expected = [0u8; 20];
+		expected[0] = 2;
+		assert_eq!(AuthorInherent::find_author(no_digests), Some(H160(expected)));
+	});
+}
+
+#[test]
+fn truncation_recovers_truncated_addresses() {
+	let address = H160::repeat_byte(0xab);
+	let mut account = [0u8; 32];
+	account[..20].copy_from_slice(address.as_bytes());
+	assert_eq!(<TruncateAccountId as AccountToEvmAddress<_>>::evm_address(&account), address);
+}
+
+#[test]
+fn double_author_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_author(1));
+		assert_noop!(set_author(1), Error::<Test>::AuthorAlreadySet);
+	});
+}
+
+#[test]
+fn ineligible_author_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(set_author(3), Error::<Test>::CannotBeAuthor);
+		assert_ok!(set_author(2));
+	});
+}
+
+#[test]
+fn bad_signature_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AuthorInherent::set_author(Origin::none(), UintAuthorityId(1), sign(2)),
+			Error::<Test>::BadSignature
+		);
+		// A signature over the parent hash alone is rejected too.
+		let stale = UintAuthorityId(1).sign(&System::parent_hash().encode()).unwrap();
+		assert_noop!(
+			AuthorInherent::set_author(Origin::none(), UintAuthorityId(1), stale),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn signature_is_bound_to_the_slot() {
+	new_test_ext().execute_with(|| {
+		set_slot(7);
+		let signature = sign(1);
+		// A sibling block built on the same parent for another slot cannot reuse the signature.
+		set_slot(8);
+		assert_noop!(
+			AuthorInherent::set_author(Origin::none(), UintAuthorityId(1), signature.clone()),
+			Error::<Test>::BadSignature
+		);
+		set_slot(7);
+		assert_ok!(AuthorInherent::set_author(Origin::none(), UintAuthorityId(1), signature));
+	});
+}
+
+#[test]
+fn unregistered_author_id_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(set_author(10), Error::<Test>::NoAccountId);
+	});
+}
+
+#[test]
+fn block_without_author_does_not_panic() {
+	new_test_ext().execute_with(|| {
+		roll_to(2);
+		AuthorInherent::on_finalize(2);
+	});
+}
+
+#[test]
+fn inherent_is_required() {
+	new_test_ext().execute_with(|| {
+		let data = InherentData::new();
+		assert!(matches!(AuthorInherent::is_inherent_required(&data), Ok(Some(_))));
+	});
+}
+
+#[test]
+fn inherent_is_optional_until_authors_register() {
+	new_test_ext().execute_with(|| {
+		unregister_all();
+		let mut data = InherentData::new();
+		assert!(matches!(AuthorInherent::is_inherent_required(&data), Ok(None)));
+
+		// Signed data is left out rather than failing the block.
+		let raw: InherentType = (UintAuthorityId(1), sign(1)).encode();
+		data.put_data(INHERENT_IDENTIFIER, &raw).unwrap();
+		assert!(AuthorInherent::create_inherent(&data).is_none());
+	});
+}
+
+#[test]
+fn create_inherent_decodes_author_and_signature() {
+	new_test_ext().execute_with(|| {
+		let mut data = InherentData::new();
+		assert!(AuthorInherent::create_inherent(&data).is_none());
+
+		let raw: InherentType = (UintAuthorityId(1), sign(1)).encode();
+		data.put_data(INHERENT_IDENTIFIER, &raw).unwrap();
+		assert_eq!(
+			AuthorInherent::create_inherent(&data),
+			Some(crate::Call::set_author(UintAuthorityId(1), sign(1)))
+		);
+	});
+}
+
+#[test]
+fn check_inherent_enforces_can_author() {
+	new_test_ext().execute_with(|| {
+		let data = InherentData::new();
+		let call = |author| crate::Call::set_author(UintAuthorityId(author), sign(author));
+		assert!(AuthorInherent::check_inherent(&call(1), &data).is_ok());
+		assert!(AuthorInherent::check_inherent(&call(3), &data).is_err());
+		assert!(AuthorInherent::check_inherent(&call(10), &data).is_err());
+	});
+}
+
+#[test]
+fn migrate_to_v2_works() {
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::OnRuntimeUpgrade,
+	};
+
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		put_storage_value(b"Author", b"Author", &[], 5u64);
+
+		AuthorInherent::on_runtime_upgrade();
+
+		assert_eq!(AuthorInherent::storage_version(), Releases::V2_0_0);
+		assert_eq!(AuthorInherent::author(), Some(5));
+		assert_eq!(get_storage_value::<u64>(b"Author", b"Author", &[]), None);
+
+		// a second upgrade leaves the author alone
+		AuthorInherent::on_runtime_upgrade();
+		assert_eq!(AuthorInherent::author(), Some(5));
+	})
+}
@@ -0,0 +1,15 @@
+//! Runtime API definition for the author mapping pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuthorMappingApi<AuthorId, AccountId> where
+		AuthorId: Codec,
+		AccountId: Codec,
+	{
+		/// The account `author_id` is mapped to, if any.
+		fn account_id(author_id: AuthorId) -> Option<AccountId>;
+	}
+}
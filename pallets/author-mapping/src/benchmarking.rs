@@ -0,0 +1,82 @@
+//! Author mapping pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as AuthorMapping;
+use codec::Decode;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_core::H160;
+use sp_runtime::traits::{Hash, Saturating};
+
+// A deterministic author id, derived the same way `account` derives account ids.
+fn author_id<T: Config>(index: u32) -> T::AuthorId {
+	let entropy = T::Hashing::hash_of(&(b"author", index));
+	T::AuthorId::decode(&mut entropy.as_ref()).expect("a hash is enough to decode an author id")
+}
+
+// A caller that can afford the registration deposit.
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	let balance = T::DepositAmount::get().saturating_mul(100u32.into());
+	T::DepositCurrency::make_free_balance_be(&caller, balance);
+	caller
+}
+
+// Register `author_id(0)` to a funded caller.
+fn registered_caller<T: Config>() -> Result<T::AccountId, &'static str> {
+	let caller = funded_caller::<T>();
+	AuthorMapping::<T>::add_association(RawOrigin::Signed(caller.clone()).into(), author_id::<T>(0))
+		.map_err(|_| "failed to register author id")?;
+	Ok(caller)
+}
+
+benchmarks! {
+	add_association {
+		let caller = funded_caller::<T>();
+		let id = author_id::<T>(0);
+	}: _(RawOrigin::Signed(caller.clone()), id.clone())
+	verify {
+		assert_eq!(Mappings::<T>::get(&id).map(|info| info.account), Some(caller));
+	}
+
+	update_association {
+		let caller = registered_caller::<T>()?;
+		let new_id = author_id::<T>(1);
+	}: _(RawOrigin::Signed(caller.clone()), author_id::<T>(0), new_id.clone())
+	verify {
+		assert!(!Mappings::<T>::contains_key(&author_id::<T>(0)));
+		assert_eq!(Mappings::<T>::get(&new_id).map(|info| info.account), Some(caller));
+	}
+
+	clear_association {
+		let caller = registered_caller::<T>()?;
+	}: _(RawOrigin::Signed(caller), author_id::<T>(0))
+	verify {
+		assert!(!Mappings::<T>::contains_key(&author_id::<T>(0)));
+	}
+
+	set_evm_address {
+		let caller: T::AccountId = whitelisted_caller();
+		let address = H160::repeat_byte(1);
+	}: _(RawOrigin::Signed(caller.clone()), address)
+	verify {
+		assert_eq!(EvmAddresses::<T>::get(&caller), Some(address));
+	}
+
+	clear_evm_address {
+		let caller: T::AccountId = whitelisted_caller();
+		EvmAddresses::<T>::insert(&caller, H160::repeat_byte(1));
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!EvmAddresses::<T>::contains_key(&caller));
+	}
+}
+
+impl_benchmark_test_suite!(
+	AuthorMapping,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
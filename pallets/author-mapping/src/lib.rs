@@ -0,0 +1,302 @@
+//! # Author Mapping Pallet
+//!
+//! Links the session keys collators sign their blocks with to the staking accounts that get
+//! credited for those blocks, so the staking account itself never has to live on the node.
+//!
+//! - `add_association`: a staking account registers a session key, reserving `DepositAmount`.
+//! - `update_association`: the owner rotates a registered key to a new one, keeping the deposit.
+//! - `clear_association`: the owner deregisters a key and gets the deposit back.
+//! - `set_evm_address`/`clear_evm_address`: an account chooses the address its blocks report as
+//!   the EVM coinbase.
+//!
+//! The pallet implements `author_inherent::AccountLookup`, which the author inherent uses to
+//! resolve the key that signed a block to the account passed on to `note_author`, and
+//! `author_inherent::AccountToEvmAddress`, which falls back to truncating the account id when no
+//! EVM address was registered.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+
+#[cfg(test)]
+pub(crate) mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[pallet]
+pub mod pallet {
+	use codec::{Decode, Encode};
+	use frame_support::pallet_prelude::*;
+	use frame_support::traits::{Currency, ReservableCurrency};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::MaybeSerializeDeserialize;
+	use sp_core::H160;
+	use sp_runtime::RuntimeDebug;
+	use sp_std::prelude::*;
+	use crate::WeightInfo;
+
+	pub type BalanceOf<T> = <<T as Config>::DepositCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	pub type RegistrationInfoOf<T> =
+		RegistrationInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// The account a session key is registered to and the deposit reserved for it.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct RegistrationInfo<AccountId, Balance> {
+		/// The staking account credited for blocks authored with the key.
+		pub account: AccountId,
+		/// The deposit reserved from `account`.
+		pub deposit: Balance,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The session key collators author blocks with.
+		type AuthorId: Member + Parameter + MaybeSerializeDeserialize;
+
+		/// Currency in which the registration deposit is reserved.
+		type DepositCurrency: ReservableCurrency<Self::AccountId>;
+
+		/// Deposit reserved for each registered session key.
+		#[pallet::constant]
+		type DepositAmount: Get<BalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Registered session keys and the accounts they author for.
+	#[pallet::storage]
+	#[pallet::getter(fn mapping)]
+	pub type Mappings<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AuthorId, RegistrationInfoOf<T>, OptionQuery>;
+
+	/// EVM addresses accounts chose to receive the block coinbase at.
+	#[pallet::storage]
+	#[pallet::getter(fn evm_address_of)]
+	pub type EvmAddresses<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, H160, OptionQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The session key is not registered.
+		AssociationNotFound,
+		/// The session key is registered to another account.
+		NotYourAssociation,
+		/// The account cannot afford the registration deposit.
+		CannotAffordSecurityDeposit,
+		/// The session key is already registered.
+		AlreadyAssociated,
+		/// The account has no EVM address registered.
+		EvmAddressNotFound,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A session key was registered. \[author_id, account\]
+		AuthorRegistered(T::AuthorId, T::AccountId),
+		/// A session key was rotated. \[old_author_id, new_author_id, account\]
+		AuthorRotated(T::AuthorId, T::AuthorId, T::AccountId),
+		/// A session key was deregistered and its deposit returned. \[author_id, account\]
+		AuthorDeRegistered(T::AuthorId, T::AccountId),
+		/// An account registered the EVM address of its blocks. \[account, address\]
+		EvmAddressSet(T::AccountId, H160),
+		/// An account removed the EVM address of its blocks. \[account\]
+		EvmAddressCleared(T::AccountId),
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub mappings: Vec<(T::AuthorId, T::AccountId)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				mappings: vec![],
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (author_id, account) in &self.mappings {
+				assert!(
+					Pallet::<T>::enact_registration(author_id, account).is_ok(),
+					"Genesis account cannot afford the author mapping deposit."
+				);
+			}
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a session key to the caller, reserving `DepositAmount`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `AuthorRegistered` if successful.
+		#[pallet::weight(T::WeightInfo::add_association())]
+		pub fn add_association(
+			origin: OriginFor<T>,
+			author_id: T::AuthorId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!Mappings::<T>::contains_key(&author_id), Error::<T>::AlreadyAssociated);
+			Self::enact_registration(&author_id, &who)?;
+
+			Self::deposit_event(Event::AuthorRegistered(author_id, who));
+			Ok(().into())
+		}
+
+		/// Move the caller's registration from `old_author_id` to `new_author_id`. The deposit
+		/// stays reserved.
+		///
+		/// The dispatch origin for this call must be _Signed_ and own `old_author_id`.
+		///
+		/// Emits `AuthorRotated` if successful.
+		#[pallet::weight(T::WeightInfo::update_association())]
+		pub fn update_association(
+			origin: OriginFor<T>,
+			old_author_id: T::AuthorId,
+			new_author_id: T::AuthorId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let info = Self::owned_registration(&old_author_id, &who)?;
+			ensure!(!Mappings::<T>::contains_key(&new_author_id), Error::<T>::AlreadyAssociated);
+
+			Mappings::<T>::remove(&old_author_id);
+			Mappings::<T>::insert(&new_author_id, info);
+
+			Self::deposit_event(Event::AuthorRotated(old_author_id, new_author_id, who));
+			Ok(().into())
+		}
+
+		/// Deregister a session key and return its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ and own `author_id`.
+		///
+		/// Emits `AuthorDeRegistered` if successful.
+		#[pallet::weight(T::WeightInfo::clear_association())]
+		pub fn clear_association(
+			origin: OriginFor<T>,
+			author_id: T::AuthorId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let info = Self::owned_registration(&author_id, &who)?;
+			Mappings::<T>::remove(&author_id);
+			T::DepositCurrency::unreserve(&who, info.deposit);
+
+			Self::deposit_event(Event::AuthorDeRegistered(author_id, who));
+			Ok(().into())
+		}
+
+		/// Report `address` as the EVM coinbase of the blocks credited to the caller.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `EvmAddressSet` if successful.
+		#[pallet::weight(T::WeightInfo::set_evm_address())]
+		pub fn set_evm_address(origin: OriginFor<T>, address: H160) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			EvmAddresses::<T>::insert(&who, address);
+
+			Self::deposit_event(Event::EvmAddressSet(who, address));
+			Ok(().into())
+		}
+
+		/// Go back to reporting the truncated account id as the EVM coinbase.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `EvmAddressCleared` if successful.
+		#[pallet::weight(T::WeightInfo::clear_evm_address())]
+		pub fn clear_evm_address(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(EvmAddresses::<T>::contains_key(&who), Error::<T>::EvmAddressNotFound);
+			EvmAddresses::<T>::remove(&who);
+
+			Self::deposit_event(Event::EvmAddressCleared(who));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Reserve the deposit from `account` and register `author_id` to it.
+		fn enact_registration(
+			author_id: &T::AuthorId,
+			account: &T::AccountId,
+		) -> DispatchResult {
+			let deposit = T::DepositAmount::get();
+			T::DepositCurrency::reserve(account, deposit)
+				.map_err(|_| Error::<T>::CannotAffordSecurityDeposit)?;
+
+			Mappings::<T>::insert(author_id, RegistrationInfo {
+				account: account.clone(),
+				deposit,
+			});
+			Ok(())
+		}
+
+		/// The registration of `author_id`, provided it belongs to `who`.
+		fn owned_registration(
+			author_id: &T::AuthorId,
+			who: &T::AccountId,
+		) -> Result<RegistrationInfoOf<T>, DispatchError> {
+			let info = Mappings::<T>::get(author_id).ok_or(Error::<T>::AssociationNotFound)?;
+			ensure!(&info.account == who, Error::<T>::NotYourAssociation);
+			Ok(info)
+		}
+	}
+
+	impl<T: Config> author_inherent::AccountLookup<T::AuthorId, T::AccountId> for Pallet<T> {
+		fn lookup_account(author: &T::AuthorId) -> Option<T::AccountId> {
+			Mappings::<T>::get(author).map(|info| info.account)
+		}
+
+		fn has_accounts() -> bool {
+			Mappings::<T>::iter().next().is_some()
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn set_account(author: &T::AuthorId, account: &T::AccountId) {
+			Mappings::<T>::insert(author, RegistrationInfo {
+				account: account.clone(),
+				deposit: sp_runtime::traits::Zero::zero(),
+			});
+		}
+	}
+
+	impl<T: Config> author_inherent::AccountToEvmAddress<T::AccountId> for Pallet<T> {
+		fn evm_address(account: &T::AccountId) -> H160 {
+			EvmAddresses::<T>::get(account).unwrap_or_else(|| {
+				<author_inherent::TruncateAccountId as author_inherent::AccountToEvmAddress<_>>
+					::evm_address(account)
+			})
+		}
+	}
+}
@@ -0,0 +1,131 @@
+use crate::*;
+use author_inherent::{AccountLookup, AccountToEvmAddress};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
+use sp_core::H160;
+use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin};
+use mock::*;
+
+#[test]
+fn genesis_mappings_reserve_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			AuthorMapping::mapping(UintAuthorityId(1)),
+			Some(RegistrationInfo { account: 1, deposit: 100 })
+		);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(AuthorMapping::lookup_account(&UintAuthorityId(1)), Some(1));
+	});
+}
+
+#[test]
+fn add_association_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(AuthorMapping::add_association(Origin::none(), UintAuthorityId(2)), BadOrigin);
+		assert_ok!(AuthorMapping::add_association(Origin::signed(2), UintAuthorityId(2)));
+
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert_eq!(AuthorMapping::lookup_account(&UintAuthorityId(2)), Some(2));
+		assert_eq!(events(), vec![crate::Event::AuthorRegistered(UintAuthorityId(2), 2)]);
+
+		// An account may register several keys.
+		assert_ok!(AuthorMapping::add_association(Origin::signed(2), UintAuthorityId(3)));
+		assert_eq!(Balances::reserved_balance(2), 200);
+	});
+}
+
+#[test]
+fn add_association_fails_for_taken_key() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AuthorMapping::add_association(Origin::signed(2), UintAuthorityId(1)),
+			Error::<Test>::AlreadyAssociated,
+		);
+		assert_eq!(AuthorMapping::lookup_account(&UintAuthorityId(1)), Some(1));
+	});
+}
+
+#[test]
+fn add_association_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AuthorMapping::add_association(Origin::signed(3), UintAuthorityId(3)),
+			Error::<Test>::CannotAffordSecurityDeposit,
+		);
+		assert_eq!(AuthorMapping::lookup_account(&UintAuthorityId(3)), None);
+	});
+}
+
+#[test]
+fn update_association_rotates_key() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AuthorMapping::update_association(Origin::signed(1), UintAuthorityId(5), UintAuthorityId(6)),
+			Error::<Test>::AssociationNotFound,
+		);
+		assert_noop!(
+			AuthorMapping::update_association(Origin::signed(2), UintAuthorityId(1), UintAuthorityId(6)),
+			Error::<Test>::NotYourAssociation,
+		);
+		assert_ok!(AuthorMapping::add_association(Origin::signed(2), UintAuthorityId(2)));
+		assert_noop!(
+			AuthorMapping::update_association(Origin::signed(1), UintAuthorityId(1), UintAuthorityId(2)),
+			Error::<Test>::AlreadyAssociated,
+		);
+
+		assert_ok!(AuthorMapping::update_association(Origin::signed(1), UintAuthorityId(1), UintAuthorityId(6)));
+		assert_eq!(AuthorMapping::lookup_account(&UintAuthorityId(1)), None);
+		assert_eq!(AuthorMapping::lookup_account(&UintAuthorityId(6)), Some(1));
+		// The deposit moves with the key.
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::AuthorRotated(UintAuthorityId(1), UintAuthorityId(6), 1))
+		);
+	});
+}
+
+#[test]
+fn clear_association_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AuthorMapping::clear_association(Origin::signed(2), UintAuthorityId(1)),
+			Error::<Test>::NotYourAssociation,
+		);
+		assert_noop!(
+			AuthorMapping::clear_association(Origin::signed(1), UintAuthorityId(2)),
+			Error::<Test>::AssociationNotFound,
+		);
+
+		assert!(AuthorMapping::has_accounts());
+		assert_ok!(AuthorMapping::clear_association(Origin::signed(1), UintAuthorityId(1)));
+		assert_eq!(AuthorMapping::lookup_account(&UintAuthorityId(1)), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(events(), vec![crate::Event::AuthorDeRegistered(UintAuthorityId(1), 1)]);
+		// With the only mapping gone, blocks don't need the author inherent anymore.
+		assert!(!AuthorMapping::has_accounts());
+
+		// The key is free to be registered by someone else now.
+		assert_ok!(AuthorMapping::add_association(Origin::signed(2), UintAuthorityId(1)));
+		assert!(AuthorMapping::has_accounts());
+	});
+}
+
+#[test]
+fn evm_address_defaults_to_truncation() {
+	new_test_ext().execute_with(|| {
+		let mut truncated = [0u8; 20];
+		truncated[0] = 1;
+		assert_eq!(AuthorMapping::evm_address(&1), H160(truncated));
+
+		assert_ok!(AuthorMapping::set_evm_address(Origin::signed(1), H160::repeat_byte(7)));
+		assert_eq!(AuthorMapping::evm_address(&1), H160::repeat_byte(7));
+		assert_eq!(events(), vec![crate::Event::EvmAddressSet(1, H160::repeat_byte(7))]);
+
+		assert_ok!(AuthorMapping::clear_evm_address(Origin::signed(1)));
+		assert_eq!(AuthorMapping::evm_address(&1), H160(truncated));
+		assert_noop!(
+			AuthorMapping::clear_evm_address(Origin::signed(1)),
+			Error::<Test>::EvmAddressNotFound,
+		);
+	});
+}
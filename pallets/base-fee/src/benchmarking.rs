@@ -0,0 +1,26 @@
+//! Base fee pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as BaseFee;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+use sp_core::U256;
+
+benchmarks! {
+	set_bounds {
+		let min = U256::from(2_000);
+		let max = U256::from(3_000);
+	}: _(RawOrigin::Root, min, max)
+	verify {
+		assert_eq!(MaxBaseFee::<T>::get(), max);
+		assert!(BaseFeePerGas::<T>::get() >= min && BaseFeePerGas::<T>::get() <= max);
+	}
+}
+
+impl_benchmark_test_suite!(
+	BaseFee,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -0,0 +1,174 @@
+//! # Base Fee Pallet
+//!
+//! Keeps an EIP-1559 style base fee per gas for EVM transactions and serves it as the EVM's
+//! minimum gas price.
+//!
+//! - At the end of every block the base fee moves by up to 1/8th of itself towards keeping blocks
+//!   `TargetBlockFullness` full, measured in normal dispatch weight: fuller blocks raise it,
+//!   emptier blocks lower it.
+//! - The base fee never leaves the bounds governance sets with `set_bounds`.
+//!
+//! Unlike EIP-1559 the whole gas price is charged, base fee included, and goes wherever the EVM's
+//! `OnChargeTransaction` sends it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+
+#[cfg(test)]
+pub(crate) mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_support::weights::DispatchClass;
+	use frame_system::pallet_prelude::*;
+	use sp_core::U256;
+	use sp_runtime::Permill;
+	use crate::WeightInfo;
+
+	/// The base fee moves by at most `1 / BASE_FEE_MAX_CHANGE_DENOMINATOR` of itself per block.
+	pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u32 = 8;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Base fee per gas the chain starts with.
+		type DefaultBaseFeePerGas: Get<U256>;
+
+		/// Lowest base fee per gas until governance sets the bounds.
+		type DefaultMinBaseFee: Get<U256>;
+
+		/// Highest base fee per gas until governance sets the bounds.
+		type DefaultMaxBaseFee: Get<U256>;
+
+		/// Share of the normal dispatch weight blocks should use for the base fee to hold steady.
+		#[pallet::constant]
+		type TargetBlockFullness: Get<Permill>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::type_value]
+	pub fn InitialBaseFeePerGas<T: Config>() -> U256 {
+		T::DefaultBaseFeePerGas::get()
+	}
+
+	#[pallet::type_value]
+	pub fn InitialMinBaseFee<T: Config>() -> U256 {
+		T::DefaultMinBaseFee::get()
+	}
+
+	#[pallet::type_value]
+	pub fn InitialMaxBaseFee<T: Config>() -> U256 {
+		T::DefaultMaxBaseFee::get()
+	}
+
+	/// Minimum gas price of EVM transactions.
+	#[pallet::storage]
+	#[pallet::getter(fn base_fee_per_gas)]
+	pub type BaseFeePerGas<T: Config> = StorageValue<_, U256, ValueQuery, InitialBaseFeePerGas<T>>;
+
+	/// The base fee never goes below this.
+	#[pallet::storage]
+	#[pallet::getter(fn min_base_fee)]
+	pub type MinBaseFee<T: Config> = StorageValue<_, U256, ValueQuery, InitialMinBaseFee<T>>;
+
+	/// The base fee never goes above this.
+	#[pallet::storage]
+	#[pallet::getter(fn max_base_fee)]
+	pub type MaxBaseFee<T: Config> = StorageValue<_, U256, ValueQuery, InitialMaxBaseFee<T>>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The lower bound is above the upper bound.
+		MinAboveMax,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The bounds of the base fee were changed. \[min, max\]
+		BoundsUpdated(U256, U256),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// Covers reading the block weight, the fee and its bounds, and writing the fee in
+			// `on_finalize`.
+			T::DbWeight::get().reads_writes(4, 1)
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			let limits = T::BlockWeights::get();
+			let max = limits.get(DispatchClass::Normal).max_total.unwrap_or(limits.max_block);
+			let used = *frame_system::Pallet::<T>::block_weight().get(DispatchClass::Normal);
+
+			let fullness = Permill::from_rational(used, max.max(1));
+			BaseFeePerGas::<T>::mutate(|fee| *fee = Self::adjusted(*fee, fullness));
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the bounds of the base fee, moving the base fee into them if needed.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// Emits `BoundsUpdated` if successful.
+		#[pallet::weight(T::WeightInfo::set_bounds())]
+		pub fn set_bounds(origin: OriginFor<T>, min: U256, max: U256) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(min <= max, Error::<T>::MinAboveMax);
+
+			MinBaseFee::<T>::put(min);
+			MaxBaseFee::<T>::put(max);
+			BaseFeePerGas::<T>::mutate(|fee| *fee = (*fee).max(min).min(max));
+
+			Self::deposit_event(Event::BoundsUpdated(min, max));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The base fee following a block `fullness` full, within the bounds.
+		pub fn adjusted(base_fee: U256, fullness: Permill) -> U256 {
+			let target = T::TargetBlockFullness::get().deconstruct().max(1);
+			let fullness = fullness.deconstruct();
+			// Moves by 1/8th of the fee when the distance to the target is the target itself.
+			let change = |distance: u32| {
+				base_fee.saturating_mul(distance.min(target).into()) /
+					U256::from(target) /
+					U256::from(BASE_FEE_MAX_CHANGE_DENOMINATOR)
+			};
+
+			let adjusted = if fullness > target {
+				// As in EIP-1559, a fee too small to move by a fraction of itself still goes up.
+				base_fee.saturating_add(change(fullness - target).max(U256::one()))
+			} else {
+				base_fee.saturating_sub(change(target - fullness))
+			};
+			adjusted.max(MinBaseFee::<T>::get()).min(MaxBaseFee::<T>::get())
+		}
+	}
+
+	impl<T: Config> pallet_evm::FeeCalculator for Pallet<T> {
+		fn min_gas_price() -> U256 {
+			BaseFeePerGas::<T>::get()
+		}
+	}
+}
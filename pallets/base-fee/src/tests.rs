@@ -0,0 +1,102 @@
+use crate::*;
+use frame_support::{assert_noop, assert_ok, traits::OnFinalize};
+use pallet_evm::FeeCalculator;
+use sp_core::U256;
+use sp_runtime::traits::BadOrigin;
+use mock::*;
+
+/// Finish a block that used `weight` of normal dispatch weight.
+fn end_block(weight: u64) {
+	System::set_block_consumed_resources(weight, 0);
+	BaseFee::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+}
+
+#[test]
+fn fee_starts_at_the_default() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1_000));
+		assert_eq!(BaseFee::min_base_fee(), U256::from(100));
+		assert_eq!(BaseFee::max_base_fee(), U256::from(10_000));
+		assert_eq!(<BaseFee as FeeCalculator>::min_gas_price(), U256::from(1_000));
+	});
+}
+
+#[test]
+fn fee_holds_at_the_target_fullness() {
+	new_test_ext().execute_with(|| {
+		end_block(NORMAL_WEIGHT / 2);
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1_000));
+	});
+}
+
+#[test]
+fn full_blocks_raise_the_fee_by_an_eighth() {
+	new_test_ext().execute_with(|| {
+		end_block(NORMAL_WEIGHT);
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1_125));
+
+		// Three quarters full is halfway from the target to full.
+		end_block(NORMAL_WEIGHT * 3 / 4);
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(1_125 + 70));
+	});
+}
+
+#[test]
+fn empty_blocks_lower_the_fee_by_an_eighth() {
+	new_test_ext().execute_with(|| {
+		end_block(0);
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(875));
+		assert_eq!(<BaseFee as FeeCalculator>::min_gas_price(), U256::from(875));
+	});
+}
+
+#[test]
+fn fee_stays_within_the_bounds() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..100 {
+			end_block(0);
+		}
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(100));
+
+		for _ in 0..100 {
+			end_block(NORMAL_WEIGHT);
+		}
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(10_000));
+	});
+}
+
+#[test]
+fn small_fee_still_rises() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BaseFee::set_bounds(Origin::root(), U256::from(1), U256::from(100)));
+		BaseFeePerGas::<Test>::put(U256::from(5));
+
+		end_block(NORMAL_WEIGHT);
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(6));
+	});
+}
+
+#[test]
+fn set_bounds_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			BaseFee::set_bounds(Origin::signed(1), U256::from(1), U256::from(2)),
+			BadOrigin,
+		);
+		assert_noop!(
+			BaseFee::set_bounds(Origin::root(), U256::from(3), U256::from(2)),
+			Error::<Test>::MinAboveMax,
+		);
+
+		// The fee moves into the new bounds.
+		assert_ok!(BaseFee::set_bounds(Origin::root(), U256::from(2_000), U256::from(3_000)));
+		assert_eq!(BaseFee::min_base_fee(), U256::from(2_000));
+		assert_eq!(BaseFee::max_base_fee(), U256::from(3_000));
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(2_000));
+		assert_eq!(events(), vec![crate::Event::BoundsUpdated(U256::from(2_000), U256::from(3_000))]);
+
+		assert_ok!(BaseFee::set_bounds(Origin::root(), U256::from(10), U256::from(500)));
+		assert_eq!(BaseFee::base_fee_per_gas(), U256::from(500));
+	});
+}
@@ -0,0 +1,220 @@
+//! Bounties pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as Bounties;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{ensure, traits::{Currency, EnsureOrigin, Get, Imbalance}, weights::Weight};
+use frame_system::RawOrigin;
+use pallet_treasury::{BalanceOf, PositiveImbalanceOf, SpendFunds};
+use sp_runtime::traits::{Bounded, Saturating, Zero};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+
+// Create bounties that are approved for use in `on_initialize`.
+fn create_approved_bounties<T: Config>(n: u32) -> Result<(), &'static str> {
+	for i in 0..n {
+		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(i, T::MaximumReasonLength::get());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)
+			.map_err(|_| "failed to propose bounty")?;
+		let bounty_id = BountyCount::<T>::get() - 1;
+		Bounties::<T>::approve_bounty(T::ApproveOrigin::successful_origin(), bounty_id)
+			.map_err(|_| "failed to approve bounty")?;
+	}
+	ensure!(BountyApprovals::<T>::get().len() == n as usize, "Not all bounty approved");
+	Ok(())
+}
+
+// Create the pre-requisite information needed to create a treasury `propose_bounty`.
+fn setup_bounty<T: Config>(u: u32, d: u32)
+	-> (T::AccountId, T::AccountId, BalanceOf<T>, BalanceOf<T>, Vec<u8>)
+{
+	let caller = account("caller", u, SEED);
+	let value: BalanceOf<T> = T::BountyValueMinimum::get().saturating_mul(100u32.into());
+	let fee = value / 2u32.into();
+	let deposit = T::BountyDepositBase::get() + T::DataDepositPerByte::get() * d.into();
+	let _ = T::Currency::make_free_balance_be(&caller, deposit + T::Currency::minimum_balance());
+	let curator = account("curator", u, SEED);
+	let _ = T::Currency::make_free_balance_be(&curator, fee / 2u32.into() + T::Currency::minimum_balance());
+	let reason = vec![0; d as usize];
+	(caller, curator, fee, value, reason)
+}
+
+// Propose, approve and fund a bounty, then propose and accept its curator.
+fn create_bounty<T: Config>() -> Result<(T::AccountId, BountyIndex), &'static str> {
+	let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, T::MaximumReasonLength::get());
+	Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)
+		.map_err(|_| "failed to propose bounty")?;
+	let bounty_id = BountyCount::<T>::get() - 1;
+	Bounties::<T>::approve_bounty(T::ApproveOrigin::successful_origin(), bounty_id)
+		.map_err(|_| "failed to approve bounty")?;
+	fund_approved_bounties::<T>();
+	Bounties::<T>::propose_curator(T::ApproveOrigin::successful_origin(), bounty_id, curator.clone(), fee)
+		.map_err(|_| "failed to propose curator")?;
+	Bounties::<T>::accept_curator(RawOrigin::Signed(curator.clone()).into(), bounty_id)
+		.map_err(|_| "failed to accept curator")?;
+	Ok((curator, bounty_id))
+}
+
+fn setup_pot_account<T: Config>() {
+	let pot_account = pallet_treasury::Pallet::<T>::account_id();
+	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000u32.into());
+	let _ = T::Currency::make_free_balance_be(&pot_account, value);
+}
+
+// Run the bounties' share of a spend period with an unlimited budget.
+fn fund_approved_bounties<T: Config>() {
+	let mut budget_remaining = BalanceOf::<T>::max_value();
+	let mut imbalance = PositiveImbalanceOf::<T>::zero();
+	let mut total_weight: Weight = 0;
+	let mut missed_any = false;
+	Bounties::<T>::spend_funds(&mut budget_remaining, &mut imbalance, &mut total_weight, &mut missed_any);
+}
+
+benchmarks! {
+	propose_bounty {
+		let d in 0 .. T::MaximumReasonLength::get();
+
+		let (caller, _, _, value, description) = setup_bounty::<T>(0, d);
+	}: _(RawOrigin::Signed(caller), value, description)
+	verify {
+		assert_eq!(BountyCount::<T>::get(), 1);
+	}
+
+	approve_bounty {
+		let (caller, _, _, value, reason) = setup_bounty::<T>(0, T::MaximumReasonLength::get());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)
+			.map_err(|_| "failed to propose bounty")?;
+		let bounty_id = BountyCount::<T>::get() - 1;
+		let approve_origin = T::ApproveOrigin::successful_origin();
+	}: _<T::Origin>(approve_origin, bounty_id)
+	verify {
+		assert_eq!(BountyApprovals::<T>::get(), vec![bounty_id]);
+	}
+
+	propose_curator {
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, T::MaximumReasonLength::get());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)
+			.map_err(|_| "failed to propose bounty")?;
+		let bounty_id = BountyCount::<T>::get() - 1;
+		Bounties::<T>::approve_bounty(T::ApproveOrigin::successful_origin(), bounty_id)
+			.map_err(|_| "failed to approve bounty")?;
+		fund_approved_bounties::<T>();
+		let approve_origin = T::ApproveOrigin::successful_origin();
+	}: _<T::Origin>(approve_origin, bounty_id, curator.clone(), fee)
+	verify {
+		let bounty = Bounties::<T>::bounties(bounty_id).expect("bounty exists; qed");
+		assert_eq!(bounty.status, BountyStatus::CuratorProposed { curator });
+	}
+
+	// Worst case: the curator is inactive and gets slashed by someone else.
+	unassign_curator {
+		setup_pot_account::<T>();
+		let (_, bounty_id) = create_bounty::<T>()?;
+		let update_due = frame_system::Pallet::<T>::block_number() + T::BountyUpdatePeriod::get();
+		frame_system::Pallet::<T>::set_block_number(update_due + 1u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), bounty_id)
+	verify {
+		let bounty = Bounties::<T>::bounties(bounty_id).expect("bounty exists; qed");
+		assert_eq!(bounty.status, BountyStatus::Funded);
+	}
+
+	accept_curator {
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, T::MaximumReasonLength::get());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)
+			.map_err(|_| "failed to propose bounty")?;
+		let bounty_id = BountyCount::<T>::get() - 1;
+		Bounties::<T>::approve_bounty(T::ApproveOrigin::successful_origin(), bounty_id)
+			.map_err(|_| "failed to approve bounty")?;
+		fund_approved_bounties::<T>();
+		Bounties::<T>::propose_curator(T::ApproveOrigin::successful_origin(), bounty_id, curator.clone(), fee)
+			.map_err(|_| "failed to propose curator")?;
+	}: _(RawOrigin::Signed(curator), bounty_id)
+	verify {
+		assert!(!Bounties::<T>::bounties(bounty_id).expect("bounty exists; qed").curator_deposit.is_zero());
+	}
+
+	award_bounty {
+		setup_pot_account::<T>();
+		let (curator, bounty_id) = create_bounty::<T>()?;
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+	}: _(RawOrigin::Signed(curator), bounty_id, beneficiary)
+	verify {
+		let bounty = Bounties::<T>::bounties(bounty_id).expect("bounty exists; qed");
+		assert!(matches!(bounty.status, BountyStatus::PendingPayout { .. }));
+	}
+
+	claim_bounty {
+		setup_pot_account::<T>();
+		let (curator, bounty_id) = create_bounty::<T>()?;
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		Bounties::<T>::award_bounty(RawOrigin::Signed(curator.clone()).into(), bounty_id, beneficiary.clone())
+			.map_err(|_| "failed to award bounty")?;
+		let unlock_at = frame_system::Pallet::<T>::block_number() + T::BountyDepositPayoutDelay::get();
+		frame_system::Pallet::<T>::set_block_number(unlock_at);
+		ensure!(T::Currency::free_balance(&beneficiary).is_zero(), "Beneficiary already has balance");
+	}: _(RawOrigin::Signed(curator), bounty_id)
+	verify {
+		ensure!(!T::Currency::free_balance(&beneficiary).is_zero(), "Beneficiary didn't get paid");
+	}
+
+	close_bounty_proposed {
+		setup_pot_account::<T>();
+		let (caller, _, _, value, reason) = setup_bounty::<T>(0, 0);
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)
+			.map_err(|_| "failed to propose bounty")?;
+		let bounty_id = BountyCount::<T>::get() - 1;
+	}: close_bounty(RawOrigin::Root, bounty_id)
+	verify {
+		assert!(Bounties::<T>::bounties(bounty_id).is_none());
+	}
+
+	close_bounty_active {
+		setup_pot_account::<T>();
+		let (_, bounty_id) = create_bounty::<T>()?;
+	}: close_bounty(RawOrigin::Root, bounty_id)
+	verify {
+		assert!(Bounties::<T>::bounties(bounty_id).is_none());
+	}
+
+	extend_bounty_expiry {
+		setup_pot_account::<T>();
+		let (curator, bounty_id) = create_bounty::<T>()?;
+	}: _(RawOrigin::Signed(curator), bounty_id, Vec::new())
+	verify {
+		assert!(Bounties::<T>::bounties(bounty_id).is_some());
+	}
+
+	spend_funds {
+		let b in 1 .. T::MaxApprovals::get();
+		setup_pot_account::<T>();
+		create_approved_bounties::<T>(b)?;
+
+		let mut budget_remaining = BalanceOf::<T>::max_value();
+		let mut imbalance = PositiveImbalanceOf::<T>::zero();
+		let mut total_weight: Weight = 0;
+		let mut missed_any = false;
+	}: {
+		<Bounties<T> as SpendFunds<T>>::spend_funds(
+			&mut budget_remaining,
+			&mut imbalance,
+			&mut total_weight,
+			&mut missed_any,
+		);
+	}
+	verify {
+		ensure!(!missed_any, "Missed some");
+		assert!(BountyApprovals::<T>::get().is_empty());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Bounties,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
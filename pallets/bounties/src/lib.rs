@@ -0,0 +1,645 @@
+//! # Bounties Pallet
+//!
+//! A bounty is a reward for a specified body of work, or specified set of objectives, that needs
+//! to be executed for a predefined treasury amount to be paid out. A curator is assigned after the
+//! bounty is approved and funded by the treasury to be delegated with the responsibility of
+//! assigning a payout address once the objectives are completed.
+//!
+//! A bounty goes through the following life cycle:
+//! - `propose_bounty`: anyone proposes a bounty, reserving a deposit for its description.
+//! - `approve_bounty`: the approve origin queues it for funding at the next spend period.
+//! - `propose_curator` / `accept_curator`: a curator is proposed with a fee and accepts it by
+//!   reserving a deposit proportional to the fee.
+//! - `award_bounty`: the curator names a beneficiary.
+//! - `claim_bounty`: after `BountyDepositPayoutDelay` the beneficiary and curator are paid.
+//!
+//! Bounties are funded through the treasury's `SpendFunds` hook out of its spend period budget,
+//! and cancelled ones are returned with its `reclaim`, so the pot and origins stay the treasury's
+//! own. The flow lives in its own pallet, like tips, so a runtime can run the treasury without it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+
+#[cfg(test)]
+pub(crate) mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[pallet]
+pub mod pallet {
+	use codec::{Decode, Encode};
+	use frame_support::dispatch::DispatchResultWithPostInfo;
+	use frame_support::traits::{
+		Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get, Imbalance, OnUnbalanced,
+		ReservableCurrency,
+	};
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_treasury::{BalanceOf, PositiveImbalanceOf};
+	use sp_runtime::traits::{AccountIdConversion, BadOrigin, Saturating, Zero};
+	use sp_runtime::{Permill, RuntimeDebug};
+	use sp_std::prelude::*;
+	use crate::WeightInfo;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_treasury::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The amount held on deposit for placing a bounty proposal.
+		#[pallet::constant]
+		type BountyDepositBase: Get<BalanceOf<Self>>;
+
+		/// The amount held on deposit per byte within the bounty description.
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The delay period for which a bounty beneficiary need to wait before claim the payout.
+		#[pallet::constant]
+		type BountyDepositPayoutDelay: Get<Self::BlockNumber>;
+
+		/// Bounty duration in blocks.
+		#[pallet::constant]
+		type BountyUpdatePeriod: Get<Self::BlockNumber>;
+
+		/// Percentage of the curator fee that will be reserved upfront as deposit for bounty
+		/// curator.
+		#[pallet::constant]
+		type BountyCuratorDeposit: Get<Permill>;
+
+		/// Minimum value for a bounty.
+		#[pallet::constant]
+		type BountyValueMinimum: Get<BalanceOf<Self>>;
+
+		/// Maximum acceptable reason length.
+		#[pallet::constant]
+		type MaximumReasonLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// An index of a bounty. Just a `u32`.
+	pub type BountyIndex = u32;
+
+	pub type BountyOf<T> = Bounty<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// A bounty proposal.
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct Bounty<AccountId, Balance, BlockNumber> {
+		/// The account proposing it.
+		pub proposer: AccountId,
+		/// The (total) amount that should be paid if the bounty is rewarded.
+		pub value: Balance,
+		/// The curator fee. Included in value.
+		pub fee: Balance,
+		/// The deposit of curator.
+		pub curator_deposit: Balance,
+		/// The amount held on deposit (reserved) for making this proposal.
+		pub bond: Balance,
+		/// The status of this bounty.
+		pub status: BountyStatus<AccountId, BlockNumber>,
+	}
+
+	/// The status of a bounty proposal.
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub enum BountyStatus<AccountId, BlockNumber> {
+		/// The bounty is proposed and waiting for approval.
+		Proposed,
+		/// The bounty is approved and waiting to become active at next spend period.
+		Approved,
+		/// The bounty is funded and waiting for curator assignment.
+		Funded,
+		/// A curator has been proposed by the `ApproveOrigin`. Waiting for acceptance from the
+		/// curator.
+		CuratorProposed {
+			/// The assigned curator of this bounty.
+			curator: AccountId,
+		},
+		/// The bounty is active and waiting to be awarded.
+		Active {
+			/// The curator of this bounty.
+			curator: AccountId,
+			/// An update from the curator is due by this block, else they are considered inactive.
+			update_due: BlockNumber,
+		},
+		/// The bounty is awarded and waiting to released after a delay.
+		PendingPayout {
+			/// The curator of this bounty.
+			curator: AccountId,
+			/// The beneficiary of the bounty.
+			beneficiary: AccountId,
+			/// When the bounty can be claimed.
+			unlock_at: BlockNumber,
+		},
+	}
+
+	/// Number of bounty proposals that have been made.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_count)]
+	pub type BountyCount<T> = StorageValue<_, BountyIndex, ValueQuery>;
+
+	/// Bounties that have been made.
+	#[pallet::storage]
+	#[pallet::getter(fn bounties)]
+	pub type Bounties<T: Config> = StorageMap<_, Twox64Concat, BountyIndex, BountyOf<T>, OptionQuery>;
+
+	/// The description of each bounty.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_descriptions)]
+	pub type BountyDescriptions<T> = StorageMap<_, Twox64Concat, BountyIndex, Vec<u8>, OptionQuery>;
+
+	/// Bounty indices that have been approved but not yet funded.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_approvals)]
+	pub type BountyApprovals<T> = StorageValue<_, Vec<BountyIndex>, ValueQuery>;
+
+	/// Error for the bounties module.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Proposer's balance is too low.
+		InsufficientProposersBalance,
+		/// No proposal or bounty at that index.
+		InvalidIndex,
+		/// The reason given is just too big.
+		ReasonTooBig,
+		/// The bounty status is unexpected.
+		UnexpectedStatus,
+		/// Require bounty curator.
+		RequireCurator,
+		/// Invalid bounty value.
+		InvalidValue,
+		/// Invalid bounty fee.
+		InvalidFee,
+		/// A bounty payout is pending. To cancel the bounty, you must unassign and slash the
+		/// curator.
+		PendingPayout,
+		/// The bounties cannot be claimed/closed because it's still in the countdown period.
+		Premature,
+		/// Too many bounties are already queued for funding.
+		TooManyQueued,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// New bounty proposal. \[index\]
+		BountyProposed(BountyIndex),
+		/// A bounty proposal was rejected; funds were slashed. \[index, bond\]
+		BountyRejected(BountyIndex, BalanceOf<T>),
+		/// A bounty proposal is funded and became active. \[index\]
+		BountyBecameActive(BountyIndex),
+		/// A bounty is awarded to a beneficiary. \[index, beneficiary\]
+		BountyAwarded(BountyIndex, T::AccountId),
+		/// A bounty is claimed by beneficiary. \[index, payout, beneficiary\]
+		BountyClaimed(BountyIndex, BalanceOf<T>, T::AccountId),
+		/// A bounty is cancelled. \[index\]
+		BountyCanceled(BountyIndex),
+		/// A bounty expiry is extended. \[index\]
+		BountyExtended(BountyIndex),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Propose a new bounty.
+		///
+		/// Payment: `BountyDepositBase` plus `DataDepositPerByte` for each byte of `description`
+		/// will be reserved from the origin account, and returned once the bounty is funded, or
+		/// slashed if it is rejected.
+		#[pallet::weight(<T as Config>::WeightInfo::propose_bounty(description.len() as u32))]
+		pub fn propose_bounty(
+			origin: OriginFor<T>,
+			value: BalanceOf<T>,
+			description: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let proposer = ensure_signed(origin)?;
+
+			ensure!(
+				description.len() <= T::MaximumReasonLength::get() as usize,
+				Error::<T>::ReasonTooBig,
+			);
+			ensure!(value >= T::BountyValueMinimum::get(), Error::<T>::InvalidValue);
+
+			let bond = T::BountyDepositBase::get()
+				+ T::DataDepositPerByte::get() * (description.len() as u32).into();
+			T::Currency::reserve(&proposer, bond)
+				.map_err(|_| Error::<T>::InsufficientProposersBalance)?;
+
+			let index = Self::bounty_count();
+			BountyCount::<T>::put(index + 1);
+			Bounties::<T>::insert(index, Bounty {
+				proposer,
+				value,
+				fee: Zero::zero(),
+				curator_deposit: Zero::zero(),
+				bond,
+				status: BountyStatus::Proposed,
+			});
+			BountyDescriptions::<T>::insert(index, description);
+
+			Self::deposit_event(Event::BountyProposed(index));
+			Ok(Default::default())
+		}
+
+		/// Approve a bounty proposal. At a later time, the bounty will be funded and become
+		/// active and the original deposit will be returned.
+		///
+		/// May only be called from the treasury's `ApproveOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::approve_bounty())]
+		pub fn approve_bounty(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+		) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+				ensure!(bounty.status == BountyStatus::Proposed, Error::<T>::UnexpectedStatus);
+
+				BountyApprovals::<T>::try_mutate(|approvals| -> DispatchResult {
+					ensure!(
+						(approvals.len() as u32) < T::MaxApprovals::get(),
+						Error::<T>::TooManyQueued,
+					);
+					approvals.push(bounty_id);
+					Ok(())
+				})?;
+				bounty.status = BountyStatus::Approved;
+
+				Ok(())
+			})?;
+
+			Ok(Default::default())
+		}
+
+		/// Assign a curator to a funded bounty.
+		///
+		/// May only be called from the treasury's `ApproveOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::propose_curator())]
+		pub fn propose_curator(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+			curator: T::AccountId,
+			fee: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+				ensure!(bounty.status == BountyStatus::Funded, Error::<T>::UnexpectedStatus);
+				ensure!(fee < bounty.value, Error::<T>::InvalidFee);
+
+				bounty.status = BountyStatus::CuratorProposed { curator };
+				bounty.fee = fee;
+
+				Ok(())
+			})?;
+
+			Ok(Default::default())
+		}
+
+		/// Unassign curator from a bounty.
+		///
+		/// This function can only be called by the `RejectOrigin` or a signed origin.
+		///
+		/// If this function is called by the `RejectOrigin`, we assume that the curator is
+		/// malicious or inactive. As a result, we will slash the curator when possible.
+		///
+		/// If the origin is the curator, we take this as a sign they are unable to do their job
+		/// and they willingly give up. We could slash them, but for now we allow them to recover
+		/// their deposit and exit without issue. (We may want to change this if it is abused.)
+		///
+		/// Finally, the origin can be anyone if and only if the curator is "inactive". This allows
+		/// anyone in the community to call out that a curator is not doing their due diligence,
+		/// and we should pick a new curator. In this case the curator should also be slashed.
+		#[pallet::weight(<T as Config>::WeightInfo::unassign_curator())]
+		pub fn unassign_curator(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+		) -> DispatchResultWithPostInfo {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+
+				let slash_curator = |curator: &T::AccountId, curator_deposit: &mut BalanceOf<T>| {
+					let imbalance = T::Currency::slash_reserved(curator, *curator_deposit).0;
+					pallet_treasury::Pallet::<T>::on_unbalanced(imbalance);
+					*curator_deposit = Zero::zero();
+				};
+
+				match bounty.status {
+					BountyStatus::Proposed | BountyStatus::Approved | BountyStatus::Funded => {
+						// No curator to unassign at this point.
+						return Err(Error::<T>::UnexpectedStatus.into())
+					},
+					BountyStatus::CuratorProposed { ref curator } => {
+						// A curator has been proposed, but not accepted yet. Either `RejectOrigin`
+						// or the proposed curator can unassign the curator.
+						ensure!(maybe_sender.map_or(true, |sender| sender == *curator), BadOrigin);
+					},
+					BountyStatus::Active { ref curator, ref update_due } => {
+						match maybe_sender {
+							// If the `RejectOrigin` is calling this function, slash the curator.
+							None => slash_curator(curator, &mut bounty.curator_deposit),
+							Some(sender) => {
+								if sender != *curator {
+									// Anyone can unassign a curator whose update is overdue.
+									let now = frame_system::Pallet::<T>::block_number();
+									ensure!(*update_due < now, Error::<T>::Premature);
+									slash_curator(curator, &mut bounty.curator_deposit);
+								} else {
+									// The curator gives up; they get their deposit back.
+									T::Currency::unreserve(curator, bounty.curator_deposit);
+									bounty.curator_deposit = Zero::zero();
+								}
+							},
+						}
+					},
+					BountyStatus::PendingPayout { ref curator, .. } => {
+						// The bounty is pending payout, so only the `RejectOrigin` can unassign
+						// the curator. By doing so, they are claiming the curator is acting
+						// maliciously, so we slash the curator.
+						ensure!(maybe_sender.is_none(), BadOrigin);
+						slash_curator(curator, &mut bounty.curator_deposit);
+						// Continue to change bounty status below...
+					},
+				};
+
+				bounty.status = BountyStatus::Funded;
+				Ok(())
+			})?;
+
+			Ok(Default::default())
+		}
+
+		/// Accept the curator role for a bounty. A deposit will be reserved from the curator and
+		/// refunded upon successful payout.
+		///
+		/// May only be called from the proposed curator.
+		#[pallet::weight(<T as Config>::WeightInfo::accept_curator())]
+		pub fn accept_curator(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+		) -> DispatchResultWithPostInfo {
+			let signer = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+
+				match bounty.status {
+					BountyStatus::CuratorProposed { ref curator } => {
+						ensure!(signer == *curator, Error::<T>::RequireCurator);
+
+						let deposit = T::BountyCuratorDeposit::get() * bounty.fee;
+						T::Currency::reserve(curator, deposit)?;
+						bounty.curator_deposit = deposit;
+
+						let update_due = frame_system::Pallet::<T>::block_number()
+							+ T::BountyUpdatePeriod::get();
+						bounty.status = BountyStatus::Active { curator: curator.clone(), update_due };
+
+						Ok(())
+					},
+					_ => Err(Error::<T>::UnexpectedStatus.into()),
+				}
+			})?;
+
+			Ok(Default::default())
+		}
+
+		/// Award bounty to a beneficiary account. The beneficiary will be able to claim the funds
+		/// after a delay.
+		///
+		/// The dispatch origin for this call must be the curator of this bounty.
+		#[pallet::weight(<T as Config>::WeightInfo::award_bounty())]
+		pub fn award_bounty(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+			beneficiary: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let signer = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+				match &bounty.status {
+					BountyStatus::Active { curator, .. } => {
+						ensure!(signer == *curator, Error::<T>::RequireCurator);
+					},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				}
+				bounty.status = BountyStatus::PendingPayout {
+					curator: signer,
+					beneficiary: beneficiary.clone(),
+					unlock_at: frame_system::Pallet::<T>::block_number()
+						+ T::BountyDepositPayoutDelay::get(),
+				};
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::BountyAwarded(bounty_id, beneficiary));
+			Ok(Default::default())
+		}
+
+		/// Claim the payout from an awarded bounty after payout delay.
+		///
+		/// The dispatch origin for this call may be any signed origin.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_bounty())]
+		pub fn claim_bounty(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.take().ok_or(Error::<T>::InvalidIndex)?;
+				if let BountyStatus::PendingPayout { curator, beneficiary, unlock_at } = bounty.status {
+					ensure!(
+						frame_system::Pallet::<T>::block_number() >= unlock_at,
+						Error::<T>::Premature,
+					);
+					let bounty_account = Self::bounty_account_id(bounty_id);
+					let balance = T::Currency::free_balance(&bounty_account);
+					let fee = bounty.fee.min(balance); // just to be safe
+					let payout = balance.saturating_sub(fee);
+					T::Currency::unreserve(&curator, bounty.curator_deposit);
+					// should not fail
+					let _ = T::Currency::transfer(&bounty_account, &curator, fee, AllowDeath);
+					// should not fail
+					let _ = T::Currency::transfer(&bounty_account, &beneficiary, payout, AllowDeath);
+					BountyDescriptions::<T>::remove(bounty_id);
+
+					Self::deposit_event(Event::BountyClaimed(bounty_id, payout, beneficiary));
+					Ok(())
+				} else {
+					Err(Error::<T>::UnexpectedStatus.into())
+				}
+			})?;
+
+			Ok(Default::default())
+		}
+
+		/// Cancel a proposed or active bounty. All the funds will be sent to treasury and the
+		/// curator deposit will be unreserved if possible.
+		///
+		/// Only the treasury's `RejectOrigin` is able to cancel a bounty.
+		#[pallet::weight(<T as Config>::WeightInfo::close_bounty_proposed()
+			.max(<T as Config>::WeightInfo::close_bounty_active()))]
+		pub fn close_bounty(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+		) -> DispatchResultWithPostInfo {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResultWithPostInfo {
+				let bounty = maybe_bounty.as_ref().ok_or(Error::<T>::InvalidIndex)?;
+
+				match &bounty.status {
+					BountyStatus::Proposed => {
+						// The reject origin would like to cancel a proposed bounty.
+						BountyDescriptions::<T>::remove(bounty_id);
+						let value = bounty.bond;
+						let imbalance = T::Currency::slash_reserved(&bounty.proposer, value).0;
+						pallet_treasury::Pallet::<T>::on_unbalanced(imbalance);
+						*maybe_bounty = None;
+
+						Self::deposit_event(Event::BountyRejected(bounty_id, value));
+						// Return early, nothing else to do.
+						return Ok(Some(<T as Config>::WeightInfo::close_bounty_proposed()).into())
+					},
+					BountyStatus::Approved => {
+						// For weight reasons, we don't allow a council to cancel in this phase.
+						// We ask for them to wait until it is funded before they can cancel.
+						return Err(Error::<T>::UnexpectedStatus.into())
+					},
+					BountyStatus::Funded | BountyStatus::CuratorProposed { .. } => {
+						// Nothing extra to do besides the removal of the bounty below.
+					},
+					BountyStatus::Active { curator, .. } => {
+						// Cancelled by council, refund deposit of the working curator.
+						T::Currency::unreserve(curator, bounty.curator_deposit);
+						// Then execute removal of the bounty below.
+					},
+					BountyStatus::PendingPayout { .. } => {
+						// Bounty is already pending payout. If council wants to cancel
+						// this bounty, it should mean the curator was acting maliciously.
+						// So the council should first unassign the curator, slashing their
+						// deposit.
+						return Err(Error::<T>::PendingPayout.into())
+					},
+				}
+
+				let bounty_account = Self::bounty_account_id(bounty_id);
+
+				BountyDescriptions::<T>::remove(bounty_id);
+
+				pallet_treasury::Pallet::<T>::reclaim(&bounty_account);
+				*maybe_bounty = None;
+
+				Self::deposit_event(Event::BountyCanceled(bounty_id));
+				Ok(Some(<T as Config>::WeightInfo::close_bounty_active()).into())
+			})
+		}
+
+		/// Extend the expiry time of an active bounty.
+		///
+		/// The dispatch origin for this call must be the curator of this bounty.
+		#[pallet::weight(<T as Config>::WeightInfo::extend_bounty_expiry())]
+		pub fn extend_bounty_expiry(
+			origin: OriginFor<T>,
+			bounty_id: BountyIndex,
+			_remark: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let signer = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+
+				match bounty.status {
+					BountyStatus::Active { ref curator, ref mut update_due } => {
+						ensure!(*curator == signer, Error::<T>::RequireCurator);
+						*update_due = (frame_system::Pallet::<T>::block_number()
+							+ T::BountyUpdatePeriod::get()).max(*update_due);
+					},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				}
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::BountyExtended(bounty_id));
+			Ok(Default::default())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account ID of a bounty account.
+		pub fn bounty_account_id(id: BountyIndex) -> T::AccountId {
+			// only use two byte prefix to support 16 byte account id (used by test)
+			// "modl" ++ "Treasury" ++ "bt" is 14 bytes, and two bytes remaining for bounty index
+			T::PalletId::get().into_sub_account(("bt", id))
+		}
+	}
+
+	/// Funds approved bounties from the treasury's budget at each spend period.
+	impl<T: Config> pallet_treasury::SpendFunds<T> for Pallet<T> {
+		fn spend_funds(
+			budget_remaining: &mut BalanceOf<T>,
+			imbalance: &mut PositiveImbalanceOf<T>,
+			total_weight: &mut Weight,
+			missed_any: &mut bool,
+		) {
+			let approvals = BountyApprovals::<T>::get();
+			let approvals_len = approvals.len() as u32;
+			let remaining = approvals.into_iter().filter(|&index| {
+				Bounties::<T>::mutate(index, |maybe_bounty| {
+					let bounty = match maybe_bounty {
+						Some(bounty) => bounty,
+						None => return false,
+					};
+					if bounty.value > *budget_remaining {
+						*missed_any = true;
+						return true;
+					}
+
+					*budget_remaining = budget_remaining.saturating_sub(bounty.value);
+					bounty.status = BountyStatus::Funded;
+					// return their deposit
+					T::Currency::unreserve(&bounty.proposer, bounty.bond);
+					// fund the bounty account
+					imbalance.subsume(T::Currency::deposit_creating(
+						&Self::bounty_account_id(index),
+						bounty.value,
+					));
+
+					Self::deposit_event(Event::BountyBecameActive(index));
+					false
+				})
+			}).collect::<Vec<_>>();
+			BountyApprovals::<T>::put(remaining);
+
+			*total_weight += <T as Config>::WeightInfo::spend_funds(approvals_len);
+		}
+	}
+}
@@ -0,0 +1,272 @@
+use crate::*;
+use frame_support::{assert_noop, assert_ok, traits::{Currency, Get}};
+use sp_runtime::traits::BadOrigin;
+use mock::*;
+
+// Put `value` into the treasury pot on top of its existential deposit.
+fn fund_treasury(value: Balance) {
+	Balances::make_free_balance_be(&Treasury::account_id(), value + ExistentialDeposit::get());
+}
+
+// Propose and approve a bounty of `value` from account 0 and fund it at the next spend period.
+fn create_funded_bounty(value: Balance) -> BountyIndex {
+	let index = Bounties::bounty_count();
+	assert_ok!(Bounties::propose_bounty(Origin::signed(0), value, b"12345".to_vec()));
+	assert_ok!(Bounties::approve_bounty(Origin::root(), index));
+	run_to_block(System::block_number() + SpendPeriod::get() - System::block_number() % SpendPeriod::get());
+	assert_eq!(Bounties::bounties(index).unwrap().status, BountyStatus::Funded);
+	index
+}
+
+// Make account 4 the active curator of `index` with `fee`.
+fn assign_curator(index: BountyIndex, fee: Balance) {
+	Balances::make_free_balance_be(&4, 10);
+	assert_ok!(Bounties::propose_curator(Origin::root(), index, 4, fee));
+	assert_ok!(Bounties::accept_curator(Origin::signed(4), index));
+}
+
+#[test]
+fn propose_bounty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"1234567890".to_vec()));
+
+		let deposit = BountyDepositBase::get() + 10 * DataDepositPerByte::get();
+		assert_eq!(Balances::reserved_balance(0), deposit);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+		assert_eq!(Bounties::bounty_count(), 1);
+		assert_eq!(Bounties::bounty_descriptions(0), Some(b"1234567890".to_vec()));
+		assert_eq!(
+			Bounties::bounties(0),
+			Some(Bounty {
+				proposer: 0,
+				value: 10,
+				fee: 0,
+				curator_deposit: 0,
+				bond: deposit,
+				status: BountyStatus::Proposed,
+			})
+		);
+		assert_eq!(events(), vec![crate::Event::BountyProposed(0)]);
+	});
+}
+
+#[test]
+fn propose_bounty_validation_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(1), 0, Vec::new()),
+			Error::<Test>::InvalidValue,
+		);
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(1), 10, vec![0; 16385]),
+			Error::<Test>::ReasonTooBig,
+		);
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(2), 10, b"12345".to_vec()),
+			Error::<Test>::InsufficientProposersBalance,
+		);
+	});
+}
+
+#[test]
+fn close_proposed_bounty_slashes_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"12345".to_vec()));
+		assert_noop!(Bounties::close_bounty(Origin::signed(0), 0), BadOrigin);
+		assert_ok!(Bounties::close_bounty(Origin::root(), 0));
+
+		let deposit = BountyDepositBase::get() + 5 * DataDepositPerByte::get();
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+		assert_eq!(Balances::free_balance(Treasury::account_id()), deposit);
+		assert_eq!(Bounties::bounties(0), None);
+		assert_eq!(Bounties::bounty_descriptions(0), None);
+		assert_eq!(events().last(), Some(&crate::Event::BountyRejected(0, deposit)));
+	});
+}
+
+#[test]
+fn approve_bounty_funds_at_spend_period() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+		assert_noop!(Bounties::approve_bounty(Origin::signed(0), 0), BadOrigin);
+		assert_noop!(Bounties::approve_bounty(Origin::root(), 1), Error::<Test>::InvalidIndex);
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+		assert_noop!(Bounties::approve_bounty(Origin::root(), 0), Error::<Test>::UnexpectedStatus);
+
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Approved);
+		assert_eq!(Bounties::bounty_approvals(), vec![0]);
+		// Cancelling is not possible until the bounty is funded.
+		assert_noop!(Bounties::close_bounty(Origin::root(), 0), Error::<Test>::UnexpectedStatus);
+
+		run_to_block(2);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert!(Bounties::bounty_approvals().is_empty());
+		// The deposit is returned and the bounty account holds the value.
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+		assert_eq!(Treasury::pot(), 50);
+		assert_eq!(events().last(), Some(&crate::Event::BountyBecameActive(0)));
+	});
+}
+
+#[test]
+fn approved_bounty_waits_for_funds() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(20);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		run_to_block(2);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Approved);
+		assert_eq!(Bounties::bounty_approvals(), vec![0]);
+
+		fund_treasury(50);
+		run_to_block(4);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+	});
+}
+
+#[test]
+fn assign_curator_works() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		let index = create_funded_bounty(50);
+
+		assert_noop!(
+			Bounties::propose_curator(Origin::root(), index, 4, 50),
+			Error::<Test>::InvalidFee,
+		);
+		assert_ok!(Bounties::propose_curator(Origin::root(), index, 4, 4));
+		assert_eq!(
+			Bounties::bounties(index).unwrap().status,
+			BountyStatus::CuratorProposed { curator: 4 },
+		);
+
+		assert_noop!(Bounties::accept_curator(Origin::signed(1), index), Error::<Test>::RequireCurator);
+		// The curator cannot cover the deposit yet.
+		assert!(Bounties::accept_curator(Origin::signed(4), index).is_err());
+
+		Balances::make_free_balance_be(&4, 10);
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), index));
+		let bounty = Bounties::bounties(index).unwrap();
+		assert_eq!(bounty.curator_deposit, 2);
+		assert_eq!(bounty.status, BountyStatus::Active { curator: 4, update_due: 22 });
+		assert_eq!(Balances::reserved_balance(4), 2);
+	});
+}
+
+#[test]
+fn unassign_curator_works() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		let index = create_funded_bounty(50);
+		assign_curator(index, 4);
+
+		// Others have to wait for the curator to become inactive.
+		assert_noop!(Bounties::unassign_curator(Origin::signed(1), index), Error::<Test>::Premature);
+
+		// The curator may step down and keeps their deposit.
+		assert_ok!(Bounties::unassign_curator(Origin::signed(4), index));
+		assert_eq!(Bounties::bounties(index).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(4), 10);
+
+		// The reject origin slashes the curator.
+		assign_curator(index, 4);
+		let pot = Treasury::pot();
+		assert_ok!(Bounties::unassign_curator(Origin::root(), index));
+		assert_eq!(Bounties::bounties(index).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(4), 8);
+		assert_eq!(Treasury::pot(), pot + 2);
+	});
+}
+
+#[test]
+fn anyone_can_unassign_inactive_curator() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		let index = create_funded_bounty(50);
+		assign_curator(index, 4);
+
+		System::set_block_number(23);
+		assert_ok!(Bounties::unassign_curator(Origin::signed(1), index));
+		assert_eq!(Bounties::bounties(index).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(4), 8);
+	});
+}
+
+#[test]
+fn award_and_claim_bounty_works() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		let index = create_funded_bounty(50);
+		assign_curator(index, 4);
+
+		assert_noop!(
+			Bounties::award_bounty(Origin::signed(1), index, 3),
+			Error::<Test>::RequireCurator,
+		);
+		assert_ok!(Bounties::award_bounty(Origin::signed(4), index, 3));
+		assert_eq!(
+			Bounties::bounties(index).unwrap().status,
+			BountyStatus::PendingPayout { curator: 4, beneficiary: 3, unlock_at: 5 },
+		);
+		assert_noop!(
+			Bounties::close_bounty(Origin::root(), index),
+			Error::<Test>::PendingPayout,
+		);
+		assert_noop!(Bounties::claim_bounty(Origin::signed(1), index), Error::<Test>::Premature);
+
+		run_to_block(5);
+		assert_ok!(Bounties::claim_bounty(Origin::signed(1), index));
+
+		assert_eq!(Balances::free_balance(3), 46);
+		assert_eq!(Balances::free_balance(4), 14);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(index)), 0);
+		assert_eq!(Bounties::bounties(index), None);
+		assert_eq!(Bounties::bounty_descriptions(index), None);
+		assert_eq!(events().last(), Some(&crate::Event::BountyClaimed(index, 46, 3)));
+	});
+}
+
+#[test]
+fn close_active_bounty_returns_funds() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		let index = create_funded_bounty(50);
+		assign_curator(index, 4);
+
+		let pot = Treasury::pot();
+		assert_ok!(Bounties::close_bounty(Origin::root(), index));
+		assert_eq!(Treasury::pot(), pot + 50);
+		assert_eq!(Balances::free_balance(4), 10);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Bounties::bounties(index), None);
+		assert_eq!(events().last(), Some(&crate::Event::BountyCanceled(index)));
+	});
+}
+
+#[test]
+fn extend_bounty_expiry_works() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		let index = create_funded_bounty(50);
+		assign_curator(index, 4);
+
+		assert_noop!(
+			Bounties::extend_bounty_expiry(Origin::signed(1), index, Vec::new()),
+			Error::<Test>::RequireCurator,
+		);
+		System::set_block_number(10);
+		assert_ok!(Bounties::extend_bounty_expiry(Origin::signed(4), index, Vec::new()));
+		assert_eq!(
+			Bounties::bounties(index).unwrap().status,
+			BountyStatus::Active { curator: 4, update_due: 30 },
+		);
+		assert_eq!(events().last(), Some(&crate::Event::BountyExtended(index)));
+	});
+}
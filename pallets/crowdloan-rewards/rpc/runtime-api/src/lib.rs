@@ -0,0 +1,51 @@
+//! Runtime API definition for the crowdloan rewards pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Reward of a relay account in one campaign, as seen at a given block.
+#[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RewardStatus<Balance, BlockNumber> {
+	/// Whole reward of the contribution
+	pub total_reward: Balance,
+	/// Reward paid out so far
+	pub claimed_reward: Balance,
+	/// Reward vested by the block
+	pub vested_reward: Balance,
+	/// Amount a claim in the block would pay out
+	pub claimable_reward: Balance,
+	/// Block at which the whole reward is vested
+	pub end_block: BlockNumber,
+	/// Last block in which the reward can be claimed
+	pub claim_deadline: BlockNumber,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait CrowdloanRewardsApi<AccountId, RelayChainAccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		RelayChainAccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Relay account `account` is associated with.
+		fn associated_relay_account(account: AccountId) -> Option<RelayChainAccountId>;
+
+		/// Native accounts associated with `relay_account`.
+		fn associated_accounts(relay_account: RelayChainAccountId) -> Vec<AccountId>;
+
+		/// Reward of `relay_account` in `campaign_id` at block `at`, `None` if it did not
+		/// contribute to the campaign.
+		fn reward_status(
+			campaign_id: u32,
+			relay_account: RelayChainAccountId,
+			at: BlockNumber,
+		) -> Option<RewardStatus<Balance, BlockNumber>>;
+	}
+}
@@ -0,0 +1,146 @@
+//! RPC interface for the crowdloan rewards pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, One, Saturating},
+};
+
+pub use pallet_crowdloan_rewards_rpc_runtime_api::{
+	CrowdloanRewardsApi as CrowdloanRewardsRuntimeApi, RewardStatus,
+};
+
+#[rpc]
+pub trait CrowdloanRewardsApi<BlockHash, AccountId, RelayChainAccountId, Balance, BlockNumber> {
+	/// Relay account `account` is associated with.
+	#[rpc(name = "crowdloan_associatedRelayAccount")]
+	fn associated_relay_account(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Option<RelayChainAccountId>>;
+
+	/// Native accounts associated with `relay_account`.
+	#[rpc(name = "crowdloan_associatedAccounts")]
+	fn associated_accounts(
+		&self,
+		relay_account: RelayChainAccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<AccountId>>;
+
+	/// Reward of `relay_account` in `campaign_id`.
+	///
+	/// Amounts are computed for `block_number`, which defaults to the block after `at`, i.e. the
+	/// block a claim submitted now would land in.
+	#[rpc(name = "crowdloan_rewardStatus")]
+	fn reward_status(
+		&self,
+		campaign_id: u32,
+		relay_account: RelayChainAccountId,
+		block_number: Option<BlockNumber>,
+		at: Option<BlockHash>,
+	) -> Result<Option<RewardStatus<Balance, BlockNumber>>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The block could not be found.
+	UnknownBlock,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::UnknownBlock => 2,
+		}
+	}
+}
+
+/// Implements the [`CrowdloanRewardsApi`] RPC trait for interacting with crowdloan rewards.
+pub struct CrowdloanRewards<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> CrowdloanRewards<C, B> {
+	/// Create new `CrowdloanRewards` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query crowdloan rewards.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, RelayChainAccountId, Balance>
+	CrowdloanRewardsApi<<Block as BlockT>::Hash, AccountId, RelayChainAccountId, Balance, NumberFor<Block>>
+	for CrowdloanRewards<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: CrowdloanRewardsRuntimeApi<Block, AccountId, RelayChainAccountId, Balance, NumberFor<Block>>,
+	AccountId: Codec,
+	RelayChainAccountId: Codec,
+	Balance: Codec,
+{
+	fn associated_relay_account(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<RelayChainAccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.associated_relay_account(&at, account).map_err(runtime_error)
+	}
+
+	fn associated_accounts(
+		&self,
+		relay_account: RelayChainAccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.associated_accounts(&at, relay_account).map_err(runtime_error)
+	}
+
+	fn reward_status(
+		&self,
+		campaign_id: u32,
+		relay_account: RelayChainAccountId,
+		block_number: Option<NumberFor<Block>>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<RewardStatus<Balance, NumberFor<Block>>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let block_number = match block_number {
+			Some(block_number) => block_number,
+			None => self.client.number(at_hash)
+				.map_err(runtime_error)?
+				.ok_or_else(|| RpcError {
+					code: ErrorCode::ServerError(Error::UnknownBlock.into()),
+					message: "Unknown block.".into(),
+					data: None,
+				})?
+				.saturating_add(One::one()),
+		};
+
+		api.reward_status(&BlockId::hash(at_hash), campaign_id, relay_account, block_number)
+			.map_err(runtime_error)
+	}
+}
@@ -0,0 +1,190 @@
+//! Benchmarks for the crowdloan rewards pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as Crowdloan;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use pallet_evm::AddressMapping;
+use sp_core::{crypto::{AccountId32, KeyTypeId}, ecdsa, sr25519, H160};
+use sp_runtime::{traits::{Bounded, Zero}, MultiSignature, Perbill};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+const MAX_CONTRIBUTORS: u32 = 1_000;
+const MAX_CAMPAIGNS: u32 = 50;
+const CONTRIBUTION: u32 = 1_000_000;
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"crwd");
+
+fn relay_account<T: Config>(index: u32) -> T::RelayChainAccountId
+	where T::RelayChainAccountId: From<AccountId32>
+{
+	AccountId32::from(sp_io::hashing::blake2_256(&index.encode())).into()
+}
+
+/// A fresh relay key and its signature over `payload`.
+fn signed_by_relay_account<T: Config>(payload: &[u8]) -> (T::RelayChainAccountId, MultiSignature)
+	where T::RelayChainAccountId: From<AccountId32>
+{
+	let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+	(AccountId32::from(public).into(), sign_by_relay_account(&public, payload))
+}
+
+fn sign_by_relay_account(public: &sr25519::Public, payload: &[u8]) -> MultiSignature {
+	sp_io::crypto::sr25519_sign(KEY_TYPE, public, payload)
+		.expect("key was generated in the keystore; qed")
+		.into()
+}
+
+/// Sign `message` the way `personal_sign` does.
+fn eth_sign(secret: &secp256k1::SecretKey, message: &[u8]) -> ecdsa::Signature {
+	let hash = crate::eth::eip191_hash(message);
+	let (signature, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(&hash), secret);
+	let mut raw = [0u8; 65];
+	raw[0..64].copy_from_slice(&signature.serialize());
+	raw[64] = recovery_id.serialize() + 27;
+	ecdsa::Signature::from_raw(raw)
+}
+
+fn fund_treasury<T: Config>() {
+	T::Currency::make_free_balance_be(
+		&pallet_treasury::Pallet::<T>::account_id(),
+		BalanceOf::<T>::max_value() / 2u32.into(),
+	);
+}
+
+fn create_campaign<T: Config>(
+	contributions: Vec<(T::RelayChainAccountId, u32)>,
+	payout: PayoutMode,
+) -> Result<T::BlockNumber, &'static str> {
+	let end_block = frame_system::Pallet::<T>::block_number() + 100u32.into();
+	Crowdloan::<T>::initialize_reward(
+		RawOrigin::Root.into(),
+		contributions,
+		1,
+		end_block,
+		Perbill::from_percent(10),
+		None,
+		None,
+		Some(payout),
+	).map_err(|_| "failed to initialize reward")?;
+	Ok(end_block)
+}
+
+benchmarks! {
+	where_clause { where T::RelayChainAccountId: From<AccountId32> }
+
+	initialize_reward {
+		let c in 1 .. MAX_CONTRIBUTORS;
+		let contributions = (0..c)
+			.map(|i| (relay_account::<T>(i), CONTRIBUTION))
+			.collect::<Vec<_>>();
+		let end_block = frame_system::Pallet::<T>::block_number() + 100u32.into();
+	}: _(RawOrigin::Root, contributions, 1, end_block, Perbill::from_percent(10), None, None, None)
+	verify {
+		assert_eq!(Contributors::<T>::iter_prefix(0u32).count(), c as usize);
+	}
+
+	associate_account {
+		let caller: T::AccountId = whitelisted_caller();
+		let (relay_account, proof) = signed_by_relay_account::<T>(&Crowdloan::<T>::native_association_message(&caller));
+	}: _(RawOrigin::Signed(caller.clone()), relay_account.clone(), proof)
+	verify {
+		assert_eq!(AssociatedAccount::<T>::get(&caller), Some(relay_account));
+	}
+
+	associate_eth_account {
+		let caller: T::AccountId = whitelisted_caller();
+		let secret = secp256k1::SecretKey::parse(&[1u8; 32]).expect("valid secret key; qed");
+		let public = secp256k1::PublicKey::from_secret_key(&secret).serialize();
+		let eth_address = H160::from_slice(&sp_io::hashing::keccak_256(&public[1..])[12..]);
+
+		let relay_public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let relay_account: T::RelayChainAccountId = AccountId32::from(relay_public).into();
+		let message = Crowdloan::<T>::eth_association_message(&relay_account, &eth_address);
+		let proof = sign_by_relay_account(&relay_public, &message);
+		let eth_proof = eth_sign(&secret, &message);
+	}: _(RawOrigin::Signed(caller), relay_account.clone(), eth_address, proof, eth_proof)
+	verify {
+		let who = T::AddressMapping::into_account_id(eth_address);
+		assert_eq!(AssociatedAccount::<T>::get(&who), Some(relay_account));
+	}
+
+	update_associate_account {
+		let old: T::AccountId = account("old", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let relay_public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let relay_account: T::RelayChainAccountId = AccountId32::from(relay_public).into();
+		Crowdloan::<T>::associate_account(
+			RawOrigin::Signed(old.clone()).into(),
+			relay_account.clone(),
+			sign_by_relay_account(&relay_public, &Crowdloan::<T>::native_association_message(&old)),
+		).map_err(|_| "failed to associate account")?;
+		let proof = sign_by_relay_account(&relay_public, &Crowdloan::<T>::native_association_message(&caller));
+	}: _(RawOrigin::Signed(caller.clone()), old.clone(), relay_account.clone(), proof)
+	verify {
+		assert_eq!(AssociatedAccount::<T>::get(&caller), Some(relay_account));
+		assert_eq!(AssociatedAccount::<T>::get(&old), None);
+	}
+
+	// Worst case: the whole remaining reward is transferred and partly locked.
+	claim {
+		fund_treasury::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let relay_account = relay_account::<T>(0);
+		AssociatedAccount::<T>::insert(&caller, &relay_account);
+		let end_block = create_campaign::<T>(
+			vec![(relay_account.clone(), CONTRIBUTION)],
+			PayoutMode::VestingLock,
+		)?;
+		frame_system::Pallet::<T>::set_block_number(end_block - 50u32.into());
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		let info = Contributors::<T>::get(0u32, &relay_account).expect("contributed; qed");
+		assert_eq!(info.claimed_reward, info.total_reward);
+	}
+
+	claim_all {
+		let n in 1 .. MAX_CAMPAIGNS;
+		fund_treasury::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let relay_account = relay_account::<T>(0);
+		AssociatedAccount::<T>::insert(&caller, &relay_account);
+		let mut end_block = Zero::zero();
+		for _ in 0..n {
+			end_block = create_campaign::<T>(
+				vec![(relay_account.clone(), CONTRIBUTION)],
+				PayoutMode::Transfer,
+			)?;
+		}
+		frame_system::Pallet::<T>::set_block_number(end_block - 50u32.into());
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		for campaign_id in 0..n {
+			let info = Contributors::<T>::get(campaign_id, &relay_account).expect("contributed; qed");
+			assert!(!info.claimed_reward.is_zero());
+		}
+	}
+
+	sweep_unclaimed {
+		let n in 1 .. T::MaxSweepPerCall::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let contributions = (0..n)
+			.map(|i| (relay_account::<T>(i), CONTRIBUTION))
+			.collect::<Vec<_>>();
+		let end_block = create_campaign::<T>(contributions, PayoutMode::Transfer)?;
+		frame_system::Pallet::<T>::set_block_number(end_block + T::ClaimDeadline::get() + 1u32.into());
+	}: _(RawOrigin::Signed(caller), 0, n)
+	verify {
+		assert_eq!(Contributors::<T>::iter_prefix(0u32).count(), 0);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Crowdloan,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -0,0 +1,90 @@
+//! Messages relay chain accounts sign to associate their contribution with a native or an Ethereum
+//! (H160) account, and helpers for the Ethereum side.
+//!
+//! MetaMask users sign the association with `personal_sign`, which follows EIP-191:
+//! `keccak256("\x19Ethereum Signed Message:\n" ++ len(message) ++ message)`.
+
+use sp_core::{ecdsa, H160};
+use sp_io::hashing::keccak_256;
+use sp_std::vec::Vec;
+
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// Build the human-readable message both the relay account and the Ethereum account sign.
+pub fn association_message(
+	pallet_name: &[u8],
+	chain_id: u64,
+	relay_account: &[u8],
+	eth_address: &H160,
+) -> Vec<u8> {
+	let mut message = domain(pallet_name, chain_id);
+	message.extend_from_slice(b"associate relay account 0x");
+	message.extend_from_slice(&to_hex(relay_account));
+	message.extend_from_slice(b" with 0x");
+	message.extend_from_slice(&to_hex(eth_address.as_bytes()));
+	message
+}
+
+/// Build the human-readable message the relay account signs to associate with the native account
+/// SCALE-encoded as `native_account`.
+pub fn native_association_message(
+	pallet_name: &[u8],
+	chain_id: u64,
+	native_account: &[u8],
+) -> Vec<u8> {
+	let mut message = domain(pallet_name, chain_id);
+	message.extend_from_slice(b"associate relay account with 0x");
+	message.extend_from_slice(&to_hex(native_account));
+	message
+}
+
+/// Hash `message` the way `personal_sign` does.
+pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+	let mut prefixed = EIP191_PREFIX.to_vec();
+	prefixed.extend_from_slice(&to_decimal(message.len() as u128));
+	prefixed.extend_from_slice(message);
+	keccak_256(&prefixed)
+}
+
+/// Recover the Ethereum address that produced `signature` over the EIP-191 hash of `message`.
+pub fn recover_signer(signature: &ecdsa::Signature, message: &[u8]) -> Option<H160> {
+	let hash = eip191_hash(message);
+	let mut sig: [u8; 65] = *signature.as_ref();
+	// `personal_sign` returns the recovery id as 27/28
+	if sig[64] >= 27 {
+		sig[64] -= 27;
+	}
+	sp_io::crypto::secp256k1_ecdsa_recover(&sig, &hash)
+		.ok()
+		.map(|public| H160::from_slice(&keccak_256(&public)[12..]))
+}
+
+/// Start of every association message. The pallet name and the chain id are part of it so that a
+/// signature can not be replayed on another chain or against another pallet.
+fn domain(pallet_name: &[u8], chain_id: u64) -> Vec<u8> {
+	let mut message = pallet_name.to_vec();
+	message.extend_from_slice(b" on chain ");
+	message.extend_from_slice(&to_decimal(chain_id as u128));
+	message.extend_from_slice(b": ");
+	message
+}
+
+fn to_hex(bytes: &[u8]) -> Vec<u8> {
+	const HEX: &[u8; 16] = b"0123456789abcdef";
+	bytes.iter()
+		.flat_map(|b| sp_std::vec![HEX[(b >> 4) as usize], HEX[(b & 0xf) as usize]])
+		.collect()
+}
+
+fn to_decimal(mut n: u128) -> Vec<u8> {
+	let mut digits = Vec::new();
+	loop {
+		digits.push(b'0' + (n % 10) as u8);
+		n /= 10;
+		if n == 0 {
+			break;
+		}
+	}
+	digits.reverse();
+	digits
+}
@@ -8,14 +8,24 @@ pub(crate) mod mock;
 #[cfg(test)]
 mod tests;
 
+mod benchmarking;
+pub mod eth;
+pub mod migrations;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
 #[pallet]
 pub mod pallet {
-	use frame_support::{dispatch::fmt::Debug, pallet_prelude::*, traits::Currency, traits::ExistenceRequirement::AllowDeath};
+	use frame_support::{dispatch::fmt::Debug, pallet_prelude::*, transactional, traits::Currency, traits::ExistenceRequirement::AllowDeath, traits::PalletInfo, traits::VestingSchedule};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{AccountIdConversion, Saturating, Verify};
-	use sp_runtime::{MultiSignature, SaturatedConversion};
-	use sp_core::crypto::AccountId32;
+	use sp_runtime::traits::{Saturating, Verify, Zero};
+	use sp_runtime::{MultiSignature, Perbill, SaturatedConversion};
+	use sp_core::{crypto::AccountId32, ecdsa, H160};
 	use sp_std::{convert::{From, TryInto}, vec::Vec};
+	use pallet_evm::AddressMapping;
+	use pallet_crowdloan_rewards_rpc_runtime_api::RewardStatus;
+	use crate::WeightInfo;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_treasury::Config {
@@ -29,9 +39,37 @@ pub mod pallet {
 		+ Ord
 		+ Default
 		+ Into<AccountId32>;
+
+		/// Maps an Ethereum address to the native account receiving its rewards
+		type AddressMapping: AddressMapping<Self::AccountId>;
+
+		/// Ethereum chain id, part of the message signed to associate an Ethereum account
+		type ChainId: Get<u64>;
+
+		/// Number of blocks after the end of a campaign in which its rewards can still be claimed
+		type ClaimDeadline: Get<Self::BlockNumber>;
+
+		/// Maximum number of contributors removed by one `sweep_unclaimed` call
+		type MaxSweepPerCall: Get<u32>;
+
+		/// Locks rewards paid out with [`PayoutMode::VestingLock`]
+		type Vesting: VestingSchedule<
+			Self::AccountId,
+			Moment = Self::BlockNumber,
+			Currency = <Self as pallet_treasury::Config>::Currency,
+		>;
+
+		/// Payout mode of campaigns that do not choose one
+		type DefaultPayoutMode: Get<PayoutMode>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
-	type BalanceOf<T> = pallet_treasury::BalanceOf<T>;
+	pub(crate) type BalanceOf<T> = pallet_treasury::BalanceOf<T>;
+
+	/// Identifier of a crowdloan campaign
+	pub type CampaignId = u32;
 
 	#[derive(Default, Clone, Encode, Decode, RuntimeDebug)]
 	pub struct RewardInfo<T: Config> {
@@ -40,20 +78,99 @@ pub mod pallet {
 		pub last_paid: T::BlockNumber,
 	}
 
+	/// How the reward of a campaign reaches the contributors
+	#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+	pub enum PayoutMode {
+		/// Contributors claim what has vested so far, as often as they like
+		Transfer,
+		/// The first claim transfers the whole remaining reward and locks the part that has not
+		/// vested yet with a vesting schedule. Accounts already having a vesting schedule are paid
+		/// as with `Transfer` instead
+		VestingLock,
+	}
+
+	impl Default for PayoutMode {
+		fn default() -> Self {
+			PayoutMode::Transfer
+		}
+	}
+
+	/// Terms of a crowdloan campaign
+	#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+	pub struct CampaignInfo<AccountId, BlockNumber> {
+		/// Account the rewards are paid from
+		pub pot: AccountId,
+		/// Reward per contributed unit
+		pub rate: u32,
+		/// Block at which the whole reward is vested
+		pub end_block: BlockNumber,
+		/// Number of blocks the reward vests over
+		pub reward_period: BlockNumber,
+		/// Part of the reward that can be claimed as soon as the campaign starts
+		pub initial_payment: Perbill,
+		/// Nothing but the initial payment can be claimed before this block
+		pub cliff_block: Option<BlockNumber>,
+		/// How the reward is paid out
+		pub payout: PayoutMode,
+	}
+
+	pub type CampaignInfoOf<T> = CampaignInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	// A value placed in storage that represents the current version of the Crowdloan storage. This value
+	// is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+	pub enum Releases {
+		V1_0_0,
+		V2_0_0,
+		V3_0_0,
+		V4_0_0,
+	}
+
+	impl Default for Releases {
+		fn default() -> Self {
+			Releases::V1_0_0
+		}
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			if StorageVersion::<T>::get() == Releases::V1_0_0 {
+				weight = weight.saturating_add(crate::migrations::migrate_to_v2::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V2_0_0 {
+				weight = weight.saturating_add(crate::migrations::migrate_to_v3::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V3_0_0 {
+				weight = weight.saturating_add(crate::migrations::migrate_to_v4::<T>());
+			}
+			weight
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
+		/// Start a new campaign paying `rate` per contributed unit until `end_block`.
+		///
+		/// Campaigns run concurrently, each one is paid from its own `pot` which defaults to the
+		/// treasury account. `payout` defaults to `T::DefaultPayoutMode`.
+		#[pallet::weight(<T as Config>::WeightInfo::initialize_reward(contributions.len() as u32))]
 		pub fn initialize_reward(
 			origin: OriginFor<T>,
 			contributions: Vec<(T::RelayChainAccountId, u32)>,
 			rate: u32,
 			end_block: T::BlockNumber,
+			initial_payment: Perbill,
+			cliff_block: Option<T::BlockNumber>,
+			pot: Option<T::AccountId>,
+			payout: Option<PayoutMode>,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			let now = frame_system::Pallet::<T>::block_number();
@@ -61,12 +178,14 @@ pub mod pallet {
 				&now < &end_block,
 				Error::<T>::InvalidEndBlock
 			);
-			let current_reward_end_in = CurrentRewardEndIn::<T>::get();
-
-			ensure!(
-				&now >= &current_reward_end_in,
-				Error::<T>::AlreadyInitReward
-			);
+			if let Some(cliff) = &cliff_block {
+				ensure!(
+					&now <= cliff && cliff <= &end_block,
+					Error::<T>::InvalidCliffBlock
+				);
+			}
+
+			let campaign_id = NextCampaignId::<T>::get();
 			for (account, amount) in &contributions {
 				let reward_info = RewardInfo {
 					total_reward: BalanceOf::<T>::from(*amount)
@@ -74,21 +193,38 @@ pub mod pallet {
 					claimed_reward: 0u32.into(),
 					last_paid: now.clone(),
 				};
-				Contributors::<T>::insert(account, reward_info);
+				Contributors::<T>::insert(campaign_id, account, reward_info);
 			};
-			CurrentRewardEndIn::<T>::put(&end_block);
-			RewardPeriod::<T>::put(end_block - now);
+			Campaigns::<T>::insert(campaign_id, CampaignInfo {
+				pot: pot.unwrap_or_else(pallet_treasury::Pallet::<T>::account_id),
+				rate,
+				end_block,
+				reward_period: end_block - now,
+				initial_payment,
+				cliff_block,
+				payout: payout.unwrap_or_else(T::DefaultPayoutMode::get),
+			});
+			NextCampaignId::<T>::put(campaign_id + 1);
+
+			Self::deposit_event(Event::CampaignCreated(
+				campaign_id,
+				end_block,
+			));
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		/// Associate a relay chain account with the caller.
+		///
+		/// The relay account signs the message built by
+		/// [`crate::eth::native_association_message`] for the caller.
+		#[pallet::weight(<T as Config>::WeightInfo::associate_account())]
 		pub fn associate_account(
 			origin: OriginFor<T>,
 			relay_account: T::RelayChainAccountId,
 			proof: MultiSignature,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let payload = who.clone().encode();
+			let payload = Self::native_association_message(&who);
 
 			ensure!(
 				proof.verify(payload.as_slice(), &relay_account.clone().into()),
@@ -101,6 +237,7 @@ pub mod pallet {
 			);
 
 			AssociatedAccount::<T>::insert(&who, &relay_account);
+			AssociatedNativeAccounts::<T>::insert(&relay_account, &who, ());
 
 			Self::deposit_event(Event::AssociatedAccount(
 				who,
@@ -109,7 +246,53 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		/// Associate a relay chain account with an Ethereum account.
+		///
+		/// Both the relay account and the Ethereum account sign the message built by
+		/// [`crate::eth::association_message`], the latter through `personal_sign` (EIP-191). Rewards are
+		/// paid to the native account `eth_address` maps to.
+		#[pallet::weight(<T as Config>::WeightInfo::associate_eth_account())]
+		pub fn associate_eth_account(
+			origin: OriginFor<T>,
+			relay_account: T::RelayChainAccountId,
+			eth_address: H160,
+			proof: MultiSignature,
+			eth_proof: ecdsa::Signature,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let payload = Self::eth_association_message(&relay_account, &eth_address);
+
+			ensure!(
+				proof.verify(payload.as_slice(), &relay_account.clone().into()),
+				Error::<T>::InvalidSignature
+			);
+			ensure!(
+				crate::eth::recover_signer(&eth_proof, &payload) == Some(eth_address),
+				Error::<T>::InvalidEthSignature
+			);
+
+			let who = T::AddressMapping::into_account_id(eth_address);
+			ensure!(
+				AssociatedAccount::<T>::get(&who).is_none(),
+				Error::<T>::AlreadyAssociated
+			);
+
+			AssociatedAccount::<T>::insert(&who, &relay_account);
+			AssociatedNativeAccounts::<T>::insert(&relay_account, &who, ());
+
+			Self::deposit_event(Event::AssociatedEthAccount(
+				eth_address,
+				who,
+				relay_account,
+			));
+			Ok(Default::default())
+		}
+
+		/// Move the association of `relay_account` from `associated_native_account` to the caller.
+		///
+		/// The relay account signs the message built by
+		/// [`crate::eth::native_association_message`] for the caller.
+		#[pallet::weight(<T as Config>::WeightInfo::update_associate_account())]
 		pub fn update_associate_account(
 			origin: OriginFor<T>,
 			associated_native_account: T::AccountId,
@@ -117,7 +300,7 @@ pub mod pallet {
 			proof: MultiSignature,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let payload = who.clone().encode();
+			let payload = Self::native_association_message(&who);
 
 			ensure!(
 				proof.verify(payload.as_slice(), &relay_account.clone().into()),
@@ -134,8 +317,10 @@ pub mod pallet {
 				relay_account == relay_associated_account,
 				Error::<T>::BadRelayAccount
 			);
-			AssociatedAccount::<T>::remove(associated_native_account);
+			AssociatedAccount::<T>::remove(&associated_native_account);
+			AssociatedNativeAccounts::<T>::remove(&relay_account, &associated_native_account);
 			AssociatedAccount::<T>::insert(&who, &relay_account);
+			AssociatedNativeAccounts::<T>::insert(&relay_account, &who, ());
 			Self::deposit_event(Event::UpdateAssociateAccount(
 				who,
 				relay_account,
@@ -143,97 +328,398 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
-		pub fn get_money(
+		/// Claim the reward vested so far in `campaign_id`.
+		#[pallet::weight(<T as Config>::WeightInfo::claim())]
+		pub fn claim(
 			origin: OriginFor<T>,
+			campaign_id: CampaignId,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let now = frame_system::Pallet::<T>::block_number();
 
 			let relay_account =
 				AssociatedAccount::<T>::get(&who).ok_or(Error::<T>::NoAssociatedAccount)?;
 
-			let mut info =
-				Contributors::<T>::get(&relay_account).ok_or(Error::<T>::NotContributedYet)?;
-
-			ensure!(
-				&info.total_reward > &info.claimed_reward,
-				Error::<T>::AlreadyPaid
-			);
+			Self::pay_reward(&who, &relay_account, campaign_id)?;
+			Ok(Default::default())
+		}
 
-			let reward_period = RewardPeriod::<T>::get()
-				.saturated_into::<u128>()
-				.try_into()
-				.ok()
-				.ok_or(Error::<T>::WrongConversionU128ToBalance)?;
+		/// Claim the reward vested so far in every campaign the associated relay account
+		/// contributed to.
+		///
+		/// A campaign failing to pay emits `ClaimFailed` and leaves the others paying. The call
+		/// fails as a whole if no campaign paid.
+		///
+		/// The weight covers every campaign created so far, whether the account contributed to it
+		/// or not.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_all(NextCampaignId::<T>::get()))]
+		#[transactional]
+		pub fn claim_all(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 
-			let reward_per_block = info.total_reward / reward_period;
-			let reward_period = now.saturating_sub(info.last_paid);
+			let relay_account =
+				AssociatedAccount::<T>::get(&who).ok_or(Error::<T>::NoAssociatedAccount)?;
 
-			let reward_period_as_balance: BalanceOf<T> = reward_period
-				.saturated_into::<u128>()
-				.try_into()
-				.ok()
-				.ok_or(Error::<T>::WrongConversionU128ToBalance)?;
+			let mut paid = false;
+			for campaign_id in 0..NextCampaignId::<T>::get() {
+				if Contributors::<T>::contains_key(campaign_id, &relay_account) {
+					match Self::pay_reward(&who, &relay_account, campaign_id) {
+						Ok(_) => paid = true,
+						Err(error) => Self::deposit_event(Event::ClaimFailed(
+							who.clone(),
+							campaign_id,
+							error,
+						)),
+					}
+				}
+			}
+
+			ensure!(paid, Error::<T>::NothingToClaim);
+			Ok(Default::default())
+		}
 
-			let amount = if reward_per_block.saturating_mul(reward_period_as_balance)
-				> info.total_reward - info.claimed_reward {
-				info.total_reward - info.claimed_reward
-			} else {
-				reward_per_block.saturating_mul(reward_period_as_balance)
-			};
-			info.last_paid = now;
-			info.claimed_reward = info.claimed_reward.saturating_add(amount);
-			Contributors::<T>::insert(&relay_account, info);
+		/// Remove up to `limit` contributors of `campaign_id` who did not claim their whole reward
+		/// before the claim deadline. The forfeited rewards stay in the campaign pot.
+		///
+		/// Anyone can call this once the deadline has passed.
+		#[pallet::weight(<T as Config>::WeightInfo::sweep_unclaimed((*limit).min(T::MaxSweepPerCall::get())))]
+		pub fn sweep_unclaimed(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let campaign = Campaigns::<T>::get(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
 
 			ensure!(
-				amount >= T::Currency::minimum_balance(),
-				Error::<T>::ScantyReward
+				now > Self::claim_deadline(&campaign),
+				Error::<T>::ClaimDeadlineNotPassed
 			);
 
+			let limit = limit.min(T::MaxSweepPerCall::get()) as usize;
+			let mut swept: BalanceOf<T> = Zero::zero();
+			let mut count = 0u32;
+			for (relay_account, info) in Contributors::<T>::drain_prefix(campaign_id).take(limit) {
+				let forfeited = info.total_reward.saturating_sub(info.claimed_reward);
+				swept = swept.saturating_add(forfeited);
+				count += 1;
+				Self::deposit_event(Event::RewardForfeited(campaign_id, relay_account, forfeited));
+			}
+
+			UnclaimedSwept::<T>::mutate(campaign_id, |total| *total = total.saturating_add(swept));
+			if campaign.pot == pallet_treasury::Pallet::<T>::account_id() {
+				pallet_treasury::Pallet::<T>::note_recovered(swept);
+			}
+
+			Self::deposit_event(Event::UnclaimedSwept(campaign_id, swept));
+			Ok(Some(<T as Config>::WeightInfo::sweep_unclaimed(count)).into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Last block in which rewards of `campaign` can be claimed.
+		pub fn claim_deadline(campaign: &CampaignInfoOf<T>) -> T::BlockNumber {
+			campaign.end_block.saturating_add(T::ClaimDeadline::get())
+		}
+
+		/// Pay `who` the reward `relay_account` has vested so far in `campaign_id`.
+		///
+		/// Nothing is written unless the whole payment succeeds.
+		#[transactional]
+		fn pay_reward(
+			who: &T::AccountId,
+			relay_account: &T::RelayChainAccountId,
+			campaign_id: CampaignId,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let campaign = Campaigns::<T>::get(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+
+			let mut info = Contributors::<T>::get(campaign_id, relay_account)
+				.ok_or(Error::<T>::NotContributedYet)?;
+
+			let (mut amount, mut locked) = Self::payout(&campaign, &info, now, campaign.payout)?;
+
+			let mut schedule = None;
+			if !locked.is_zero() {
+				let vesting = Self::vesting_schedule(&campaign, locked)?;
+				if T::Vesting::can_add_vesting_schedule(who, vesting.0, vesting.1, vesting.2).is_ok() {
+					schedule = Some(vesting);
+				} else {
+					// An account holds a single vesting schedule, so with one already there only
+					// what has vested so far is paid.
+					amount = Self::payout(&campaign, &info, now, PayoutMode::Transfer)?.0;
+					locked = Zero::zero();
+				}
+			}
+
 			T::Currency::transfer(
-				&T::PalletId::get().into_account(),
-				&who,
+				&campaign.pot,
+				who,
 				amount,
 				AllowDeath,
 			).map_err(|_| Error::<T>::RewardFailed)?;
 
+			if let Some((locked, per_block, starting_block)) = schedule {
+				T::Vesting::add_vesting_schedule(who, locked, per_block, starting_block)
+					.map_err(|_| Error::<T>::VestingFailed)?;
+			}
+
+			info.last_paid = now;
+			info.claimed_reward = info.claimed_reward.saturating_add(amount);
+			Contributors::<T>::insert(campaign_id, relay_account, info);
+
 			Self::deposit_event(Event::RewardPaid(
-				who,
+				who.clone(),
+				campaign_id,
 				amount,
 			));
-			Ok(Default::default())
+			if !locked.is_zero() {
+				Self::deposit_event(Event::RewardLocked(
+					who.clone(),
+					campaign_id,
+					locked,
+				));
+			}
+			Ok(amount)
+		}
+
+		/// Amount a claim of `info` at `now` paid out in `mode` pays and the part of it that gets
+		/// locked.
+		fn payout(
+			campaign: &CampaignInfoOf<T>,
+			info: &RewardInfo<T>,
+			now: T::BlockNumber,
+			mode: PayoutMode,
+		) -> Result<(BalanceOf<T>, BalanceOf<T>), Error<T>> {
+			ensure!(
+				&info.total_reward > &info.claimed_reward,
+				Error::<T>::AlreadyPaid
+			);
+			ensure!(
+				now <= Self::claim_deadline(campaign),
+				Error::<T>::ClaimDeadlinePassed
+			);
+
+			let remaining = info.total_reward.saturating_sub(info.claimed_reward);
+			let (amount, locked) = match mode {
+				PayoutMode::Transfer => {
+					let amount = Self::vested_reward(campaign, info, now)?
+						.saturating_sub(info.claimed_reward);
+					(amount, Zero::zero())
+				}
+				PayoutMode::VestingLock => (remaining, Self::unvested_reward(campaign, info, now)?),
+			};
+
+			// The last payment settles whatever is left, even when it is below the minimum balance.
+			ensure!(
+				!amount.is_zero() && (amount >= T::Currency::minimum_balance() || amount == remaining),
+				Error::<T>::ScantyReward
+			);
+			Ok((amount, locked))
+		}
+
+		/// Native accounts associated with `relay_account`.
+		pub fn associated_accounts(relay_account: &T::RelayChainAccountId) -> Vec<T::AccountId> {
+			AssociatedNativeAccounts::<T>::iter_prefix(relay_account)
+				.map(|(account, _)| account)
+				.collect()
+		}
+
+		/// Reward of `relay_account` in `campaign_id` at block `now`.
+		///
+		/// With [`PayoutMode::VestingLock`] the claimable amount includes the part that gets
+		/// locked.
+		pub fn reward_status(
+			campaign_id: CampaignId,
+			relay_account: &T::RelayChainAccountId,
+			now: T::BlockNumber,
+		) -> Option<RewardStatus<BalanceOf<T>, T::BlockNumber>> {
+			let campaign = Campaigns::<T>::get(campaign_id)?;
+			let info = Contributors::<T>::get(campaign_id, relay_account)?;
+
+			Some(RewardStatus {
+				total_reward: info.total_reward,
+				claimed_reward: info.claimed_reward,
+				vested_reward: Self::vested_reward(&campaign, &info, now).ok()?,
+				claimable_reward: Self::payout(&campaign, &info, now, campaign.payout)
+					.map(|(amount, _)| amount)
+					.unwrap_or_else(|_| Zero::zero()),
+				end_block: campaign.end_block,
+				claim_deadline: Self::claim_deadline(&campaign),
+			})
+		}
+
+		/// The message to sign for associating `relay_account` with `eth_address`.
+		pub fn eth_association_message(
+			relay_account: &T::RelayChainAccountId,
+			eth_address: &H160,
+		) -> Vec<u8> {
+			let relay_account: AccountId32 = relay_account.clone().into();
+			crate::eth::association_message(
+				Self::pallet_name().as_bytes(),
+				T::ChainId::get(),
+				relay_account.as_ref(),
+				eth_address,
+			)
+		}
+
+		/// The message a relay account signs for associating with `who`.
+		pub fn native_association_message(who: &T::AccountId) -> Vec<u8> {
+			crate::eth::native_association_message(
+				Self::pallet_name().as_bytes(),
+				T::ChainId::get(),
+				&who.encode(),
+			)
+		}
+
+		fn pallet_name() -> &'static str {
+			<T as frame_system::Config>::PalletInfo::name::<Self>().unwrap_or("CrowdloanRewards")
+		}
+
+		/// Total amount of `info` that has vested in `campaign` by block `now`.
+		///
+		/// The `initial_payment` part is available as soon as the campaign starts. The rest vests
+		/// linearly over `reward_period` but nothing of it can be claimed before `cliff_block`.
+		/// Once `end_block` is reached the whole `total_reward` is vested, so no remainder of the
+		/// integer division is ever lost.
+		pub fn vested_reward(
+			campaign: &CampaignInfoOf<T>,
+			info: &RewardInfo<T>,
+			now: T::BlockNumber,
+		) -> Result<BalanceOf<T>, Error<T>> {
+			let end_block = campaign.end_block;
+			if now >= end_block {
+				return Ok(info.total_reward);
+			}
+
+			let initial_reward = campaign.initial_payment * info.total_reward;
+			if campaign.cliff_block.map_or(false, |cliff| now < cliff) {
+				return Ok(initial_reward);
+			}
+
+			let reward_period = campaign.reward_period;
+			let start_block = end_block.saturating_sub(reward_period);
+			let elapsed = now.saturating_sub(start_block);
+
+			let reward_period_as_balance = Self::block_number_to_balance(reward_period)?;
+			let elapsed_as_balance = Self::block_number_to_balance(elapsed)?;
+			if reward_period_as_balance.is_zero() {
+				return Ok(info.total_reward);
+			}
+
+			let vesting_reward = info.total_reward.saturating_sub(initial_reward);
+			let vested = vesting_reward.saturating_mul(elapsed_as_balance) / reward_period_as_balance;
+
+			Ok(initial_reward.saturating_add(vested))
+		}
+
+		/// Part of the remaining reward of `info` a [`PayoutMode::VestingLock`] claim at `now`
+		/// has to lock.
+		///
+		/// The lock follows the vesting of the campaign. With a cliff it is stricter: the part
+		/// vesting before the cliff stays locked until the cliff and unlocks linearly from there.
+		fn unvested_reward(
+			campaign: &CampaignInfoOf<T>,
+			info: &RewardInfo<T>,
+			now: T::BlockNumber,
+		) -> Result<BalanceOf<T>, Error<T>> {
+			if now >= campaign.end_block {
+				return Ok(Zero::zero());
+			}
+			let initial_reward = campaign.initial_payment * info.total_reward;
+			match campaign.cliff_block {
+				Some(cliff) if now < cliff => Ok(info.total_reward.saturating_sub(initial_reward)),
+				_ => Ok(info.total_reward.saturating_sub(Self::vested_reward(campaign, info, now)?)),
+			}
+		}
+
+		/// Vesting schedule `(locked, per_block, starting_block)` unlocking `locked` by the end
+		/// of `campaign`.
+		fn vesting_schedule(
+			campaign: &CampaignInfoOf<T>,
+			locked: BalanceOf<T>,
+		) -> Result<(BalanceOf<T>, BalanceOf<T>, T::BlockNumber), Error<T>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let starting_block = campaign.cliff_block.map_or(now, |cliff| cliff.max(now));
+			let blocks = Self::block_number_to_balance(campaign.end_block.saturating_sub(starting_block))?
+				.max(1u32.into());
+			// Round up so that nothing stays locked after the end block
+			let per_block = locked.saturating_add(blocks.saturating_sub(1u32.into())) / blocks;
+			Ok((locked, per_block, starting_block))
+		}
+
+		fn block_number_to_balance(n: T::BlockNumber) -> Result<BalanceOf<T>, Error<T>> {
+			n.saturated_into::<u128>()
+				.try_into()
+				.ok()
+				.ok_or(Error::<T>::WrongConversionU128ToBalance)
 		}
 	}
 
+	#[pallet::storage]
+	#[pallet::getter(fn campaigns)]
+	pub type Campaigns<T: Config> =
+	StorageMap<_, Twox64Concat, CampaignId, CampaignInfoOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_campaign_id)]
+	pub type NextCampaignId<T: Config> =
+	StorageValue<_, CampaignId, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn contributors)]
-	pub type Contributors<T: Config> =
-	StorageMap<_, Blake2_128Concat, T::RelayChainAccountId, RewardInfo<T>>;
+	pub type Contributors<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CampaignId,
+		Blake2_128Concat,
+		T::RelayChainAccountId,
+		RewardInfo<T>,
+	>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn associated_account)]
 	pub type AssociatedAccount<T: Config> =
 	StorageMap<_, Blake2_128Concat, T::AccountId, T::RelayChainAccountId>;
 
+	/// Native accounts associated with each relay chain account, the reverse of
+	/// `AssociatedAccount`.
 	#[pallet::storage]
-	#[pallet::getter(fn current_reward_end_in)]
-	pub type CurrentRewardEndIn<T: Config> =
-	StorageValue<_, T::BlockNumber, ValueQuery>;
+	pub type AssociatedNativeAccounts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::RelayChainAccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+	>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn reward_period)]
-	pub type RewardPeriod<T: Config> =
-	StorageValue<_, T::BlockNumber, ValueQuery>;
+	#[pallet::getter(fn unclaimed_swept)]
+	pub type UnclaimedSwept<T: Config> =
+	StorageMap<_, Twox64Concat, CampaignId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub type StorageVersion<T: Config> =
+	StorageValue<_, Releases, ValueQuery>;
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Current block great than end block
 		InvalidEndBlock,
-		/// Already init a reward
-		AlreadyInitReward,
+		/// Cliff block is not between the current block and the end block
+		InvalidCliffBlock,
+		/// Campaign does not exist
+		CampaignNotExist,
+		/// No campaign has a reward to claim
+		NothingToClaim,
 		/// User provide wrong signature
 		InvalidSignature,
+		/// Ethereum signature does not recover to the given address
+		InvalidEthSignature,
 		/// User already associated relay account with native account
 		AlreadyAssociated,
 		/// Already paid all reward
@@ -248,8 +734,14 @@ pub mod pallet {
 		WrongConversionU128ToBalance,
 		/// User cannot receive a reward
 		RewardFailed,
+		/// The reward can not be locked with a vesting schedule
+		VestingFailed,
 		/// The amount of reward is lower than the minimum balance
 		ScantyReward,
+		/// Rewards can not be claimed after the claim deadline
+		ClaimDeadlinePassed,
+		/// Unclaimed rewards can only be swept after the claim deadline
+		ClaimDeadlineNotPassed,
 	}
 
 	#[pallet::event]
@@ -259,7 +751,19 @@ pub mod pallet {
 
 		UpdateAssociateAccount(T::AccountId, T::RelayChainAccountId),
 
-		RewardPaid(T::AccountId, BalanceOf<T>),
+		AssociatedEthAccount(H160, T::AccountId, T::RelayChainAccountId),
+
+		CampaignCreated(CampaignId, T::BlockNumber),
+
+		RewardPaid(T::AccountId, CampaignId, BalanceOf<T>),
+
+		RewardLocked(T::AccountId, CampaignId, BalanceOf<T>),
+
+		ClaimFailed(T::AccountId, CampaignId, DispatchError),
+
+		RewardForfeited(CampaignId, T::RelayChainAccountId, BalanceOf<T>),
+
+		UnclaimedSwept(CampaignId, BalanceOf<T>),
 	}
 }
 
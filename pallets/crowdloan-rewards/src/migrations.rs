@@ -0,0 +1,117 @@
+//! Storage migrations of the crowdloan rewards pallet.
+
+use crate::pallet::{
+	AssociatedAccount, AssociatedNativeAccounts, BalanceOf, CampaignId, CampaignInfo, CampaignInfoOf,
+	Campaigns, Config, Contributors, NextCampaignId, Pallet, PayoutMode, Releases, RewardInfo,
+	StorageVersion, UnclaimedSwept,
+};
+use frame_support::{
+	storage::migration::{remove_storage_prefix, storage_key_iter, take_storage_value},
+	traits::{Get, PalletInfo},
+	weights::Weight,
+	Blake2_128Concat,
+};
+use sp_runtime::{traits::AccountIdConversion, Perbill};
+use sp_std::vec::Vec;
+
+/// Campaign the rewards initialized before campaigns existed are moved to.
+pub const LEGACY_CAMPAIGN_ID: CampaignId = 0;
+
+/// Move the single reward of `V1_0_0` into campaign [`LEGACY_CAMPAIGN_ID`].
+///
+/// The rate of the legacy reward was never stored, so the campaign records `0`. It is only
+/// informative, the contributors keep their `total_reward`.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	let pallet_name = <T as frame_system::Config>::PalletInfo::name::<Pallet<T>>()
+		.unwrap_or("CrowdloanRewards")
+		.as_bytes();
+
+	let end_block = take_storage_value::<T::BlockNumber>(pallet_name, b"CurrentRewardEndIn", &[]);
+	let reward_period = take_storage_value::<T::BlockNumber>(pallet_name, b"RewardPeriod", &[]);
+	let initial_payment = take_storage_value::<Perbill>(pallet_name, b"InitialPayment", &[]);
+	let cliff_block = take_storage_value::<T::BlockNumber>(pallet_name, b"RewardCliff", &[]);
+	let swept = take_storage_value::<BalanceOf<T>>(pallet_name, b"UnclaimedSwept", &[]);
+
+	let mut count = 0u64;
+	// Only chains that initialized a reward before campaigns existed have anything to move. The
+	// new `Contributors` double map shares its prefix with the old map, so it must not be touched
+	// otherwise.
+	if let Some(end_block) = end_block {
+		let contributors: Vec<(T::RelayChainAccountId, RewardInfo<T>)> =
+			storage_key_iter::<T::RelayChainAccountId, RewardInfo<T>, Blake2_128Concat>(
+				pallet_name,
+				b"Contributors",
+			).collect();
+		remove_storage_prefix(pallet_name, b"Contributors", &[]);
+
+		count = contributors.len() as u64;
+		for (relay_account, info) in &contributors {
+			Contributors::<T>::insert(LEGACY_CAMPAIGN_ID, relay_account, info);
+		}
+		Campaigns::<T>::insert(LEGACY_CAMPAIGN_ID, CampaignInfo {
+			pot: T::PalletId::get().into_account(),
+			rate: 0,
+			end_block,
+			reward_period: reward_period.unwrap_or_default(),
+			initial_payment: initial_payment.unwrap_or_default(),
+			cliff_block,
+			payout: PayoutMode::Transfer,
+		});
+		if let Some(swept) = swept {
+			UnclaimedSwept::<T>::insert(LEGACY_CAMPAIGN_ID, swept);
+		}
+		NextCampaignId::<T>::put(LEGACY_CAMPAIGN_ID + 1);
+	}
+	StorageVersion::<T>::put(Releases::V2_0_0);
+
+	log::info!(
+		target: "runtime::crowdloan-rewards",
+		"migrated {} contributors to campaign {}",
+		count,
+		LEGACY_CAMPAIGN_ID,
+	);
+
+	T::DbWeight::get().reads_writes(count + 6, count * 2 + 10)
+}
+
+/// Index the associations of `V2_0_0` by relay chain account in `AssociatedNativeAccounts`.
+pub fn migrate_to_v3<T: Config>() -> Weight {
+	let mut count = 0u64;
+	for (who, relay_account) in AssociatedAccount::<T>::iter() {
+		AssociatedNativeAccounts::<T>::insert(&relay_account, &who, ());
+		count += 1;
+	}
+	StorageVersion::<T>::put(Releases::V3_0_0);
+
+	log::info!(
+		target: "runtime::crowdloan-rewards",
+		"indexed {} associated accounts",
+		count,
+	);
+
+	T::DbWeight::get().reads_writes(count + 1, count + 1)
+}
+
+/// Pay the campaigns of `V3_0_0` paid from the `PalletId` account from the treasury account,
+/// which the treasury now derives from its EVM address and moves its pot to.
+pub fn migrate_to_v4<T: Config>() -> Weight {
+	let legacy: T::AccountId = T::PalletId::get().into_account();
+	let treasury = pallet_treasury::Pallet::<T>::account_id();
+	let mut count = 0u64;
+	Campaigns::<T>::translate(|_, mut campaign: CampaignInfoOf<T>| {
+		if campaign.pot == legacy {
+			campaign.pot = treasury.clone();
+			count += 1;
+		}
+		Some(campaign)
+	});
+	StorageVersion::<T>::put(Releases::V4_0_0);
+
+	log::info!(
+		target: "runtime::crowdloan-rewards",
+		"moved {} campaigns to the treasury account",
+		count,
+	);
+
+	T::DbWeight::get().reads_writes(count + 1, count + 1)
+}
@@ -1,10 +1,12 @@
-use crate::{self as pallet_crowdloan_rewards, Config};
+use crate::{self as pallet_crowdloan_rewards, Config, PayoutMode};
 use frame_support::{construct_runtime, parameter_types, PalletId};
-use sp_core::{ed25519, Pair, H256};
+use frame_system::EnsureRoot;
+use sp_core::{ecdsa, ed25519, Pair, H160, H256};
 use sp_io;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+	Perbill, Permill,
 };
 use sp_std::convert::{From, TryInto};
 
@@ -63,19 +65,101 @@ impl pallet_utility::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 1;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 1;
+	pub const MetadataDepositPerByte: Balance = 1;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"Treasury");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: u128 = 1;
+	pub const SpendPeriod: u64 = 100;
+	pub const MaxApprovals: u32 = 100;
+	pub const Burn: Permill = Permill::from_percent(0);
+	pub const MaxPaymentsPerBlock: u32 = 50;
 }
 
 impl pallet_treasury::Config for Test {
 	type PalletId = TreasuryPalletId;
 	type Currency = Balances;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
 	type Event = Event;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type MaxApprovals = MaxApprovals;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type MaxPaymentsPerBlock = MaxPaymentsPerBlock;
+	type Assets = Assets;
+	type Erc20 = ();
+	type AddressMapping = TestAddressMapping;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: u128 = 1;
+}
+
+impl pallet_vesting::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ChainId: u64 = 11;
+	pub const ClaimDeadline: u64 = 5;
+	pub const MaxSweepPerCall: u32 = 2;
+	pub const DefaultPayoutMode: PayoutMode = PayoutMode::Transfer;
+}
+
+/// Maps an Ethereum address to the `u64` read big-endian from its first 8 bytes, so the
+/// treasury account differs from the little-endian `PalletId` account as it does on chain.
+pub struct TestAddressMapping;
+
+impl pallet_evm::AddressMapping<AccountId> for TestAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		let mut bytes = [0u8; 8];
+		bytes.copy_from_slice(&address.as_bytes()[0..8]);
+		AccountId::from_be_bytes(bytes)
+	}
 }
 
 impl Config for Test {
 	type Event = Event;
 	type RelayChainAccountId = [u8; 32];
+	type AddressMapping = TestAddressMapping;
+	type ChainId = ChainId;
+	type ClaimDeadline = ClaimDeadline;
+	type MaxSweepPerCall = MaxSweepPerCall;
+	type Vesting = Vesting;
+	type DefaultPayoutMode = DefaultPayoutMode;
+	type WeightInfo = ();
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -89,16 +173,22 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Crowdloan: pallet_crowdloan_rewards::{Pallet, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Pallet, Call, Storage, Event},
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
 pub struct ExtBuilder;
 
 impl ExtBuilder {
-	pub fn build(contributions: Vec<([u8; 32], u32)>) -> sp_io::TestExternalities {
+	pub fn build(
+		contributions: Vec<([u8; 32], u32)>,
+		initial_payment: Perbill,
+		cliff_block: Option<u64>,
+	) -> sp_io::TestExternalities {
 		let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 		// Provide some initial balances
 		pallet_balances::GenesisConfig::<Test> {balances: vec![(100, 100_000_000)]}
@@ -111,7 +201,11 @@ impl ExtBuilder {
 				Origin::root(),
 				contributions.clone(),
 				10,
-				10
+				10,
+				initial_payment,
+				cliff_block,
+				None,
+				None,
 			).unwrap();
 			System::set_block_number(1)
 		});
@@ -120,6 +214,18 @@ impl ExtBuilder {
 	}
 }
 
+/// Externalities without any campaign, with a keystore to sign relay chain payloads.
+#[cfg(feature = "runtime-benchmarks")]
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	use sp_keystore::{testing::KeyStore, KeystoreExt};
+
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::from(storage);
+	ext.register_extension(KeystoreExt(std::sync::Arc::new(KeyStore::new())));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
 pub(crate) fn get_ed25519_pairs(num: u32) -> Vec<ed25519::Pair> {
 	let seed: u128 = 12345678901234567890123456789012;
 	let mut pairs = Vec::new();
@@ -135,13 +241,42 @@ pub(crate) fn get_ed25519_pairs(num: u32) -> Vec<ed25519::Pair> {
 	pairs
 }
 
+pub(crate) fn get_eth_key(seed: u8) -> (secp256k1::SecretKey, H160) {
+	let secret = secp256k1::SecretKey::parse(&[seed; 32]).unwrap();
+	let public = secp256k1::PublicKey::from_secret_key(&secret).serialize();
+	let address = H160::from_slice(&sp_io::hashing::keccak_256(&public[1..])[12..]);
+	(secret, address)
+}
+
+/// Sign `message` the way MetaMask `personal_sign` does.
+pub(crate) fn eth_sign(secret: &secp256k1::SecretKey, message: &[u8]) -> ecdsa::Signature {
+	let hash = crate::eth::eip191_hash(message);
+	let (signature, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(&hash), secret);
+	let mut raw = [0u8; 65];
+	raw[0..64].copy_from_slice(&signature.serialize());
+	raw[64] = recovery_id.serialize() + 27;
+	ecdsa::Signature::from_raw(raw)
+}
+
 pub(crate) fn mock_test() -> sp_io::TestExternalities {
+	mock_test_with_vesting(Perbill::zero(), None)
+}
+
+pub(crate) fn mock_test_with_vesting(
+	initial_payment: Perbill,
+	cliff_block: Option<u64>,
+) -> sp_io::TestExternalities {
 	let pairs = get_ed25519_pairs(3);
-	ExtBuilder::build(vec![
-		([1u8; 32].into(), 500),
-		([2u8; 32].into(), 500),
-		(pairs[0].public().into(), 500),
-	])
+	ExtBuilder::build(
+		vec![
+			([1u8; 32].into(), 500),
+			([2u8; 32].into(), 500),
+			(pairs[0].public().into(), 500),
+			(pairs[2].public().into(), 3),
+		],
+		initial_payment,
+		cliff_block,
+	)
 }
 
 pub(crate) fn events() -> Vec<super::Event<Test>> {
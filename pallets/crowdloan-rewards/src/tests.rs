@@ -3,12 +3,16 @@ use frame_support::{assert_noop, assert_ok};
 use mock::*;
 use codec::Encode;
 use sp_core::Pair;
-use sp_runtime::MultiSignature;
+use pallet_evm::AddressMapping;
+use sp_runtime::{MultiSignature, Perbill};
+use frame_support::traits::VestingSchedule;
+use pallet_crowdloan_rewards_rpc_runtime_api::RewardStatus;
 
 #[test]
 fn init_reward_work () {
 	mock_test().execute_with(|| {
-		assert_noop!(
+		// campaigns run concurrently
+		assert_ok!(
 			Crowdloan::initialize_reward(
 				Origin::root(),
 				vec![
@@ -16,9 +20,12 @@ fn init_reward_work () {
 					([2u8; 32].into(), 500)
 				],
 				10,
-				10
-			),
-			Error::<Test>::AlreadyInitReward
+				10,
+				Perbill::zero(),
+				None,
+				None,
+				None
+			)
 		);
 		run_to_block(10);
 		assert_noop!(
@@ -29,7 +36,11 @@ fn init_reward_work () {
 					([2u8; 32].into(), 500)
 				],
 				10,
-				10
+				10,
+				Perbill::zero(),
+				None,
+				None,
+				None
 			),
 			Error::<Test>::InvalidEndBlock
 		);
@@ -41,16 +52,29 @@ fn init_reward_work () {
 					([2u8; 32].into(), 500)
 				],
 				10,
-				20
+				20,
+				Perbill::zero(),
+				None,
+				Some(100),
+				None
 			),
 		);
+		assert_eq!(Crowdloan::next_campaign_id(), 3);
+		assert_eq!(Crowdloan::campaigns(1u32).unwrap().reward_period, 9);
+		assert_eq!(Crowdloan::campaigns(2u32).unwrap().pot, 100);
+		assert_eq!(Crowdloan::contributors(2u32, &[1u8; 32]).unwrap().total_reward, 5000);
+		let expected = vec![
+			crate::Event::CampaignCreated(1, 10),
+			crate::Event::CampaignCreated(2, 20),
+		];
+		assert_eq!(events(), expected);
 	})
 }
 
 #[test]
 fn associate_account_work() {
 	let pairs = get_ed25519_pairs(1);
-	let proof: MultiSignature = pairs[0].sign(&1u64.encode()).into();
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
 	mock_test().execute_with(|| {
 		assert_noop!(
 			Crowdloan::associate_account(
@@ -84,10 +108,31 @@ fn associate_account_work() {
 }
 
 #[test]
-fn get_money_work() {
+fn associate_account_proof_is_bound_to_the_chain() {
+	let pairs = get_ed25519_pairs(1);
+	let bare: MultiSignature = pairs[0].sign(&1u64.encode()).into();
+	let other_chain: MultiSignature = pairs[0]
+		.sign(&crate::eth::native_association_message(b"Crowdloan", 12, &1u64.encode()))
+		.into();
+	mock_test().execute_with(|| {
+		for proof in vec![bare, other_chain] {
+			assert_noop!(
+				Crowdloan::associate_account(Origin::signed(1), pairs[0].public().into(), proof),
+				Error::<Test>::InvalidSignature
+			);
+		}
+		assert_eq!(
+			Crowdloan::native_association_message(&1),
+			[&b"Crowdloan on chain 11: associate relay account with 0x"[..], b"0100000000000000"].concat()
+		);
+	})
+}
+
+#[test]
+fn claim_work() {
 	let pairs = get_ed25519_pairs(2);
-	let proof: MultiSignature = pairs[0].sign(&1u64.encode()).into();
-	let proof1: MultiSignature = pairs[1].sign(&11u64.encode()).into();
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let proof1: MultiSignature = pairs[1].sign(&Crowdloan::native_association_message(&11)).into();
 	let relay_account = pairs[0].public().into();
 	// 1 is contributor, 11 not
 	mock_test().execute_with(|| {
@@ -95,9 +140,7 @@ fn get_money_work() {
 		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
 
 		assert_noop!(
-			Crowdloan::get_money(
-				Origin::signed(1),
-			),
+			Crowdloan::claim(Origin::signed(1), 0),
 			Error::<Test>::NoAssociatedAccount
 		);
 		Crowdloan::associate_account(
@@ -106,9 +149,7 @@ fn get_money_work() {
 			proof1.clone()
 		).unwrap();
 		assert_noop!(
-			Crowdloan::get_money(
-				Origin::signed(11),
-			),
+			Crowdloan::claim(Origin::signed(11), 0),
 			Error::<Test>::NotContributedYet
 		);
 		Crowdloan::associate_account(
@@ -117,53 +158,43 @@ fn get_money_work() {
 			proof.clone()
 		).unwrap();
 		run_to_block(2);
-		assert_ok!(Crowdloan::get_money(
-				Origin::signed(1),
-		));
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
 		assert_noop!(
-			Crowdloan::get_money(
-				Origin::signed(1),
-			),
+			Crowdloan::claim(Origin::signed(1), 0),
 			Error::<Test>::ScantyReward
 		);
 		assert_eq!(
-			Crowdloan::contributors(&relay_account).unwrap().last_paid,
+			Crowdloan::contributors(0u32, &relay_account).unwrap().last_paid,
 			2u64
 		);
 		// we mock rate = 10 and period = 10 and pair1 contribute 500
 		// earn (500 * 10)/10 = 500 token per block
 		assert_eq!(
-			Crowdloan::contributors(&relay_account).unwrap().claimed_reward,
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
 			1000
 		);
 		run_to_block(8);
-		assert_ok!(Crowdloan::get_money(
-				Origin::signed(1),
-		));
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
 		assert_eq!(
-			Crowdloan::contributors(&relay_account).unwrap().claimed_reward,
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
 			4000
 		);
 		run_to_block(11);
-		assert_ok!(Crowdloan::get_money(
-				Origin::signed(1),
-		));
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
 		assert_eq!(
-			Crowdloan::contributors(&relay_account).unwrap().claimed_reward,
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
 			5000
 		);
 		assert_noop!(
-			Crowdloan::get_money(
-				Origin::signed(1),
-			),
+			Crowdloan::claim(Origin::signed(1), 0),
 			Error::<Test>::AlreadyPaid
 		);
 		let expected = vec![
 			crate::Event::AssociatedAccount(11, pairs[1].public().into()),
 			crate::Event::AssociatedAccount(1, relay_account),
-			crate::Event::RewardPaid(1, 1000),
-			crate::Event::RewardPaid(1, 3000),
-			crate::Event::RewardPaid(1, 1000),
+			crate::Event::RewardPaid(1, 0, 1000),
+			crate::Event::RewardPaid(1, 0, 3000),
+			crate::Event::RewardPaid(1, 0, 1000),
 		];
 		assert_eq!(events(), expected);
 	})
@@ -172,9 +203,9 @@ fn get_money_work() {
 #[test]
 fn update_associate_account_work() {
 	let pairs = get_ed25519_pairs(2);
-	let proof: MultiSignature = pairs[0].sign(&1u64.encode()).into();
-	let proof1: MultiSignature = pairs[1].sign(&2u64.encode()).into();
-	let proof2: MultiSignature = pairs[0].sign(&3u64.encode()).into();
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let proof1: MultiSignature = pairs[1].sign(&Crowdloan::native_association_message(&2)).into();
+	let proof2: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&3)).into();
 	mock_test().execute_with(|| {
 		// user 100 donate fund to Treasury
 		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
@@ -213,9 +244,7 @@ fn update_associate_account_work() {
 			Error::<Test>::NoAssociatedAccount
 		);
 		run_to_block(5);
-		Crowdloan::get_money(
-			Origin::signed(1),
-		).unwrap();
+		Crowdloan::claim(Origin::signed(1), 0).unwrap();
 		// the only way to update is sign proof by own relay account and input correct associated account
 		assert_ok!(
 			Crowdloan::update_associate_account(
@@ -225,17 +254,592 @@ fn update_associate_account_work() {
 				proof2.clone()
 			),
 		);
+		assert_eq!(Crowdloan::associated_accounts(&pairs[0].public().into()), vec![3]);
 		run_to_block(6);
 		assert_ok!(
-			Crowdloan::get_money(
-				Origin::signed(3)
+			Crowdloan::claim(Origin::signed(3), 0)
+		);
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 0),
+			Error::<Test>::NoAssociatedAccount
+		);
+	})
+}
+
+#[test]
+fn initial_payment_work() {
+	let pairs = get_ed25519_pairs(1);
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let relay_account = pairs[0].public().into();
+	mock_test_with_vesting(Perbill::from_percent(20), None).execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		Crowdloan::associate_account(
+			Origin::signed(1),
+			relay_account,
+			proof.clone()
+		).unwrap();
+		// 20% of 5000 is paid up front, the rest vests 400 token per block
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
+		assert_eq!(
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
+			1400
+		);
+		run_to_block(10);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
+		assert_eq!(
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
+			5000
+		);
+	})
+}
+
+#[test]
+fn cliff_work() {
+	let pairs = get_ed25519_pairs(1);
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let relay_account = pairs[0].public().into();
+	mock_test_with_vesting(Perbill::from_percent(20), Some(5)).execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		Crowdloan::associate_account(
+			Origin::signed(1),
+			relay_account,
+			proof.clone()
+		).unwrap();
+		// only the initial payment is available before the cliff
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
+		assert_eq!(
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
+			1000
+		);
+		run_to_block(4);
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 0),
+			Error::<Test>::ScantyReward
+		);
+		// at the cliff everything vested since the start becomes claimable
+		run_to_block(5);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
+		assert_eq!(
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
+			3000
+		);
+	})
+}
+
+#[test]
+fn remainder_paid_at_end_work() {
+	let pairs = get_ed25519_pairs(3);
+	let proof: MultiSignature = pairs[2].sign(&Crowdloan::native_association_message(&4)).into();
+	let relay_account = pairs[2].public().into();
+	mock_test_with_vesting(Perbill::from_percent(10), None).execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		Crowdloan::associate_account(
+			Origin::signed(4),
+			relay_account,
+			proof.clone()
+		).unwrap();
+		// 3 up front, then 27 vest over 10 blocks which does not divide evenly
+		assert_ok!(Crowdloan::claim(Origin::signed(4), 0));
+		assert_eq!(
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
+			5
+		);
+		run_to_block(3);
+		assert_ok!(Crowdloan::claim(Origin::signed(4), 0));
+		assert_eq!(
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
+			11
+		);
+		run_to_block(10);
+		assert_ok!(Crowdloan::claim(Origin::signed(4), 0));
+		assert_eq!(
+			Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward,
+			30
+		);
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(4), 0),
+			Error::<Test>::AlreadyPaid
+		);
+	})
+}
+
+#[test]
+fn associate_eth_account_work() {
+	let pairs = get_ed25519_pairs(2);
+	let relay_account: [u8; 32] = pairs[0].public().into();
+	let (eth_secret, eth_address) = get_eth_key(1);
+	let (other_secret, _) = get_eth_key(2);
+	mock_test().execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		let message = Crowdloan::eth_association_message(&relay_account, &eth_address);
+		let proof: MultiSignature = pairs[0].sign(&message).into();
+
+		// the relay account must sign the association message
+		assert_noop!(
+			Crowdloan::associate_eth_account(
+				Origin::signed(1),
+				relay_account,
+				eth_address,
+				pairs[1].sign(&message).into(),
+				eth_sign(&eth_secret, &message)
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// the old payload can not be replayed through the new path
+		assert_noop!(
+			Crowdloan::associate_eth_account(
+				Origin::signed(1),
+				relay_account,
+				eth_address,
+				pairs[0].sign(&Crowdloan::native_association_message(&1)).into(),
+				eth_sign(&eth_secret, &message)
+			),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			Crowdloan::associate_eth_account(
+				Origin::signed(1),
+				relay_account,
+				eth_address,
+				proof.clone(),
+				eth_sign(&other_secret, &message)
+			),
+			Error::<Test>::InvalidEthSignature
+		);
+		assert_ok!(
+			Crowdloan::associate_eth_account(
+				Origin::signed(1),
+				relay_account,
+				eth_address,
+				proof.clone(),
+				eth_sign(&eth_secret, &message)
 			)
 		);
+		let who = TestAddressMapping::into_account_id(eth_address);
+		assert_eq!(Crowdloan::associated_account(who), Some(relay_account));
 		assert_noop!(
-			Crowdloan::get_money(
-				Origin::signed(1)
+			Crowdloan::associate_eth_account(
+				Origin::signed(1),
+				relay_account,
+				eth_address,
+				proof.clone(),
+				eth_sign(&eth_secret, &message)
 			),
-			Error::<Test>::NoAssociatedAccount
+			Error::<Test>::AlreadyAssociated
+		);
+
+		// the H160 mapped account receives the reward
+		run_to_block(2);
+		assert_ok!(Crowdloan::claim(Origin::signed(who), 0));
+		assert_eq!(Balances::free_balance(who), 1000);
+		let expected = vec![
+			crate::Event::AssociatedEthAccount(eth_address, who, relay_account),
+			crate::Event::RewardPaid(who, 0, 1000),
+		];
+		assert_eq!(events(), expected);
+	})
+}
+
+#[test]
+fn sweep_unclaimed_work() {
+	let pairs = get_ed25519_pairs(1);
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let relay_account = pairs[0].public().into();
+	mock_test().execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		Crowdloan::associate_account(
+			Origin::signed(1),
+			relay_account,
+			proof.clone()
+		).unwrap();
+		run_to_block(2);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
+		// reward ends at block 10 and can be claimed for 5 more blocks
+		run_to_block(15);
+		assert_noop!(
+			Crowdloan::sweep_unclaimed(
+				Origin::signed(2),
+				0,
+				10
+			),
+			Error::<Test>::ClaimDeadlineNotPassed
+		);
+		run_to_block(16);
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 0),
+			Error::<Test>::ClaimDeadlinePassed
+		);
+		// each call removes at most `MaxSweepPerCall` contributors
+		assert_ok!(Crowdloan::sweep_unclaimed(Origin::signed(2), 0, 10));
+		assert_eq!(Contributors::<Test>::iter_prefix(0u32).count(), 2);
+		assert_ok!(Crowdloan::sweep_unclaimed(Origin::signed(2), 0, 10));
+		assert_eq!(Contributors::<Test>::iter_prefix(0u32).count(), 0);
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 0),
+			Error::<Test>::NotContributedYet
+		);
+
+		// 5000 + 5000 + 30 untouched and 4000 left by the claimer
+		assert_eq!(Crowdloan::unclaimed_swept(0u32), 14030);
+		// the campaign was paid from the treasury, which records what it got back
+		assert_eq!(Treasury::recovered(), 14030);
+		let forfeited = events()
+			.into_iter()
+			.filter(|e| matches!(e, crate::Event::RewardForfeited(..)))
+			.count();
+		assert_eq!(forfeited, 4);
+	})
+}
+
+#[test]
+fn claim_all_work() {
+	let pairs = get_ed25519_pairs(2);
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let proof1: MultiSignature = pairs[1].sign(&Crowdloan::native_association_message(&11)).into();
+	let relay_account = pairs[0].public().into();
+	mock_test().execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		// a second campaign paid by user 100, 100 token per block
+		assert_ok!(Crowdloan::initialize_reward(
+			Origin::root(),
+			vec![(relay_account, 100)],
+			10,
+			11,
+			Perbill::zero(),
+			None,
+			Some(100),
+			None
+		));
+		Crowdloan::associate_account(
+			Origin::signed(1),
+			relay_account,
+			proof.clone()
+		).unwrap();
+		Crowdloan::associate_account(
+			Origin::signed(11),
+			pairs[1].public().into(),
+			proof1.clone()
+		).unwrap();
+
+		run_to_block(2);
+		assert_ok!(Crowdloan::claim_all(Origin::signed(1)));
+		assert_eq!(Crowdloan::contributors(0u32, &relay_account).unwrap().claimed_reward, 1000);
+		assert_eq!(Crowdloan::contributors(1u32, &relay_account).unwrap().claimed_reward, 100);
+		assert_eq!(Balances::free_balance(1), 1100);
+		assert_eq!(Balances::free_balance(100), 89_999_900);
+		assert_noop!(
+			Crowdloan::claim_all(Origin::signed(1)),
+			Error::<Test>::NothingToClaim
 		);
+		assert_noop!(
+			Crowdloan::claim_all(Origin::signed(11)),
+			Error::<Test>::NothingToClaim
+		);
+
+		// campaign 0 ends first, campaign 1 keeps paying
+		run_to_block(10);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 0),
+			Error::<Test>::AlreadyPaid
+		);
+		assert_ok!(Crowdloan::claim_all(Origin::signed(1)));
+		assert_eq!(Crowdloan::contributors(1u32, &relay_account).unwrap().claimed_reward, 900);
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 2),
+			Error::<Test>::CampaignNotExist
+		);
+
+		let expected = vec![
+			crate::Event::CampaignCreated(1, 11),
+			crate::Event::AssociatedAccount(1, relay_account),
+			crate::Event::AssociatedAccount(11, pairs[1].public().into()),
+			crate::Event::RewardPaid(1, 0, 1000),
+			crate::Event::RewardPaid(1, 1, 100),
+			crate::Event::RewardPaid(1, 0, 4000),
+			crate::Event::ClaimFailed(1, 0, Error::<Test>::AlreadyPaid.into()),
+			crate::Event::RewardPaid(1, 1, 800),
+		];
+		assert_eq!(events(), expected);
+	})
+}
+
+#[test]
+fn vesting_lock_work() {
+	let pairs = get_ed25519_pairs(3);
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let proof2: MultiSignature = pairs[2].sign(&Crowdloan::native_association_message(&4)).into();
+	let relay_account: [u8; 32] = pairs[0].public().into();
+	let relay_account2: [u8; 32] = pairs[2].public().into();
+	mock_test().execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		// 20% up front, the rest vests 400 token per block until block 11
+		assert_ok!(Crowdloan::initialize_reward(
+			Origin::root(),
+			vec![(relay_account, 500), (relay_account2, 500)],
+			10,
+			11,
+			Perbill::from_percent(20),
+			None,
+			None,
+			Some(PayoutMode::VestingLock)
+		));
+		// same terms but nothing but the initial payment unlocks before block 6
+		assert_ok!(Crowdloan::initialize_reward(
+			Origin::root(),
+			vec![(relay_account2, 500)],
+			10,
+			11,
+			Perbill::from_percent(20),
+			Some(6),
+			None,
+			Some(PayoutMode::VestingLock)
+		));
+		Crowdloan::associate_account(
+			Origin::signed(1),
+			relay_account,
+			proof.clone()
+		).unwrap();
+		Crowdloan::associate_account(
+			Origin::signed(4),
+			relay_account2,
+			proof2.clone()
+		).unwrap();
+
+		// the whole reward is paid at once, what has not vested yet is locked
+		run_to_block(3);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 1));
+		assert_eq!(Balances::free_balance(1), 5000);
+		assert_eq!(Vesting::vesting_balance(&1), Some(3200));
+		assert_eq!(Crowdloan::contributors(1u32, &relay_account).unwrap().claimed_reward, 5000);
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 1),
+			Error::<Test>::AlreadyPaid
+		);
+
+		// the lock starts at the cliff
+		assert_ok!(Crowdloan::claim(Origin::signed(4), 2));
+		assert_eq!(Balances::free_balance(4), 5000);
+		assert_eq!(Vesting::vesting_balance(&4), Some(4000));
+
+		run_to_block(7);
+		assert_eq!(Vesting::vesting_balance(&1), Some(1600));
+		assert_eq!(Vesting::vesting_balance(&4), Some(3200));
+		run_to_block(11);
+		assert_eq!(Vesting::vesting_balance(&1), Some(0));
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+
+		let expected = vec![
+			crate::Event::CampaignCreated(1, 11),
+			crate::Event::CampaignCreated(2, 11),
+			crate::Event::AssociatedAccount(1, relay_account),
+			crate::Event::AssociatedAccount(4, relay_account2),
+			crate::Event::RewardPaid(1, 1, 5000),
+			crate::Event::RewardLocked(1, 1, 3200),
+			crate::Event::RewardPaid(4, 2, 5000),
+			crate::Event::RewardLocked(4, 2, 4000),
+		];
+		assert_eq!(events(), expected);
+	})
+}
+
+#[test]
+fn vesting_lock_with_existing_schedule_work() {
+	let pairs = get_ed25519_pairs(1);
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let relay_account: [u8; 32] = pairs[0].public().into();
+	mock_test().execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		// two campaigns with the same terms: 20% up front, the rest vests 400 token per block
+		for _ in 0..2 {
+			assert_ok!(Crowdloan::initialize_reward(
+				Origin::root(),
+				vec![(relay_account, 500)],
+				10,
+				11,
+				Perbill::from_percent(20),
+				None,
+				None,
+				Some(PayoutMode::VestingLock)
+			));
+		}
+		Crowdloan::associate_account(
+			Origin::signed(1),
+			relay_account,
+			proof.clone()
+		).unwrap();
+
+		run_to_block(3);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 1));
+		assert_eq!(Vesting::vesting_balance(&1), Some(3200));
+		// the account already has a schedule, so the second campaign pays what has vested
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 2));
+		assert_eq!(Balances::free_balance(1), 6800);
+		assert_eq!(Vesting::vesting_balance(&1), Some(3200));
+		assert_eq!(Crowdloan::contributors(2u32, &relay_account).unwrap().claimed_reward, 1800);
+
+		run_to_block(11);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 2));
+		assert_eq!(Balances::free_balance(1), 10_000);
+		assert_eq!(Vesting::vesting_balance(&1), Some(0));
+		assert_noop!(
+			Crowdloan::claim(Origin::signed(1), 2),
+			Error::<Test>::AlreadyPaid
+		);
+
+		let expected = vec![
+			crate::Event::CampaignCreated(1, 11),
+			crate::Event::CampaignCreated(2, 11),
+			crate::Event::AssociatedAccount(1, relay_account),
+			crate::Event::RewardPaid(1, 1, 5000),
+			crate::Event::RewardLocked(1, 1, 3200),
+			crate::Event::RewardPaid(1, 2, 1800),
+			crate::Event::RewardPaid(1, 2, 3200),
+		];
+		assert_eq!(events(), expected);
+	})
+}
+
+#[test]
+fn reward_status_work() {
+	let pairs = get_ed25519_pairs(1);
+	let proof: MultiSignature = pairs[0].sign(&Crowdloan::native_association_message(&1)).into();
+	let relay_account: [u8; 32] = pairs[0].public().into();
+	mock_test().execute_with(|| {
+		assert_ok!(Treasury::donate(Origin::signed(100), 10_000_000));
+		Crowdloan::associate_account(
+			Origin::signed(1),
+			relay_account,
+			proof.clone()
+		).unwrap();
+		assert_eq!(Crowdloan::associated_accounts(&relay_account), vec![1]);
+		assert_eq!(Crowdloan::associated_accounts(&[1u8; 32]), Vec::<u64>::new());
+
+		run_to_block(3);
+		assert_eq!(
+			Crowdloan::reward_status(0, &relay_account, 3),
+			Some(RewardStatus {
+				total_reward: 5000,
+				claimed_reward: 0,
+				vested_reward: 1500,
+				claimable_reward: 1500,
+				end_block: 10,
+				claim_deadline: 15,
+			})
+		);
+		assert_ok!(Crowdloan::claim(Origin::signed(1), 0));
+		let status = Crowdloan::reward_status(0, &relay_account, 3).unwrap();
+		assert_eq!(status.claimed_reward, 1500);
+		assert_eq!(status.claimable_reward, 0);
+		// the status can be computed for any block
+		let status = Crowdloan::reward_status(0, &relay_account, 12).unwrap();
+		assert_eq!(status.vested_reward, 5000);
+		assert_eq!(status.claimable_reward, 3500);
+		let status = Crowdloan::reward_status(0, &relay_account, 16).unwrap();
+		assert_eq!(status.claimable_reward, 0);
+
+		assert_eq!(Crowdloan::reward_status(1, &relay_account, 3), None);
+		assert_eq!(Crowdloan::reward_status(0, &[9u8; 32], 3), None);
+	})
+}
+
+#[test]
+fn migrate_to_v2_work() {
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::OnRuntimeUpgrade,
+		Blake2_128Concat, StorageHasher,
+	};
+
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		let info = RewardInfo::<Test> {
+			total_reward: 5000,
+			claimed_reward: 1000,
+			last_paid: 2,
+		};
+		for account in &[[1u8; 32], [2u8; 32]] {
+			put_storage_value(
+				b"Crowdloan",
+				b"Contributors",
+				&Blake2_128Concat::hash(&account.encode()),
+				info.clone(),
+			);
+		}
+		put_storage_value(b"Crowdloan", b"CurrentRewardEndIn", &[], 10u64);
+		put_storage_value(b"Crowdloan", b"RewardPeriod", &[], 10u64);
+		put_storage_value(b"Crowdloan", b"InitialPayment", &[], Perbill::from_percent(20));
+		put_storage_value(b"Crowdloan", b"UnclaimedSwept", &[], 30u128);
+
+		Crowdloan::on_runtime_upgrade();
+
+		assert_eq!(Crowdloan::storage_version(), Releases::V4_0_0);
+		assert_eq!(Crowdloan::next_campaign_id(), 1);
+		let campaign = Crowdloan::campaigns(0u32).unwrap();
+		assert_eq!(campaign.pot, Treasury::account_id());
+		assert_eq!(campaign.end_block, 10);
+		assert_eq!(campaign.reward_period, 10);
+		assert_eq!(campaign.initial_payment, Perbill::from_percent(20));
+		assert_eq!(campaign.cliff_block, None);
+		assert_eq!(Contributors::<Test>::iter_prefix(0u32).count(), 2);
+		assert_eq!(Crowdloan::contributors(0u32, &[1u8; 32]).unwrap().claimed_reward, 1000);
+		assert_eq!(Crowdloan::unclaimed_swept(0u32), 30);
+		assert_eq!(
+			get_storage_value::<u64>(b"Crowdloan", b"CurrentRewardEndIn", &[]),
+			None
+		);
+
+		// a second upgrade leaves the campaigns alone
+		Crowdloan::on_runtime_upgrade();
+		assert_eq!(Contributors::<Test>::iter_prefix(0u32).count(), 2);
+	})
+}
+
+#[test]
+fn migrate_to_v3_work() {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		StorageVersion::<Test>::put(Releases::V2_0_0);
+		AssociatedAccount::<Test>::insert(1, [1u8; 32]);
+		AssociatedAccount::<Test>::insert(2, [1u8; 32]);
+		AssociatedAccount::<Test>::insert(3, [2u8; 32]);
+
+		crate::migrations::migrate_to_v3::<Test>();
+
+		assert_eq!(Crowdloan::storage_version(), Releases::V3_0_0);
+		let mut accounts = Crowdloan::associated_accounts(&[1u8; 32]);
+		accounts.sort();
+		assert_eq!(accounts, vec![1, 2]);
+		assert_eq!(Crowdloan::associated_accounts(&[2u8; 32]), vec![3]);
+	})
+}
+
+#[test]
+fn migrate_to_v4_work() {
+	use frame_support::traits::Get;
+	use sp_runtime::traits::AccountIdConversion;
+
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		let legacy: AccountId = TreasuryPalletId::get().into_account();
+		let campaign = |pot| CampaignInfo {
+			pot,
+			rate: 10,
+			end_block: 10,
+			reward_period: 10,
+			initial_payment: Perbill::zero(),
+			cliff_block: None,
+			payout: PayoutMode::Transfer,
+		};
+		StorageVersion::<Test>::put(Releases::V3_0_0);
+		Campaigns::<Test>::insert(0, campaign(legacy));
+		Campaigns::<Test>::insert(1, campaign(100));
+
+		crate::migrations::migrate_to_v4::<Test>();
+
+		assert_eq!(Crowdloan::storage_version(), Releases::V4_0_0);
+		assert_ne!(legacy, Treasury::account_id());
+		assert_eq!(Crowdloan::campaigns(0u32).unwrap().pot, Treasury::account_id());
+		assert_eq!(Crowdloan::campaigns(1u32).unwrap().pot, 100);
 	})
 }
@@ -0,0 +1,146 @@
+//! Staking pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as Staking;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Saturating;
+
+const SEED: u32 = 0;
+
+// An account that can afford any of the bonds below.
+fn funded<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, SEED);
+	let min = T::MinCollatorStake::get().max(T::MinNominatorStake::get());
+	T::Currency::make_free_balance_be(&who, min.saturating_mul(100u32.into()));
+	who
+}
+
+// A collator that bonded `amount` but is not active yet.
+fn bonded_collator<T: Config>(index: u32, amount: BalanceOf<T>) -> Result<T::AccountId, &'static str> {
+	let collator = funded::<T>("collator", index);
+	Staking::<T>::bond(RawOrigin::Signed(collator.clone()).into(), amount)
+		.map_err(|_| "failed to bond collator")?;
+	Ok(collator)
+}
+
+// A collator that bonded `amount` and can be nominated.
+fn active_collator<T: Config>(index: u32, amount: BalanceOf<T>) -> Result<T::AccountId, &'static str> {
+	let collator = bonded_collator::<T>(index, amount)?;
+	Staking::<T>::force_onboard(RawOrigin::Root.into(), collator.clone())
+		.map_err(|_| "failed to onboard collator")?;
+	Ok(collator)
+}
+
+// Nominate `collator` with `amount` from `nominator`.
+fn nominate<T: Config>(
+	nominator: &T::AccountId,
+	collator: &T::AccountId,
+	amount: BalanceOf<T>,
+) -> Result<(), &'static str> {
+	Staking::<T>::nominate(RawOrigin::Signed(nominator.clone()).into(), collator.clone(), amount)
+		.map(|_| ())
+		.map_err(|_| "failed to nominate")
+}
+
+benchmarks! {
+	config {
+		let settings = SettingStruct {
+			bond_duration: 4,
+			blocks_per_round: 100,
+			desired_target: 4,
+		};
+	}: _(RawOrigin::Root, settings.clone())
+	verify {
+		assert_eq!(Settings::<T>::get(), settings);
+	}
+
+	bond {
+		let caller = funded::<T>("collator", 0);
+	}: _(RawOrigin::Signed(caller.clone()), T::MinCollatorStake::get())
+	verify {
+		assert!(Collators::<T>::contains_key(&caller));
+	}
+
+	force_onboard {
+		let collator = bonded_collator::<T>(0, T::MinCollatorStake::get())?;
+	}: _(RawOrigin::Root, collator.clone())
+	verify {
+		assert!(Collators::<T>::get(&collator).map_or(false, |c| c.is_active()));
+	}
+
+	bond_extra {
+		let min = T::MinCollatorStake::get();
+		let collator = active_collator::<T>(0, min)?;
+	}: _(RawOrigin::Signed(collator.clone()), min)
+	verify {
+		assert_eq!(Collators::<T>::get(&collator).map(|c| c.total), Some(min.saturating_mul(2u32.into())));
+	}
+
+	bond_less {
+		let min = T::MinCollatorStake::get();
+		let collator = active_collator::<T>(0, min.saturating_mul(2u32.into()))?;
+	}: _(RawOrigin::Signed(collator.clone()), min)
+	verify {
+		assert_eq!(Collators::<T>::get(&collator).map(|c| c.active), Some(min));
+	}
+
+	collator_unbond {
+		let n in 0 .. T::MaxNominationsPerCollator::get();
+
+		let collator = active_collator::<T>(0, T::MinCollatorStake::get())?;
+		for i in 0 .. n {
+			let nominator = funded::<T>("nominator", i);
+			nominate::<T>(&nominator, &collator, T::MinNominatorStake::get())?;
+		}
+	}: _(RawOrigin::Signed(collator.clone()))
+	verify {
+		assert!(ExitQueue::<T>::contains_key(&collator));
+	}
+
+	nominate {
+		let collator = active_collator::<T>(0, T::MinCollatorStake::get())?;
+		let caller = funded::<T>("nominator", 0);
+	}: _(RawOrigin::Signed(caller.clone()), collator, T::MinNominatorStake::get())
+	verify {
+		assert!(Nominators::<T>::contains_key(&caller));
+	}
+
+	nominate_extra {
+		let min = T::MinNominatorStake::get();
+		let collator = active_collator::<T>(0, T::MinCollatorStake::get())?;
+		let caller = funded::<T>("nominator", 0);
+		nominate::<T>(&caller, &collator, min)?;
+	}: _(RawOrigin::Signed(caller.clone()), collator, min)
+	verify {
+		assert_eq!(Nominators::<T>::get(&caller).map(|n| n.total), Some(min.saturating_mul(2u32.into())));
+	}
+
+	nominate_less {
+		let min = T::MinNominatorStake::get();
+		let collator = active_collator::<T>(0, T::MinCollatorStake::get())?;
+		let caller = funded::<T>("nominator", 0);
+		nominate::<T>(&caller, &collator, min.saturating_mul(2u32.into()))?;
+	}: _(RawOrigin::Signed(caller.clone()), collator, min)
+	verify {
+		assert_eq!(Nominators::<T>::get(&caller).map(|n| n.unbonding.len()), Some(1));
+	}
+
+	nominator_leave_collator {
+		let collator = active_collator::<T>(0, T::MinCollatorStake::get())?;
+		let caller = funded::<T>("nominator", 0);
+		nominate::<T>(&caller, &collator, T::MinNominatorStake::get())?;
+	}: _(RawOrigin::Signed(caller), collator.clone())
+	verify {
+		assert_eq!(Collators::<T>::get(&collator).map(|c| c.nominations.len()), Some(0));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Staking,
+	crate::mock::mock_test(),
+	crate::mock::Test,
+);
@@ -5,9 +5,13 @@ use frame_support::pallet;
 pub(crate) mod mock;
 #[cfg(test)]
 mod tests;
+mod benchmarking;
 
 pub mod taylor_series;
 pub mod inflation;
+pub mod weights;
+
+pub use weights::WeightInfo;
 
 pub(crate) const LOG_TARGET: &'static str = "runtime::staking";
 
@@ -24,7 +28,7 @@ macro_rules! log {
 
 #[pallet]
 pub mod pallet {
-	use frame_support::{pallet_prelude::*, traits::{Currency, ReservableCurrency, CurrencyToVote, Imbalance}};
+	use frame_support::{pallet_prelude::*, traits::{Currency, ReservableCurrency, CurrencyToVote, Imbalance, OnUnbalanced}};
 	use frame_system::pallet_prelude::*;
 	use sp_runtime::traits::{Saturating, Zero};
 	use sp_runtime::{Perbill};
@@ -35,15 +39,19 @@ pub mod pallet {
 	use sp_std::{cmp::Ordering, prelude::*, ops::{Mul, AddAssign, Add, Sub}};
 	use frame_support::sp_std::fmt::Debug;
 	use log::info;
+	use crate::WeightInfo;
 
 	/// Counter for the number of round that have passed
 	pub type RoundIndex = u32;
 	/// Counter for the number of "reward" points earned by a given collator
 	pub type RewardPoint = u32;
 
-	type BalanceOf<T> = <<T as Config>::Currency as Currency<
+	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
+	type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -80,6 +88,11 @@ pub mod pallet {
 		type CurrencyToVote: CurrencyToVote<BalanceOf<Self>>;
 
 		type DesiredTarget: Get<u32>;
+		/// Handler for the part of a round's payout that is not paid to stakers, e.g. the
+		/// treasury. It is not minted when this is `()`.
+		type RewardRemainder: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -87,6 +100,15 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			// A chain adding the pallet in an upgrade has no genesis for it, start its first round now
+			if CurrentRound::<T>::exists() {
+				return T::DbWeight::get().reads(1);
+			}
+			Self::start_first_round(frame_system::Pallet::<T>::block_number());
+			T::DbWeight::get().reads_writes(3, 4)
+		}
+
 		fn on_finalize(now: T::BlockNumber) {
 			let mut current_round = CurrentRound::<T>::get();
 			if current_round.should_goto_next_round(now) {
@@ -543,6 +565,11 @@ pub mod pallet {
 				length
 			}
 		}
+		/// Index of the round
+		pub fn index(&self) -> RoundIndex {
+			self.index
+		}
+
 		pub fn next_round_index(&self) -> u32 {
 			&self.index + 1u32
 		}
@@ -631,23 +658,13 @@ pub mod pallet {
 			TotalStaked::<T>::put(total_staked);
 
 			// Start Round 1 at Block 0
-			let round: RoundInfo<T::BlockNumber> =
-				RoundInfo::new(1u32, 0u32.into(), T::BlocksPerRound::get());
-			CurrentRound::<T>::put(round);
-			TotalStakedAt::<T>::insert(1u32, TotalStaked::<T>::get());
-			TotalIssuanceAt::<T>::insert(1u32, T::Currency::total_issuance());
-			Settings::<T>::put(SettingStruct {
-				bond_duration: T::BondDuration::get(),
-				blocks_per_round: T::BlocksPerRound::get(),
-				desired_target: T::DesiredTarget::get()
-			});
-			<Pallet<T>>::deposit_event(Event::NewRoundStart(1u32, 1u32 + T::BlocksPerRound::get() as u32));
+			Pallet::<T>::start_first_round(0u32.into());
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::config())]
 		pub fn config(
 			origin: OriginFor<T>,
 			settings: SettingStruct
@@ -661,7 +678,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::bond())]
 		pub fn bond(
 			origin: OriginFor<T>,
 			amount: BalanceOf<T>
@@ -695,7 +712,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::force_onboard())]
 		pub fn force_onboard(
 			origin: OriginFor<T>,
 			candidate: T::AccountId
@@ -717,7 +734,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::bond_extra())]
 		pub fn bond_extra(
 			origin: OriginFor<T>,
 			extra: BalanceOf<T>
@@ -748,7 +765,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::bond_less())]
 		pub fn bond_less(
 			origin: OriginFor<T>,
 			less: BalanceOf<T>
@@ -777,7 +794,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::collator_unbond(T::MaxNominationsPerCollator::get()))]
 		pub fn collator_unbond(
 			origin: OriginFor<T>,
 		) -> DispatchResultWithPostInfo {
@@ -804,7 +821,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::nominate())]
 		pub fn nominate(
 			origin: OriginFor<T>,
 			candidate: T::AccountId,
@@ -861,7 +878,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::nominate_extra())]
 		pub fn nominate_extra(
 			origin: OriginFor<T>,
 			candidate: T::AccountId,
@@ -899,7 +916,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::nominate_less())]
 		pub fn nominate_less(
 			origin: OriginFor<T>,
 			candidate: T::AccountId,
@@ -948,7 +965,7 @@ pub mod pallet {
 			Ok(Default::default())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::nominator_leave_collator())]
 		pub fn nominator_leave_collator(
 			origin: OriginFor<T>,
 			candidate: T::AccountId,
@@ -975,10 +992,27 @@ pub mod pallet {
 	}
 
 	impl <T: Config> Pallet<T> {
+		/// Start round 1 at block `now`, with the settings of the runtime's config
+		pub(crate) fn start_first_round(now: T::BlockNumber) {
+			let round: RoundInfo<T::BlockNumber> =
+				RoundInfo::new(1u32, now, T::BlocksPerRound::get());
+			CurrentRound::<T>::put(round);
+			TotalStakedAt::<T>::insert(1u32, TotalStaked::<T>::get());
+			TotalIssuanceAt::<T>::insert(1u32, T::Currency::total_issuance());
+			Settings::<T>::put(SettingStruct {
+				bond_duration: T::BondDuration::get(),
+				blocks_per_round: T::BlocksPerRound::get(),
+				desired_target: T::DesiredTarget::get()
+			});
+			Self::deposit_event(Event::NewRoundStart(1u32, 1u32 + T::BlocksPerRound::get() as u32));
+		}
+
 		fn payout_stakers(current_round: RoundIndex) {
-			let mint = |amount: BalanceOf<T>, to: T::AccountId| {
+			let mut minted: BalanceOf<T> = Zero::zero();
+			let mut mint = |amount: BalanceOf<T>, to: T::AccountId| {
 				if amount > T::Currency::minimum_balance() {
 					if let Ok(imb) = T::Currency::deposit_into_existing(&to, amount) {
+						minted = minted.saturating_add(imb.peek());
 						Self::deposit_event(Event::Rewarded(to.clone(), imb.peek()));
 					}
 				}
@@ -1035,6 +1069,8 @@ pub mod pallet {
 					}
 				}
 
+				let remainder = payout.saturating_sub(minted);
+				T::RewardRemainder::on_unbalanced(T::Currency::issue(remainder));
 			}
 		}
 
@@ -1418,4 +1454,14 @@ pub mod pallet {
 		}
 	}
 
+	/// The collators elected for the current round, for an author filter to pick the eligible
+	/// authors from. There are none until the end of the first round.
+	impl<T: Config> Get<Vec<T::AccountId>> for Pallet<T> {
+		fn get() -> Vec<T::AccountId> {
+			<RoundStakerClipped<T>>::iter_prefix(<CurrentRound<T>>::get().index)
+				.map(|(account, _)| account)
+				.collect()
+		}
+	}
+
 }
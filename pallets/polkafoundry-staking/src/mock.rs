@@ -1,7 +1,7 @@
 use crate::{self as stake, Config, CollatorPoints, TotalPoints};
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{GenesisBuild, OnFinalize, OnInitialize},
+	traits::{Currency, GenesisBuild, OnFinalize, OnInitialize, OnUnbalanced},
 };
 use sp_io;
 use sp_runtime::{
@@ -100,6 +100,18 @@ impl Config for Test {
 	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
 	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
 	type DesiredTarget = DesiredTarget;
+	type RewardRemainder = RewardRemainderMock;
+	type WeightInfo = ();
+}
+
+pub const REWARD_REMAINDER_ACCOUNT: AccountId = 1000;
+
+/// Puts the part of a payout not paid to stakers into `REWARD_REMAINDER_ACCOUNT`.
+pub struct RewardRemainderMock;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for RewardRemainderMock {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&REWARD_REMAINDER_ACCOUNT, amount);
+	}
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -681,3 +681,63 @@ fn payout_stakers_work() {
 		assert_eq!(events(), expected);
 	})
 }
+
+#[test]
+fn payout_remainder_goes_to_reward_remainder() {
+	mock_test().execute_with(|| {
+		run_to_block(11);
+		assert_ok!(Staking::nominate(Origin::signed(20), 100, 400));
+		assert_ok!(Staking::nominate(Origin::signed(3), 300, 800));
+		set_author(2, 100, 3000);
+		set_author(2, 200, 2000);
+		set_author(2, 300, 5000);
+		let issuance = Balances::total_issuance();
+
+		run_to_block(31);
+		let rewarded: Balance = events()
+			.into_iter()
+			.filter_map(|e| match e {
+				crate::Event::Rewarded(_, amount) => Some(amount),
+				_ => None,
+			})
+			.sum();
+		let remainder = Balances::free_balance(&REWARD_REMAINDER_ACCOUNT);
+		assert!(remainder > 0);
+		assert_eq!(Balances::total_issuance(), issuance + rewarded + remainder);
+	})
+}
+
+#[test]
+fn runtime_upgrade_starts_the_first_round() {
+	ExtBuilder::build(vec![(1, 1000)], vec![]).execute_with(|| {
+		// A chain adding the pallet in an upgrade has none of its genesis.
+		CurrentRound::<Test>::kill();
+		Settings::<Test>::kill();
+		System::set_block_number(50);
+
+		<Staking as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(Staking::current_round(), RoundInfo::new(1, 50, 10));
+		assert_eq!(Staking::settings().blocks_per_round, 10);
+
+		// Later upgrades leave the rounds alone.
+		CurrentRound::<Test>::put(RoundInfo::new(2, 60, 10));
+		<Staking as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(Staking::current_round(), RoundInfo::new(2, 60, 10));
+	})
+}
+
+#[test]
+fn potential_authors_are_the_elected_collators() {
+	mock_test().execute_with(|| {
+		let elected = || {
+			let mut elected = <Staking as frame_support::traits::Get<Vec<AccountId>>>::get();
+			elected.sort();
+			elected
+		};
+		// Nobody is elected during the first round.
+		assert!(elected().is_empty());
+
+		run_to_block(11);
+		assert_eq!(elected(), vec![200, 300]);
+	})
+}
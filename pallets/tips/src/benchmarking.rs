@@ -0,0 +1,136 @@
+//! Tips pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as TipsMod;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::{ensure, traits::{Currency, EnsureOrigin, Get, SortedMembers}};
+use frame_system::RawOrigin;
+use pallet_treasury::BalanceOf;
+use sp_runtime::traits::{Hash, Saturating, Zero};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+const MAX_TIPPERS: u32 = 100;
+
+// Create the pre-requisite information needed to create a `tip_new`.
+fn setup_tip<T: Config>(r: u32, t: u32)
+	-> Result<(T::AccountId, Vec<u8>, T::AccountId, BalanceOf<T>), &'static str>
+{
+	let tippers_count = T::Tippers::count();
+
+	for i in 0..t {
+		let member = account("member", i, SEED);
+		T::Tippers::add(&member);
+		ensure!(T::Tippers::contains(&member), "failed to add tipper");
+	}
+
+	ensure!(T::Tippers::count() == tippers_count + t as usize, "problem creating tippers");
+	let caller = account("member", t - 1, SEED);
+	let reason = vec![0; r as usize];
+	let beneficiary = account("beneficiary", t, SEED);
+	let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+	Ok((caller, reason, beneficiary, value))
+}
+
+// Create `t` new tips for the tip proposal with `hash`.
+// This function automatically makes the tip able to close.
+fn create_tips<T: Config>(t: u32, hash: T::Hash, value: BalanceOf<T>) -> Result<(), &'static str> {
+	for i in 0..t {
+		let caller = account("member", i, SEED);
+		ensure!(T::Tippers::contains(&caller), "caller is not a tipper");
+		TipsMod::<T>::tip(RawOrigin::Signed(caller).into(), hash, value)
+			.map_err(|_| "failed to tip")?;
+	}
+	Tips::<T>::mutate(hash, |maybe_tip| {
+		if let Some(open_tip) = maybe_tip {
+			open_tip.closes = Some(T::BlockNumber::zero());
+		}
+	});
+	Ok(())
+}
+
+fn setup_pot_account<T: Config>() {
+	let pot_account = pallet_treasury::Pallet::<T>::account_id();
+	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000u32.into());
+	let _ = T::Currency::make_free_balance_be(&pot_account, value);
+}
+
+benchmarks! {
+	tip_new {
+		let r in 0 .. T::MaximumReasonLength::get();
+		let t in 1 .. MAX_TIPPERS;
+
+		let (caller, reason, beneficiary, value) = setup_tip::<T>(r, t)?;
+	}: _(RawOrigin::Signed(caller), reason, beneficiary, value)
+	verify {
+		assert_eq!(Tips::<T>::iter().count(), 1);
+	}
+
+	tip {
+		let t in 1 .. MAX_TIPPERS;
+		let (member, reason, beneficiary, value) = setup_tip::<T>(0, t)?;
+		TipsMod::<T>::tip_new(
+			RawOrigin::Signed(member).into(),
+			reason.clone(),
+			beneficiary.clone(),
+			value,
+		).map_err(|_| "failed to open tip")?;
+		let reason_hash = T::Hashing::hash(&reason[..]);
+		let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
+		ensure!(Tips::<T>::contains_key(hash), "tip does not exist");
+		create_tips::<T>(t - 1, hash.clone(), value)?;
+		let caller = account("member", t - 1, SEED);
+	}: _(RawOrigin::Signed(caller), hash, value)
+	verify {
+		assert!(Tips::<T>::get(hash).expect("tip exists; qed").closes.is_some());
+	}
+
+	close_tip {
+		let t in 1 .. MAX_TIPPERS;
+		setup_pot_account::<T>();
+
+		let (member, reason, beneficiary, value) = setup_tip::<T>(0, t)?;
+		TipsMod::<T>::tip_new(
+			RawOrigin::Signed(member).into(),
+			reason.clone(),
+			beneficiary.clone(),
+			value,
+		).map_err(|_| "failed to open tip")?;
+		let reason_hash = T::Hashing::hash(&reason[..]);
+		let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
+		ensure!(Tips::<T>::contains_key(hash), "tip does not exist");
+		create_tips::<T>(t, hash.clone(), value)?;
+
+		let caller = account("caller", t, SEED);
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(!T::Currency::free_balance(&beneficiary).is_zero());
+	}
+
+	slash_tip {
+		let t in 1 .. MAX_TIPPERS;
+
+		let (member, reason, beneficiary, value) = setup_tip::<T>(0, t)?;
+		TipsMod::<T>::tip_new(
+			RawOrigin::Signed(member).into(),
+			reason.clone(),
+			beneficiary.clone(),
+			value,
+		).map_err(|_| "failed to open tip")?;
+		let reason_hash = T::Hashing::hash(&reason[..]);
+		let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
+		ensure!(Tips::<T>::contains_key(hash), "tip does not exist");
+		let reject_origin = T::RejectOrigin::successful_origin();
+	}: _<T::Origin>(reject_origin, hash)
+	verify {
+		assert!(!Tips::<T>::contains_key(hash));
+	}
+}
+
+impl_benchmark_test_suite!(
+	TipsMod,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
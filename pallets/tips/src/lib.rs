@@ -0,0 +1,306 @@
+//! # Tipping Pallet
+//!
+//! A lightweight way for governance members to reward small contributions out of the treasury
+//! without a full spend proposal.
+//!
+//! - `tip_new`: a tipper opens a tip for a beneficiary with a reason and their own tip value.
+//! - `tip`: other tippers declare their values.
+//! - Once more than half of the tippers have declared, the tip closes after `TipCountdown`
+//!   blocks and `close_tip` pays the median of the declared values from the treasury.
+//! - `slash_tip`: the treasury's `RejectOrigin` can remove a tip that should not be paid.
+//!
+//! Tips are paid with the treasury's `pay_out`, so the pot and origins stay the treasury's own.
+//! The flow lives in its own pallet, like bounties, so a runtime can run the treasury without it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+
+#[cfg(test)]
+pub(crate) mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[pallet]
+pub mod pallet {
+	use codec::{Decode, Encode};
+	use frame_support::dispatch::DispatchResultWithPostInfo;
+	use frame_support::traits::{ContainsLengthBound, EnsureOrigin, Get, SortedMembers};
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_treasury::BalanceOf;
+	use sp_runtime::traits::{BadOrigin, Hash, Zero};
+	use sp_runtime::RuntimeDebug;
+	use sp_std::{prelude::*, vec};
+	use crate::WeightInfo;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_treasury::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin from which tippers must come.
+		///
+		/// `ContainsLengthBound::max_len` must be cost free (i.e. no storage read or heavy
+		/// operation).
+		type Tippers: SortedMembers<Self::AccountId> + ContainsLengthBound;
+
+		/// The period for which a tip remains open after is has achieved threshold tippers.
+		#[pallet::constant]
+		type TipCountdown: Get<Self::BlockNumber>;
+
+		/// Maximum acceptable reason length.
+		#[pallet::constant]
+		type MaximumReasonLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	pub type OpenTipOf<T> = OpenTip<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as frame_system::Config>::Hash,
+	>;
+
+	/// An open tipping "motion". Retains all details of a tip including information on the
+	/// finder and the members who have voted.
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct OpenTip<AccountId, Balance, BlockNumber, Hash> {
+		/// The hash of the reason for the tip. The reason should be a human-readable UTF-8 encoded
+		/// string. A URL would be sensible.
+		pub reason: Hash,
+		/// The account to be tipped.
+		pub who: AccountId,
+		/// The tipper who opened the tip.
+		pub finder: AccountId,
+		/// The block number at which this tip will close if `Some`. If `None`, then no closing is
+		/// scheduled.
+		pub closes: Option<BlockNumber>,
+		/// The members who have voted for this tip. Sorted by AccountId.
+		pub tips: Vec<(AccountId, Balance)>,
+	}
+
+	/// Tips that are not yet completed. Keyed by the hash of `(reason, who)` from the value.
+	#[pallet::storage]
+	#[pallet::getter(fn tips)]
+	pub type Tips<T: Config> = StorageMap<_, Twox64Concat, T::Hash, OpenTipOf<T>, OptionQuery>;
+
+	/// Simple preimage lookup from the reason's hash to the original data.
+	#[pallet::storage]
+	#[pallet::getter(fn reasons)]
+	pub type Reasons<T: Config> = StorageMap<_, Identity, T::Hash, Vec<u8>, OptionQuery>;
+
+	/// Error for the tips module.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The reason given is just too big.
+		ReasonTooBig,
+		/// The tip was already found/started.
+		AlreadyKnown,
+		/// The tip hash is unknown.
+		UnknownTip,
+		/// The tip cannot be claimed/closed because there are not enough tippers yet.
+		StillOpen,
+		/// The tip cannot be claimed/closed because it's still in the countdown period.
+		Premature,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new tip suggestion has been opened. \[tip_hash\]
+		NewTip(T::Hash),
+		/// A tip suggestion has reached threshold and is closing. \[tip_hash\]
+		TipClosing(T::Hash),
+		/// A tip suggestion has been closed. \[tip_hash, who, payout\]
+		TipClosed(T::Hash, T::AccountId, BalanceOf<T>),
+		/// A tip suggestion has been slashed. \[tip_hash\]
+		TipSlashed(T::Hash),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Give a tip for something new.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the signing account must be a
+		/// member of the `Tippers` set.
+		///
+		/// - `reason`: The reason for, or the thing that deserves, the tip; generally this will be
+		///   a UTF-8-encoded URL.
+		/// - `who`: The account which should be credited for the tip.
+		/// - `tip_value`: The amount of tip that the sender would like to give. The median tip
+		///   value of active tippers will be given to the `who`.
+		#[pallet::weight(<T as Config>::WeightInfo::tip_new(reason.len() as u32, T::Tippers::max_len() as u32))]
+		pub fn tip_new(
+			origin: OriginFor<T>,
+			reason: Vec<u8>,
+			who: T::AccountId,
+			tip_value: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let tipper = ensure_signed(origin)?;
+			ensure!(T::Tippers::contains(&tipper), BadOrigin);
+			ensure!(
+				reason.len() <= T::MaximumReasonLength::get() as usize,
+				Error::<T>::ReasonTooBig,
+			);
+
+			let reason_hash = T::Hashing::hash(&reason[..]);
+			let hash = T::Hashing::hash_of(&(&reason_hash, &who));
+			ensure!(!Tips::<T>::contains_key(&hash), Error::<T>::AlreadyKnown);
+
+			Reasons::<T>::insert(&reason_hash, &reason);
+			Self::deposit_event(Event::NewTip(hash.clone()));
+			let mut tip = OpenTip {
+				reason: reason_hash,
+				who,
+				finder: tipper.clone(),
+				closes: None,
+				tips: vec![],
+			};
+			// The finder's tip alone may reach the threshold when there are few tippers.
+			if Self::insert_tip_and_check_closing(&mut tip, tipper, tip_value) {
+				Self::deposit_event(Event::TipClosing(hash.clone()));
+			}
+			Tips::<T>::insert(&hash, tip);
+
+			Ok(Default::default())
+		}
+
+		/// Declare a tip value for an already-open tip.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the signing account must be a
+		/// member of the `Tippers` set. Declaring again replaces the sender's previous value.
+		///
+		/// Once more than half of the tippers have declared, the tip closes after `TipCountdown`.
+		#[pallet::weight(<T as Config>::WeightInfo::tip(T::Tippers::max_len() as u32))]
+		pub fn tip(
+			origin: OriginFor<T>,
+			hash: T::Hash,
+			tip_value: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let tipper = ensure_signed(origin)?;
+			ensure!(T::Tippers::contains(&tipper), BadOrigin);
+
+			let mut tip = Tips::<T>::get(hash).ok_or(Error::<T>::UnknownTip)?;
+			if Self::insert_tip_and_check_closing(&mut tip, tipper, tip_value) {
+				Self::deposit_event(Event::TipClosing(hash.clone()));
+			}
+			Tips::<T>::insert(&hash, tip);
+
+			Ok(Default::default())
+		}
+
+		/// Close and payout a tip.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// The tip identified by `hash` must have finished its countdown period.
+		#[pallet::weight(<T as Config>::WeightInfo::close_tip(T::Tippers::max_len() as u32))]
+		pub fn close_tip(origin: OriginFor<T>, hash: T::Hash) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let tip = Tips::<T>::get(hash).ok_or(Error::<T>::UnknownTip)?;
+			let n = tip.closes.as_ref().ok_or(Error::<T>::StillOpen)?;
+			ensure!(frame_system::Pallet::<T>::block_number() >= *n, Error::<T>::Premature);
+			// closed.
+			Reasons::<T>::remove(&tip.reason);
+			Tips::<T>::remove(hash);
+			Self::payout_tip(hash, tip);
+
+			Ok(Default::default())
+		}
+
+		/// Remove and slash an already-open tip.
+		///
+		/// May only be called from the treasury's `RejectOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::slash_tip(T::Tippers::max_len() as u32))]
+		pub fn slash_tip(origin: OriginFor<T>, hash: T::Hash) -> DispatchResultWithPostInfo {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let tip = Tips::<T>::take(hash).ok_or(Error::<T>::UnknownTip)?;
+			Reasons::<T>::remove(&tip.reason);
+
+			Self::deposit_event(Event::TipSlashed(hash));
+			Ok(Default::default())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Given a mutable reference to an `OpenTip`, insert the tip into it and check whether it
+		/// closes, if so, then deposit the relevant event and set closing accordingly.
+		///
+		/// `O(T)` and one storage access.
+		fn insert_tip_and_check_closing(
+			tip: &mut OpenTipOf<T>,
+			tipper: T::AccountId,
+			tip_value: BalanceOf<T>,
+		) -> bool {
+			match tip.tips.binary_search_by_key(&&tipper, |x| &x.0) {
+				Ok(pos) => tip.tips[pos] = (tipper, tip_value),
+				Err(pos) => tip.tips.insert(pos, (tipper, tip_value)),
+			}
+			Self::retain_active_tips(&mut tip.tips);
+			let threshold = T::Tippers::count() / 2 + 1;
+			if tip.tips.len() >= threshold && tip.closes.is_none() {
+				tip.closes = Some(frame_system::Pallet::<T>::block_number() + T::TipCountdown::get());
+				true
+			} else {
+				false
+			}
+		}
+
+		/// Remove any non-members of `Tippers` from a `tips` vector. `O(T)`.
+		fn retain_active_tips(tips: &mut Vec<(T::AccountId, BalanceOf<T>)>) {
+			let members = T::Tippers::sorted_members();
+			let mut members_iter = members.iter();
+			let mut member = members_iter.next();
+			tips.retain(|(ref a, _)| loop {
+				match member {
+					None => break false,
+					Some(m) if m > a => break false,
+					Some(m) => {
+						member = members_iter.next();
+						if m < a {
+							continue
+						} else {
+							break true
+						}
+					},
+				}
+			});
+		}
+
+		/// Execute the payout of a tip: the median of the active tippers' values, capped by what
+		/// the treasury holds.
+		///
+		/// Up to three balance operations.
+		/// Plus `O(T)` (`T` is Tippers length).
+		fn payout_tip(hash: T::Hash, tip: OpenTipOf<T>) {
+			let mut tips = tip.tips;
+			Self::retain_active_tips(&mut tips);
+			tips.sort_by_key(|i| i.1);
+
+			let payout = if tips.is_empty() {
+				Zero::zero()
+			} else {
+				pallet_treasury::Pallet::<T>::pay_out(&tip.who, tips[tips.len() / 2].1)
+			};
+
+			Self::deposit_event(Event::TipClosed(hash, tip.who, payout));
+		}
+	}
+}
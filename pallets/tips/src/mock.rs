@@ -0,0 +1,227 @@
+use crate::{self as pallet_tips, Config};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ContainsLengthBound, SortedMembers},
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use sp_core::{H160, H256};
+use sp_io;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Permill,
+};
+use sp_std::convert::From;
+use std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type Balance = u64;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 1;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 1;
+	pub const MetadataDepositPerByte: Balance = 1;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"Treasury");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: u64 = 1;
+	pub const SpendPeriod: u64 = 2;
+	pub const MaxApprovals: u32 = 100;
+	pub const Burn: Permill = Permill::from_percent(0);
+	pub const MaxPaymentsPerBlock: u32 = 50;
+}
+
+impl pallet_treasury::Config for Test {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type Event = Event;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type MaxApprovals = MaxApprovals;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type MaxPaymentsPerBlock = MaxPaymentsPerBlock;
+	type Assets = Assets;
+	type Erc20 = ();
+	type AddressMapping = TestAddressMapping;
+	type WeightInfo = ();
+}
+
+/// Maps an Ethereum address to the `u128` built from its first 16 bytes.
+pub struct TestAddressMapping;
+
+impl pallet_evm::AddressMapping<AccountId> for TestAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		let mut bytes = [0u8; 16];
+		bytes.copy_from_slice(&address.as_bytes()[0..16]);
+		AccountId::from_le_bytes(bytes)
+	}
+}
+
+thread_local! {
+	static TEN_TO_FOURTEEN: RefCell<Vec<AccountId>> = RefCell::new(vec![10, 11, 12, 13, 14]);
+}
+
+/// Accounts 10 to 14 are the tippers.
+pub struct TenToFourteen;
+impl SortedMembers<AccountId> for TenToFourteen {
+	fn sorted_members() -> Vec<AccountId> {
+		TEN_TO_FOURTEEN.with(|v| v.borrow().clone())
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(new: &AccountId) {
+		TEN_TO_FOURTEEN.with(|v| {
+			let mut members = v.borrow_mut();
+			members.push(*new);
+			members.sort();
+		})
+	}
+}
+/// Replace the tippers with `members`, which must be sorted.
+pub(crate) fn set_tippers(members: Vec<AccountId>) {
+	TEN_TO_FOURTEEN.with(|v| *v.borrow_mut() = members);
+}
+
+impl ContainsLengthBound for TenToFourteen {
+	fn max_len() -> usize {
+		TEN_TO_FOURTEEN.with(|v| v.borrow().len())
+	}
+	fn min_len() -> usize {
+		0
+	}
+}
+
+/// Drop `who` from the tippers.
+pub(crate) fn remove_tipper(who: AccountId) {
+	TEN_TO_FOURTEEN.with(|v| v.borrow_mut().retain(|m| *m != who));
+}
+
+parameter_types! {
+	pub const TipCountdown: u64 = 1;
+	pub const MaximumReasonLength: u32 = 16384;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Tippers = TenToFourteen;
+	type TipCountdown = TipCountdown;
+	type MaximumReasonLength = MaximumReasonLength;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
+		Tipping: pallet_tips::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	set_tippers(vec![10, 11, 12, 13, 14]);
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(0, 100), (1, 98), (2, 1)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub(crate) fn events() -> Vec<super::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::pallet_tips(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>()
+}
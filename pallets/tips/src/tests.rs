@@ -0,0 +1,170 @@
+use crate::*;
+use frame_support::{assert_noop, assert_ok, traits::{Currency, Get}};
+use sp_runtime::traits::{BadOrigin, BlakeTwo256, Hash};
+use mock::*;
+use sp_core::H256;
+
+fn tip_hash() -> H256 {
+	BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 3u128))
+}
+
+// Put `value` into the treasury pot on top of its existential deposit.
+fn fund_treasury(value: Balance) {
+	Balances::make_free_balance_be(&Treasury::account_id(), value + ExistentialDeposit::get());
+}
+
+#[test]
+fn tip_new_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Tipping::tip_new(Origin::signed(0), b"awesome.dot".to_vec(), 3, 10), BadOrigin);
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+
+		let h = tip_hash();
+		assert_eq!(Tipping::reasons(BlakeTwo256::hash(b"awesome.dot")), Some(b"awesome.dot".to_vec()));
+		assert_eq!(
+			Tipping::tips(h),
+			Some(OpenTip {
+				reason: BlakeTwo256::hash(b"awesome.dot"),
+				who: 3,
+				finder: 10,
+				closes: None,
+				tips: vec![(10, 10)],
+			})
+		);
+		assert_eq!(events(), vec![crate::Event::NewTip(h)]);
+
+		assert_noop!(
+			Tipping::tip_new(Origin::signed(11), b"awesome.dot".to_vec(), 3, 10),
+			Error::<Test>::AlreadyKnown,
+		);
+		assert_noop!(
+			Tipping::tip_new(Origin::signed(11), vec![0; 16385], 3, 10),
+			Error::<Test>::ReasonTooBig,
+		);
+	});
+}
+
+#[test]
+fn tip_starts_countdown_at_threshold() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_noop!(Tipping::tip(Origin::signed(0), h, 10), BadOrigin);
+		assert_noop!(Tipping::tip(Origin::signed(11), H256::zero(), 10), Error::<Test>::UnknownTip);
+
+		assert_ok!(Tipping::tip(Origin::signed(11), h, 10));
+		assert_eq!(Tipping::tips(h).unwrap().closes, None);
+		// Three of five tippers reach the threshold.
+		assert_ok!(Tipping::tip(Origin::signed(12), h, 10));
+		assert_eq!(Tipping::tips(h).unwrap().closes, Some(1 + TipCountdown::get()));
+		assert_eq!(events().last(), Some(&crate::Event::TipClosing(h)));
+	});
+}
+
+#[test]
+fn tip_threshold_is_more_than_half() {
+	new_test_ext().execute_with(|| {
+		set_tippers(vec![10, 11, 12, 13, 14, 15]);
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_ok!(Tipping::tip(Origin::signed(11), h, 10));
+		// Half of six tippers is not enough.
+		assert_ok!(Tipping::tip(Origin::signed(12), h, 10));
+		assert_eq!(Tipping::tips(h).unwrap().closes, None);
+		assert_ok!(Tipping::tip(Origin::signed(13), h, 10));
+		assert_eq!(Tipping::tips(h).unwrap().closes, Some(1 + TipCountdown::get()));
+	});
+}
+
+#[test]
+fn single_tipper_starts_countdown_with_tip_new() {
+	new_test_ext().execute_with(|| {
+		set_tippers(vec![10]);
+		fund_treasury(100);
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_eq!(Tipping::tips(h).unwrap().closes, Some(1 + TipCountdown::get()));
+		assert_eq!(events(), vec![crate::Event::NewTip(h), crate::Event::TipClosing(h)]);
+
+		System::set_block_number(1 + TipCountdown::get());
+		assert_ok!(Tipping::close_tip(Origin::signed(0), h));
+		assert_eq!(Balances::free_balance(3), 10);
+	});
+}
+
+#[test]
+fn close_tip_pays_median() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_ok!(Tipping::tip(Origin::signed(11), h, 10));
+		assert_noop!(Tipping::close_tip(Origin::signed(0), h), Error::<Test>::StillOpen);
+
+		assert_ok!(Tipping::tip(Origin::signed(12), h, 1000));
+		// Re-declaring replaces the earlier value.
+		assert_ok!(Tipping::tip(Origin::signed(11), h, 20));
+		assert_noop!(Tipping::close_tip(Origin::signed(0), h), Error::<Test>::Premature);
+
+		System::set_block_number(2);
+		assert_noop!(Tipping::close_tip(Origin::none(), h), BadOrigin);
+		assert_ok!(Tipping::close_tip(Origin::signed(0), h));
+
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Treasury::pot(), 80);
+		assert_eq!(Tipping::tips(h), None);
+		assert_eq!(Tipping::reasons(BlakeTwo256::hash(b"awesome.dot")), None);
+		assert_eq!(events().last(), Some(&crate::Event::TipClosed(h, 3, 20)));
+		assert_noop!(Tipping::close_tip(Origin::signed(0), h), Error::<Test>::UnknownTip);
+	});
+}
+
+#[test]
+fn close_tip_is_capped_by_pot() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(15);
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 20));
+		let h = tip_hash();
+		assert_ok!(Tipping::tip(Origin::signed(11), h, 20));
+		assert_ok!(Tipping::tip(Origin::signed(12), h, 20));
+
+		System::set_block_number(2);
+		assert_ok!(Tipping::close_tip(Origin::signed(0), h));
+		assert_eq!(Balances::free_balance(3), 15);
+		assert_eq!(Treasury::pot(), 0);
+	});
+}
+
+#[test]
+fn close_tip_ignores_former_tippers() {
+	new_test_ext().execute_with(|| {
+		fund_treasury(100);
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_ok!(Tipping::tip(Origin::signed(11), h, 10));
+		assert_ok!(Tipping::tip(Origin::signed(12), h, 40));
+		assert_ok!(Tipping::tip(Origin::signed(13), h, 40));
+
+		// Without 10 and 11 the median moves up.
+		remove_tipper(10);
+		remove_tipper(11);
+		System::set_block_number(2);
+		assert_ok!(Tipping::close_tip(Origin::signed(0), h));
+		assert_eq!(Balances::free_balance(3), 40);
+	});
+}
+
+#[test]
+fn slash_tip_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Tipping::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_noop!(Tipping::slash_tip(Origin::signed(10), h), BadOrigin);
+		assert_ok!(Tipping::slash_tip(Origin::root(), h));
+
+		assert_eq!(Tipping::tips(h), None);
+		assert_eq!(Tipping::reasons(BlakeTwo256::hash(b"awesome.dot")), None);
+		assert_eq!(events().last(), Some(&crate::Event::TipSlashed(h)));
+		assert_noop!(Tipping::slash_tip(Origin::root(), h), Error::<Test>::UnknownTip);
+	});
+}
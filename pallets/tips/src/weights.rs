@@ -0,0 +1,76 @@
+//! Weights for pallet_tips
+//!
+//! Estimated from the storage accesses of each call, not benchmarked yet.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_tips.
+pub trait WeightInfo {
+	fn tip_new(r: u32, t: u32, ) -> Weight;
+	fn tip(t: u32, ) -> Weight;
+	fn close_tip(t: u32, ) -> Weight;
+	fn slash_tip(t: u32, ) -> Weight;
+}
+
+/// Weights for pallet_tips using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn tip_new(r: u32, t: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((150_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn tip(t: u32, ) -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add((650_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn close_tip(t: u32, ) -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add((380_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn slash_tip(t: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((10_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn tip_new(r: u32, t: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((150_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn tip(t: u32, ) -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add((650_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn close_tip(t: u32, ) -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add((380_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn slash_tip(t: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((10_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,39 @@
+//! Runtime API definition for the treasury pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// An active recurring payment out of the treasury.
+#[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ScheduledPayment<AccountId, Balance, BlockNumber> {
+	/// Index of the payment schedule
+	pub index: u32,
+	/// Account receiving the payments
+	pub dest: AccountId,
+	/// Amount paid every period
+	pub amount: Balance,
+	/// Blocks between two payments
+	pub period: BlockNumber,
+	/// Payments left, including skipped ones
+	pub remaining: u32,
+	/// Block at which the next payment is due
+	pub next_payment: BlockNumber,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait TreasuryApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// All active payment schedules, in index order.
+		fn scheduled_payments() -> Vec<ScheduledPayment<AccountId, Balance, BlockNumber>>;
+	}
+}
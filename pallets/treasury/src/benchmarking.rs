@@ -0,0 +1,212 @@
+//! Treasury pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as Treasury;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{ensure, traits::{Currency, EnsureOrigin, Get, Hooks}};
+use frame_system::RawOrigin;
+use sp_core::{H160, U256};
+use sp_runtime::traits::{Bounded, Zero};
+use sp_std::vec::Vec;
+
+const SEED: u32 = 0;
+
+/// Sets up what the asset and ERC-20 benchmarks transfer, which the pallet can not create through
+/// `Assets` and `Erc20`.
+pub trait BenchmarkHelper: Config {
+    /// Create an asset with a minimum balance of at most one unit, `who` holding `amount` of it.
+    fn create_asset(who: &Self::AccountId, amount: AssetBalanceOf<Self>) -> AssetIdOf<Self>;
+    /// A token `Erc20` can transfer `amount` of from the treasury's EVM address.
+    fn create_erc20(amount: U256) -> H160;
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+// Create a funded proposer and a spend proposal from them.
+fn setup_proposal<T: Config>(u: u32) -> (T::AccountId, BalanceOf<T>, T::AccountId) {
+    let caller = account("caller", u, SEED);
+    let amount = T::Currency::minimum_balance() * 100u32.into();
+    T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+    let beneficiary = account("beneficiary", u, SEED);
+    (caller, amount, beneficiary)
+}
+
+// Create and approve `n` proposals.
+fn create_approved_proposals<T: Config>(n: u32) -> Result<(), &'static str> {
+    for i in 0..n {
+        let (caller, amount, beneficiary) = setup_proposal::<T>(i);
+        Treasury::<T>::propose_spend(RawOrigin::Signed(caller).into(), amount, beneficiary)
+            .map_err(|_| "failed to propose spend")?;
+        let proposal_id = Treasury::<T>::proposal_count() - 1;
+        Treasury::<T>::approve_proposal(T::ApproveOrigin::successful_origin(), proposal_id)
+            .map_err(|_| "failed to approve proposal")?;
+    }
+    ensure!(Approvals::<T>::get().len() == n as usize, "Not all approved");
+    Ok(())
+}
+
+// Schedule `n` payments, all due `period` blocks from now.
+fn create_payment_schedules<T: Config>(n: u32, period: T::BlockNumber) -> Result<(), &'static str> {
+    let amount = T::Currency::minimum_balance() * 100u32.into();
+    for i in 0..n {
+        let dest = account("dest", i, SEED);
+        Treasury::<T>::schedule_payment(T::ApproveOrigin::successful_origin(), dest, amount, period, 2)
+            .map_err(|_| "failed to schedule payment")?;
+    }
+    Ok(())
+}
+
+benchmarks! {
+    where_clause { where T: BenchmarkHelper }
+
+    donate {
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let amount = T::Currency::minimum_balance() * 100u32.into();
+    }: _(RawOrigin::Signed(caller), amount)
+    verify {
+        assert_eq!(T::Currency::free_balance(&Treasury::<T>::account_id()), amount);
+    }
+
+    allocate {
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let amount = T::Currency::minimum_balance() * 100u32.into();
+        T::Currency::make_free_balance_be(&Treasury::<T>::account_id(), amount * 2u32.into());
+    }: _(RawOrigin::Root, dest.clone(), amount)
+    verify {
+        assert_eq!(T::Currency::free_balance(&dest), amount);
+    }
+
+    propose_spend {
+        let (caller, amount, beneficiary) = setup_proposal::<T>(SEED);
+    }: _(RawOrigin::Signed(caller), amount, beneficiary)
+    verify {
+        assert_eq!(Treasury::<T>::proposal_count(), 1);
+    }
+
+    reject_proposal {
+        let (caller, amount, beneficiary) = setup_proposal::<T>(SEED);
+        Treasury::<T>::propose_spend(RawOrigin::Signed(caller).into(), amount, beneficiary)
+            .map_err(|_| "failed to propose spend")?;
+        let reject_origin = T::RejectOrigin::successful_origin();
+    }: _<T::Origin>(reject_origin, 0)
+    verify {
+        assert!(Treasury::<T>::proposals(0).is_none());
+    }
+
+    approve_proposal {
+        let p in 0 .. T::MaxApprovals::get() - 1;
+        create_approved_proposals::<T>(p)?;
+        let (caller, amount, beneficiary) = setup_proposal::<T>(p);
+        Treasury::<T>::propose_spend(RawOrigin::Signed(caller).into(), amount, beneficiary)
+            .map_err(|_| "failed to propose spend")?;
+        let approve_origin = T::ApproveOrigin::successful_origin();
+    }: _<T::Origin>(approve_origin, p)
+    verify {
+        assert_eq!(Approvals::<T>::get().len(), p as usize + 1);
+    }
+
+    on_initialize_proposals {
+        let p in 0 .. T::MaxApprovals::get();
+        create_approved_proposals::<T>(p)?;
+        T::Currency::make_free_balance_be(
+            &Treasury::<T>::account_id(),
+            BalanceOf::<T>::max_value() / 2u32.into(),
+        );
+    }: {
+        Treasury::<T>::on_initialize(T::BlockNumber::zero());
+    }
+    verify {
+        assert!(Approvals::<T>::get().is_empty());
+    }
+
+    schedule_payment {
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let amount = T::Currency::minimum_balance() * 100u32.into();
+        let approve_origin = T::ApproveOrigin::successful_origin();
+    }: _<T::Origin>(approve_origin, dest, amount, 10u32.into(), 12)
+    verify {
+        assert!(Treasury::<T>::payment_schedules(0).is_some());
+    }
+
+    cancel_payment {
+        create_payment_schedules::<T>(1, 10u32.into())?;
+        let approve_origin = T::ApproveOrigin::successful_origin();
+    }: _<T::Origin>(approve_origin, 0)
+    verify {
+        assert!(Treasury::<T>::payment_schedules(0).is_none());
+    }
+
+    // Worst case: every payment is made and rescheduled.
+    on_initialize_payments {
+        let p in 0 .. T::MaxPaymentsPerBlock::get();
+        let period: T::BlockNumber = 10u32.into();
+        create_payment_schedules::<T>(p, period)?;
+        T::Currency::make_free_balance_be(
+            &Treasury::<T>::account_id(),
+            BalanceOf::<T>::max_value() / 2u32.into(),
+        );
+        let due = frame_system::Pallet::<T>::block_number() + period;
+    }: {
+        Treasury::<T>::execute_payments(due);
+    }
+    verify {
+        for i in 0..p {
+            let schedule = Treasury::<T>::payment_schedules(i).expect("rescheduled; qed");
+            assert_eq!(schedule.remaining, 1);
+        }
+    }
+
+    donate_asset {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount: AssetBalanceOf<T> = 1_000_000u32.into();
+        let asset_id = T::create_asset(&caller, amount);
+    }: _(RawOrigin::Signed(caller), asset_id, amount)
+    verify {
+        assert_eq!(Treasury::<T>::asset_pot(asset_id), amount);
+    }
+
+    allocate_asset {
+        let amount: AssetBalanceOf<T> = 1_000_000u32.into();
+        let asset_id = T::create_asset(&Treasury::<T>::account_id(), amount);
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let allocated = amount / 2u32.into();
+    }: _(RawOrigin::Root, asset_id, dest, allocated)
+    verify {
+        assert_eq!(Treasury::<T>::asset_pot(asset_id), amount - allocated);
+    }
+
+    // Covers the pallet's part only, the EVM execution is charged through `Erc20::transfer_weight`.
+    allocate_erc20 {
+        let amount = U256::from(1_000_000u32);
+        let token = T::create_erc20(amount);
+        let dest = H160::repeat_byte(2);
+    }: _(RawOrigin::Root, token, dest, amount, 100_000)
+    verify {
+        assert_last_event::<T>(Event::Erc20FundsAllocated(token, dest, amount).into());
+    }
+
+    // The block's slots are taken by cancelled payments, which leaves carrying `c` over as the
+    // only extra work.
+    carry_over_payments {
+        let c in 0 .. 1_000;
+        let now = frame_system::Pallet::<T>::block_number();
+        let agenda: Vec<PaymentIndex> = (0..T::MaxPaymentsPerBlock::get() + c).collect();
+        PaymentAgenda::<T>::insert(now, agenda);
+    }: {
+        Treasury::<T>::execute_payments(now);
+    }
+    verify {
+        assert_eq!(PaymentAgenda::<T>::get(now + 1u32.into()).len(), c as usize);
+    }
+}
+
+impl_benchmark_test_suite!(
+    Treasury,
+    crate::mock::mock_test(),
+    crate::mock::Test,
+);
@@ -7,19 +7,86 @@ pub use pallet::*;
 pub(crate) mod mock;
 #[cfg(test)]
 mod tests;
+pub mod benchmarking;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// A trait to allow the Treasury pallet to spend its funds for other purposes.
+///
+/// There is an expectation that the implementer of this trait will correctly manage the mutable
+/// variables passed to it:
+/// * `budget_remaining`: How much available funds that can be spent by the treasury. As funds are
+///   spent, you must correctly deduct from this value.
+/// * `imbalance`: Any imbalances that you create should be subsumed in here to maximize
+///   efficiency of updating the total issuance. (i.e. `deposit_creating`)
+/// * `total_weight`: Track any weight that your `spend_funds` implementation uses by updating
+///   this value.
+/// * `missed_any`: If there were items that you want to spend on, but there were not enough
+///   funds, mark this value as `true`. This will prevent the treasury from burning the excess
+///   funds.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait SpendFunds<T: Config> {
+    fn spend_funds(
+        budget_remaining: &mut BalanceOf<T>,
+        imbalance: &mut PositiveImbalanceOf<T>,
+        total_weight: &mut frame_support::weights::Weight,
+        missed_any: &mut bool,
+    );
+}
+
+/// Moves ERC-20 tokens held by the treasury in the EVM.
+pub trait Erc20Transactor {
+    /// The weight of a transfer allowed to use up to `gas_limit` gas.
+    fn transfer_weight(gas_limit: u64) -> frame_support::weights::Weight;
+
+    /// Transfer `amount` of the ERC-20 `token` from `source` to `dest` using at most `gas_limit`
+    /// gas, paid by the account `source` maps to.
+    fn transfer(
+        source: sp_core::H160,
+        token: sp_core::H160,
+        dest: sp_core::H160,
+        amount: sp_core::U256,
+        gas_limit: u64,
+    ) -> frame_support::dispatch::DispatchResult;
+}
+
+impl Erc20Transactor for () {
+    fn transfer_weight(_gas_limit: u64) -> frame_support::weights::Weight {
+        0
+    }
+
+    fn transfer(
+        _source: sp_core::H160,
+        _token: sp_core::H160,
+        _dest: sp_core::H160,
+        _amount: sp_core::U256,
+        _gas_limit: u64,
+    ) -> frame_support::dispatch::DispatchResult {
+        Err(frame_support::dispatch::DispatchError::Other("ERC-20 transfers are not supported"))
+    }
+}
 
 #[pallet]
 pub mod pallet {
     use codec::{Decode, Encode};
     use frame_support::dispatch::DispatchResultWithPostInfo;
     use frame_support::storage::types::{StorageMap, StorageValue, ValueQuery};
-    use frame_support::traits::{Currency, ExistenceRequirement::AllowDeath, Get, Hooks, IsType};
-    use frame_support::{pallet_prelude::*, Blake2_128Concat, PalletId};
+    use frame_support::traits::{
+        Currency, EnsureOrigin, ExistenceRequirement::{AllowDeath, KeepAlive}, Get, Hooks, Imbalance,
+        IsType, OnUnbalanced, ReservableCurrency, WithdrawReasons,
+        tokens::fungibles,
+    };
+    use frame_support::{pallet_prelude::*, PalletId};
     use frame_system::pallet_prelude::*;
     use frame_system::{ensure_root, ensure_signed};
-    use sp_runtime::traits::{AccountIdConversion, Saturating};
-    use sp_runtime::RuntimeDebug;
+    use sp_runtime::traits::{AccountIdConversion, One, Saturating, Zero};
+    use sp_core::{H160, U256};
+    use sp_runtime::{Permill, RuntimeDebug};
     use sp_std::prelude::*;
+    use pallet_evm::AddressMapping;
+    use pallet_treasury_rpc_runtime_api::ScheduledPayment;
+    use crate::{Erc20Transactor, SpendFunds, WeightInfo};
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -27,27 +94,153 @@ pub mod pallet {
         type PalletId: Get<PalletId>;
 
         /// The staking balance.
-        type Currency: Currency<Self::AccountId>;
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// Origin from which approvals must come.
+        type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Origin from which rejections must come.
+        type RejectOrigin: EnsureOrigin<Self::Origin>;
 
         /// The overarching event type.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Fraction of a proposal's value that should be bonded in order to place the proposal.
+        /// An accepted proposal gets these back. A rejected proposal does not.
+        #[pallet::constant]
+        type ProposalBond: Get<Permill>;
+
+        /// Minimum amount of funds that should be placed in a deposit for making a proposal.
+        #[pallet::constant]
+        type ProposalBondMinimum: Get<BalanceOf<Self>>;
+
+        /// Period between successive spends.
+        #[pallet::constant]
+        type SpendPeriod: Get<Self::BlockNumber>;
+
+        /// The maximum number of approvals that can wait in the spending queue.
+        #[pallet::constant]
+        type MaxApprovals: Get<u32>;
+
+        /// Percentage of spare funds (if any) that are burnt per spend period.
+        #[pallet::constant]
+        type Burn: Get<Permill>;
+
+        /// Handler for the unbalanced decrease when treasury funds are burned.
+        type BurnDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Runtime hooks to external pallets using treasury to compute spend funds.
+        type SpendFunds: SpendFunds<Self>;
+
+        /// The maximum number of scheduled payments executed in a single block. Payments due
+        /// beyond that are carried over to the next block.
+        #[pallet::constant]
+        type MaxPaymentsPerBlock: Get<u32>;
+
+        /// Assets other than `Currency` the treasury can hold, e.g. tokens deposited over XCM.
+        type Assets: fungibles::Transfer<Self::AccountId>;
+
+        /// Moves ERC-20 tokens held at the treasury's EVM address.
+        type Erc20: Erc20Transactor;
+
+        /// Maps EVM addresses to accounts the way the EVM does, used to derive the treasury
+        /// account from its EVM address.
+        type AddressMapping: AddressMapping<Self::AccountId>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     /// An index of a proposal. Just a `u32`.
     pub type ProposalIndex = u32;
+    /// An index of a payment schedule. Just a `u32`.
+    pub type PaymentIndex = u32;
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::PositiveImbalance;
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+    pub type AssetIdOf<T> =
+        <<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+    pub type AssetBalanceOf<T> =
+        <<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
     /// A spending proposal.
     #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
     #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
     pub struct Proposal<T: Config> {
+        /// The account proposing it.
+        proposer: T::AccountId,
         /// The account to whom the payment should be made if the proposal is accepted.
         user: T::AccountId,
         /// The (total) amount that should be paid if the proposal is accepted.
         amount: BalanceOf<T>,
+        /// The amount held on deposit (reserved) for making this proposal.
+        bond: BalanceOf<T>,
+    }
+
+    /// A recurring payment out of the pot.
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct PaymentSchedule<AccountId, Balance, BlockNumber> {
+        /// The account receiving the payments.
+        pub dest: AccountId,
+        /// The amount paid every period.
+        pub amount: Balance,
+        /// The number of blocks between two payments.
+        pub period: BlockNumber,
+        /// The number of payments left, including skipped ones.
+        pub remaining: u32,
+        /// The block at which the next payment is due.
+        pub next_payment: BlockNumber,
     }
 
+    pub type PaymentScheduleOf<T> = PaymentSchedule<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// Number of proposals that have been made.
+    #[pallet::storage]
+    #[pallet::getter(fn proposal_count)]
+    pub type ProposalCount<T> = StorageValue<_, ProposalIndex, ValueQuery>;
+
+    /// Proposals that have been made.
+    #[pallet::storage]
+    #[pallet::getter(fn proposals)]
+    pub type Proposals<T: Config> = StorageMap<_, Twox64Concat, ProposalIndex, Proposal<T>, OptionQuery>;
+
+    /// Proposal indices that have been approved but not yet awarded.
+    #[pallet::storage]
+    #[pallet::getter(fn approvals)]
+    pub type Approvals<T> = StorageValue<_, Vec<ProposalIndex>, ValueQuery>;
+
+    /// Number of payment schedules that have been created.
+    #[pallet::storage]
+    #[pallet::getter(fn payment_count)]
+    pub type PaymentCount<T> = StorageValue<_, PaymentIndex, ValueQuery>;
+
+    /// Active payment schedules.
+    #[pallet::storage]
+    #[pallet::getter(fn payment_schedules)]
+    pub type PaymentSchedules<T: Config> =
+        StorageMap<_, Twox64Concat, PaymentIndex, PaymentScheduleOf<T>, OptionQuery>;
+
+    /// Payment schedules due at a block. Cancelled schedules are dropped when their block comes.
+    #[pallet::storage]
+    pub type PaymentAgenda<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<PaymentIndex>, ValueQuery>;
+
+    /// Total of the funds earmarked for other pallets that were returned to the pot, see
+    /// `note_recovered`.
+    #[pallet::storage]
+    #[pallet::getter(fn recovered)]
+    pub type Recovered<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
     /// Error for the treasury module.
     #[pallet::error]
     pub enum Error<T> {
@@ -59,6 +252,20 @@ pub mod pallet {
         FailedDonation,
         /// Allocation is not successful
         FailedAllocation,
+        /// Proposer's balance is too low to cover the bond.
+        InsufficientProposersBalance,
+        /// No proposal at that index.
+        InvalidIndex,
+        /// Too many approvals are already queued.
+        TooManyApprovals,
+        /// A payment schedule needs a non-zero period and at least one payment.
+        InvalidSchedule,
+        /// No payment schedule at that index.
+        InvalidPaymentIndex,
+        /// The ERC-20 transfer out of the treasury's EVM address failed.
+        FailedErc20Allocation,
+        /// The proposal is already queued for payment.
+        AlreadyApproved,
     }
 
     #[pallet::event]
@@ -68,18 +275,77 @@ pub mod pallet {
         DonationReceived(T::AccountId, BalanceOf<T>, BalanceOf<T>),
         /// Treasury has allocated funds to a cause
         FundsAllocated(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+        /// Funds earmarked for another pallet were returned to the Treasury
+        FundsRecovered(BalanceOf<T>, BalanceOf<T>),
+        /// New proposal. \[proposal_index\]
+        Proposed(ProposalIndex),
+        /// A proposal was approved and queued for the next spend period. \[proposal_index\]
+        Approved(ProposalIndex),
+        /// A proposal was rejected; its bond was slashed into the pot. \[proposal_index, slashed\]
+        Rejected(ProposalIndex, BalanceOf<T>),
+        /// Some funds have been paid out to a proposal. \[proposal_index, award, beneficiary\]
+        Awarded(ProposalIndex, BalanceOf<T>, T::AccountId),
+        /// Funds spent in this spend period. \[spent\]
+        Spent(BalanceOf<T>),
+        /// Some of our funds have been burnt. \[burn\]
+        Burnt(BalanceOf<T>),
+        /// Spending has finished; this is the amount that rolls over until next spend.
+        /// \[budget_remaining\]
+        Rollover(BalanceOf<T>),
+        /// A recurring payment was scheduled. \[payment_index, dest, amount, period, count\]
+        PaymentScheduled(PaymentIndex, T::AccountId, BalanceOf<T>, T::BlockNumber, u32),
+        /// A payment schedule was cancelled. \[payment_index\]
+        PaymentCancelled(PaymentIndex),
+        /// A scheduled payment was made. \[payment_index, amount, dest\]
+        PaymentExecuted(PaymentIndex, BalanceOf<T>, T::AccountId),
+        /// The pot could not cover a scheduled payment, so it was skipped.
+        /// \[payment_index, amount, dest\]
+        PaymentSkipped(PaymentIndex, BalanceOf<T>, T::AccountId),
+        /// Donor has made a donation of an asset to the Treasury.
+        /// \[asset_id, donor, amount, asset_pot\]
+        AssetDonationReceived(AssetIdOf<T>, T::AccountId, AssetBalanceOf<T>, AssetBalanceOf<T>),
+        /// Treasury has allocated some of an asset to a cause. \[asset_id, dest, amount, asset_pot\]
+        AssetFundsAllocated(AssetIdOf<T>, T::AccountId, AssetBalanceOf<T>, AssetBalanceOf<T>),
+        /// Treasury has allocated ERC-20 tokens held at its EVM address. \[token, dest, amount\]
+        Erc20FundsAllocated(H160, H160, U256),
     }
 
     #[pallet::pallet]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Make the scheduled payments due, and pay out approved proposals at the end of every
+        /// spend period.
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let mut weight = Self::execute_payments(n);
+            if (n % T::SpendPeriod::get()).is_zero() {
+                weight = weight.saturating_add(Self::spend_funds());
+            }
+            weight
+        }
+
+        /// Move the pot out of the account the treasury used before its account was derived
+        /// from its EVM address. Assets other than `Currency` held there are not moved.
+        fn on_runtime_upgrade() -> Weight {
+            let legacy: T::AccountId = T::PalletId::get().into_account();
+            let treasury = Self::account_id();
+            if legacy == treasury {
+                return 0;
+            }
+            let amount = T::Currency::free_balance(&legacy);
+            if amount.is_zero() {
+                return T::DbWeight::get().reads(1);
+            }
+            let _ = T::Currency::transfer(&legacy, &treasury, amount, AllowDeath);
+            T::DbWeight::get().reads_writes(2, 2)
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Donate some funds to the Treasury
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::donate())]
         pub fn donate(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
 
@@ -99,7 +365,7 @@ pub mod pallet {
         ///
         /// Take funds from the Treasury's pot and send them somewhere. This call requires root origin,
         /// which means it must come from a governance mechanism such as Substrate's Democracy pallet.
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::allocate())]
         pub fn allocate(
             origin: OriginFor<T>,
             dest: T::AccountId,
@@ -118,16 +384,202 @@ pub mod pallet {
             Self::deposit_event(Event::FundsAllocated(dest, amount, Self::pot()));
             Ok(Default::default())
         }
+
+        /// Put forward a suggestion for spending. A deposit proportional to the value is reserved
+        /// and slashed if the proposal is rejected. It is returned once the proposal is awarded.
+        #[pallet::weight(T::WeightInfo::propose_spend())]
+        pub fn propose_spend(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            beneficiary: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let proposer = ensure_signed(origin)?;
+
+            let bond = Self::calculate_bond(amount);
+            T::Currency::reserve(&proposer, bond)
+                .map_err(|_| Error::<T>::InsufficientProposersBalance)?;
+
+            let index = Self::proposal_count();
+            ProposalCount::<T>::put(index + 1);
+            Proposals::<T>::insert(index, Proposal { proposer, user: beneficiary, amount, bond });
+
+            Self::deposit_event(Event::Proposed(index));
+            Ok(Default::default())
+        }
+
+        /// Reject a proposed spend. The original deposit will be slashed into the pot.
+        #[pallet::weight(T::WeightInfo::reject_proposal())]
+        pub fn reject_proposal(
+            origin: OriginFor<T>,
+            proposal_id: ProposalIndex,
+        ) -> DispatchResultWithPostInfo {
+            T::RejectOrigin::ensure_origin(origin)?;
+
+            let proposal = Proposals::<T>::take(proposal_id).ok_or(Error::<T>::InvalidIndex)?;
+            let (imbalance, _) = T::Currency::slash_reserved(&proposal.proposer, proposal.bond);
+            let slashed = imbalance.peek();
+            Self::on_unbalanced(imbalance);
+
+            Self::deposit_event(Event::Rejected(proposal_id, slashed));
+            Ok(Default::default())
+        }
+
+        /// Approve a proposal. At a later time, the proposal will be allocated to the beneficiary
+        /// and the original deposit will be returned.
+        #[pallet::weight(T::WeightInfo::approve_proposal(T::MaxApprovals::get()))]
+        pub fn approve_proposal(
+            origin: OriginFor<T>,
+            proposal_id: ProposalIndex,
+        ) -> DispatchResultWithPostInfo {
+            T::ApproveOrigin::ensure_origin(origin)?;
+
+            ensure!(Proposals::<T>::contains_key(proposal_id), Error::<T>::InvalidIndex);
+            let queued = Approvals::<T>::try_mutate(|approvals| -> Result<u32, DispatchError> {
+                ensure!(!approvals.contains(&proposal_id), Error::<T>::AlreadyApproved);
+                ensure!(
+                    (approvals.len() as u32) < T::MaxApprovals::get(),
+                    Error::<T>::TooManyApprovals,
+                );
+                approvals.push(proposal_id);
+                Ok(approvals.len() as u32)
+            })?;
+
+            Self::deposit_event(Event::Approved(proposal_id));
+            Ok(Some(T::WeightInfo::approve_proposal(queued)).into())
+        }
+
+        /// Schedule `count` payments of `amount` to `dest`, one every `period` blocks starting
+        /// `period` blocks from now.
+        ///
+        /// A payment the pot cannot cover when it is due is skipped, not deferred.
+        #[pallet::weight(T::WeightInfo::schedule_payment())]
+        pub fn schedule_payment(
+            origin: OriginFor<T>,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+            period: T::BlockNumber,
+            count: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::ApproveOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                amount >= T::Currency::minimum_balance(),
+                Error::<T>::ScantyAmount,
+            );
+            ensure!(!period.is_zero() && count > 0, Error::<T>::InvalidSchedule);
+
+            let index = Self::payment_count();
+            PaymentCount::<T>::put(index + 1);
+            let next_payment = frame_system::Pallet::<T>::block_number().saturating_add(period);
+            PaymentSchedules::<T>::insert(index, PaymentSchedule {
+                dest: dest.clone(),
+                amount,
+                period,
+                remaining: count,
+                next_payment,
+            });
+            PaymentAgenda::<T>::append(next_payment, index);
+
+            Self::deposit_event(Event::PaymentScheduled(index, dest, amount, period, count));
+            Ok(Default::default())
+        }
+
+        /// Donate some of an asset other than the native currency to the Treasury.
+        #[pallet::weight(T::WeightInfo::donate_asset())]
+        pub fn donate_asset(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+            amount: AssetBalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(
+                amount >= <T::Assets as fungibles::Inspect<_>>::minimum_balance(asset_id),
+                Error::<T>::ScantyAmount,
+            );
+
+            let treasury = Self::account_id();
+            <T::Assets as fungibles::Transfer<_>>::transfer(asset_id, &sender, &treasury, amount, false)
+                .map_err(|_| Error::<T>::FailedDonation)?;
+            let pot = Self::asset_pot(asset_id);
+            Self::deposit_event(Event::AssetDonationReceived(asset_id, sender, amount, pot));
+
+            Ok(Default::default())
+        }
+
+        /// Allocate some of an asset other than the native currency held by the Treasury.
+        ///
+        /// Like `allocate`, this call requires root origin.
+        #[pallet::weight(T::WeightInfo::allocate_asset())]
+        pub fn allocate_asset(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+            dest: T::AccountId,
+            amount: AssetBalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            ensure!(
+                amount >= <T::Assets as fungibles::Inspect<_>>::minimum_balance(asset_id),
+                Error::<T>::ScantyAmount,
+            );
+
+            let treasury = Self::account_id();
+            <T::Assets as fungibles::Transfer<_>>::transfer(asset_id, &treasury, &dest, amount, false)
+                .map_err(|_| Error::<T>::FailedAllocation)?;
+
+            let pot = Self::asset_pot(asset_id);
+            Self::deposit_event(Event::AssetFundsAllocated(asset_id, dest, amount, pot));
+            Ok(Default::default())
+        }
+
+        /// Allocate ERC-20 tokens held at the Treasury's EVM address, spending at most
+        /// `gas_limit` gas.
+        ///
+        /// Like `allocate`, this call requires root origin. The gas is paid from the pot, the
+        /// account the EVM address maps to.
+        #[pallet::weight(
+            T::WeightInfo::allocate_erc20().saturating_add(T::Erc20::transfer_weight(*gas_limit))
+        )]
+        pub fn allocate_erc20(
+            origin: OriginFor<T>,
+            token: H160,
+            dest: H160,
+            amount: U256,
+            gas_limit: u64,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            T::Erc20::transfer(Self::evm_address(), token, dest, amount, gas_limit)
+                .map_err(|_| Error::<T>::FailedErc20Allocation)?;
+
+            Self::deposit_event(Event::Erc20FundsAllocated(token, dest, amount));
+            Ok(Default::default())
+        }
+
+        /// Cancel a payment schedule. Payments already made are not affected.
+        #[pallet::weight(T::WeightInfo::cancel_payment())]
+        pub fn cancel_payment(
+            origin: OriginFor<T>,
+            payment_id: PaymentIndex,
+        ) -> DispatchResultWithPostInfo {
+            T::ApproveOrigin::ensure_origin(origin)?;
+
+            PaymentSchedules::<T>::take(payment_id).ok_or(Error::<T>::InvalidPaymentIndex)?;
+
+            Self::deposit_event(Event::PaymentCancelled(payment_id));
+            Ok(Default::default())
+        }
     }
 
     #[pallet::extra_constants]
     impl<T: Config> Pallet<T> {
-        /// The account ID of the treasury pot.
+        /// The account ID of the treasury pot, the account `evm_address` maps to.
         ///
         /// This actually does computation. If you need to keep using it, then make sure you cache the
         /// value and only call this once.
         pub fn account_id() -> T::AccountId {
-            T::PalletId::get().into_account()
+            T::AddressMapping::into_account_id(Self::evm_address())
         }
 
         /// Return the amount of money in the pot.
@@ -137,5 +589,196 @@ pub mod pallet {
                 // Must never be less than 0 but better be safe.
                 .saturating_sub(T::Currency::minimum_balance())
         }
+
+        /// The address holding the treasury's ERC-20 tokens in the EVM.
+        ///
+        /// It is `PalletId` encoded as an account and truncated to 20 bytes. The account it maps
+        /// to is `account_id`, so the EVM charges gas and pays native tokens to the pot.
+        pub fn evm_address() -> H160 {
+            T::PalletId::get().into_account()
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Pay `dest` up to `amount` out of the pot, for the spending extensions built on the
+        /// treasury such as tips. Returns what was paid, less than `amount` if the pot is short.
+        pub fn pay_out(dest: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+            let amount = amount.min(Self::pot());
+            match T::Currency::transfer(&Self::account_id(), dest, amount, KeepAlive) {
+                Ok(()) => amount,
+                Err(_) => Zero::zero(),
+            }
+        }
+
+        /// Return everything `source` holds to the pot, e.g. the account of a cancelled bounty the
+        /// treasury funded. Returns the amount recovered.
+        pub fn reclaim(source: &T::AccountId) -> BalanceOf<T> {
+            let amount = T::Currency::free_balance(source);
+            match T::Currency::transfer(source, &Self::account_id(), amount, AllowDeath) {
+                Ok(()) => amount,
+                Err(_) => Zero::zero(),
+            }
+        }
+
+        /// Record that `amount` already held by the treasury account is no longer owed elsewhere,
+        /// e.g. crowdloan rewards nobody claimed. It is added to `Recovered`.
+        pub fn note_recovered(amount: BalanceOf<T>) {
+            Recovered::<T>::mutate(|total| *total = total.saturating_add(amount));
+            Self::deposit_event(Event::FundsRecovered(amount, Self::pot()));
+        }
+
+        /// Return the amount of `asset_id` held by the Treasury.
+        pub fn asset_pot(asset_id: AssetIdOf<T>) -> AssetBalanceOf<T> {
+            <T::Assets as fungibles::Inspect<_>>::balance(asset_id, &Self::account_id())
+        }
+
+        /// All active payment schedules, in index order.
+        pub fn scheduled_payments(
+        ) -> Vec<ScheduledPayment<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+            let mut payments = PaymentSchedules::<T>::iter()
+                .map(|(index, schedule)| ScheduledPayment {
+                    index,
+                    dest: schedule.dest,
+                    amount: schedule.amount,
+                    period: schedule.period,
+                    remaining: schedule.remaining,
+                    next_payment: schedule.next_payment,
+                })
+                .collect::<Vec<_>>();
+            payments.sort_by_key(|payment| payment.index);
+            payments
+        }
+
+        /// Make the payments due at block `n`, at most `MaxPaymentsPerBlock` of them. The rest
+        /// are carried over to the next block, which is charged for as well.
+        ///
+        /// Each payment is rescheduled one period after the block it was due at, so carrying it
+        /// over does not shift the schedule.
+        pub(crate) fn execute_payments(n: T::BlockNumber) -> Weight {
+            let mut due = PaymentAgenda::<T>::take(n);
+            if due.is_empty() {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let max = T::MaxPaymentsPerBlock::get() as usize;
+            let mut weight: Weight = 0;
+            if due.len() > max {
+                let carried = due.split_off(max);
+                weight = T::WeightInfo::carry_over_payments(carried.len() as u32);
+                PaymentAgenda::<T>::mutate(n.saturating_add(One::one()), |agenda| {
+                    agenda.extend(carried)
+                });
+            }
+
+            let account = Self::account_id();
+            for &index in due.iter() {
+                let mut schedule = match PaymentSchedules::<T>::get(index) {
+                    Some(schedule) => schedule,
+                    // Cancelled; nothing left to pay.
+                    None => continue,
+                };
+
+                let (amount, dest) = (schedule.amount, schedule.dest.clone());
+                let paid = amount <= Self::pot()
+                    && T::Currency::transfer(&account, &dest, amount, KeepAlive).is_ok();
+                if paid {
+                    Self::deposit_event(Event::PaymentExecuted(index, amount, dest));
+                } else {
+                    Self::deposit_event(Event::PaymentSkipped(index, amount, dest));
+                }
+
+                schedule.remaining = schedule.remaining.saturating_sub(1);
+                if schedule.remaining.is_zero() {
+                    PaymentSchedules::<T>::remove(index);
+                } else {
+                    // A payment carried over for longer than its period is due again right away.
+                    schedule.next_payment = schedule.next_payment
+                        .saturating_add(schedule.period)
+                        .max(n.saturating_add(One::one()));
+                    PaymentAgenda::<T>::append(schedule.next_payment, index);
+                    PaymentSchedules::<T>::insert(index, schedule);
+                }
+            }
+
+            weight.saturating_add(T::WeightInfo::on_initialize_payments(due.len() as u32))
+        }
+
+        /// The needed bond for a proposal whose spend is `amount`.
+        fn calculate_bond(amount: BalanceOf<T>) -> BalanceOf<T> {
+            T::ProposalBondMinimum::get().max(T::ProposalBond::get() * amount)
+        }
+
+        /// Pay out approved proposals in queue order while the pot can cover them, then let
+        /// `SpendFunds` draw from what is left. Proposals the pot cannot cover yet stay queued for
+        /// the next period. If nothing was missed, `Burn` of the remainder is burnt and the rest
+        /// rolls over.
+        fn spend_funds() -> Weight {
+            let budget = Self::pot();
+            let mut budget_remaining = budget;
+            let mut imbalance = <PositiveImbalanceOf<T>>::zero();
+            let mut missed_any = false;
+
+            let approvals = Approvals::<T>::get();
+            let mut total_weight = T::WeightInfo::on_initialize_proposals(approvals.len() as u32);
+            let remaining = approvals.into_iter().filter(|&index| {
+                let proposal = match Proposals::<T>::get(index) {
+                    Some(proposal) => proposal,
+                    // Rejected after approval; nothing left to pay.
+                    None => return false,
+                };
+                if proposal.amount > budget_remaining {
+                    missed_any = true;
+                    return true;
+                }
+
+                budget_remaining -= proposal.amount;
+                Proposals::<T>::remove(index);
+                T::Currency::unreserve(&proposal.proposer, proposal.bond);
+                imbalance.subsume(T::Currency::deposit_creating(&proposal.user, proposal.amount));
+                Self::deposit_event(Event::Awarded(index, proposal.amount, proposal.user));
+                false
+            }).collect::<Vec<_>>();
+            Approvals::<T>::put(remaining);
+
+            T::SpendFunds::spend_funds(
+                &mut budget_remaining,
+                &mut imbalance,
+                &mut total_weight,
+                &mut missed_any,
+            );
+            Self::deposit_event(Event::Spent(budget.saturating_sub(budget_remaining)));
+
+            if !missed_any {
+                let burn = (T::Burn::get() * budget_remaining).min(budget_remaining);
+                budget_remaining -= burn;
+
+                let (debit, credit) = T::Currency::pair(burn);
+                imbalance.subsume(debit);
+                T::BurnDestination::on_unbalanced(credit);
+                Self::deposit_event(Event::Burnt(burn));
+            }
+
+            // Must never be an error, but better to be safe. The payouts above were all bounded by
+            // the pot, so the pot can cover them without dying.
+            if let Err(problem) = T::Currency::settle(
+                &Self::account_id(),
+                imbalance,
+                WithdrawReasons::TRANSFER,
+                KeepAlive,
+            ) {
+                drop(problem);
+            }
+
+            Self::deposit_event(Event::Rollover(budget_remaining));
+            total_weight
+        }
+    }
+
+    /// Funds slashed elsewhere, e.g. forfeited bonds, go into the pot.
+    impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
+        fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+            // Must resolve into existing but better to be safe.
+            let _ = T::Currency::resolve_creating(&Self::account_id(), amount);
+        }
     }
 }
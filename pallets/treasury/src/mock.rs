@@ -1,12 +1,20 @@
-use crate::{self as pallet_treasury, Config};
-use frame_support::{construct_runtime, parameter_types, PalletId};
-use sp_core::H256;
+use crate::{self as pallet_treasury, BalanceOf, Config, Erc20Transactor, PositiveImbalanceOf, SpendFunds};
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{Currency, Imbalance, OnInitialize},
+    weights::Weight,
+    PalletId,
+};
+use frame_system::EnsureRoot;
+use sp_core::{H160, H256, U256};
 use sp_io;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, IdentityLookup, Zero},
+    Permill,
 };
 use sp_std::convert::From;
+use std::cell::RefCell;
 
 pub type AccountId = u64;
 pub type Balance = u128;
@@ -57,14 +65,149 @@ impl pallet_balances::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const AssetDeposit: Balance = 1;
+    pub const ApprovalDeposit: Balance = 1;
+    pub const StringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = 1;
+    pub const MetadataDepositPerByte: Balance = 1;
+}
+
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
 parameter_types! {
     pub const TreasuryPalletId: PalletId = PalletId(*b"Treasury");
+    pub const ProposalBond: Permill = Permill::from_percent(5);
+    pub const ProposalBondMinimum: Balance = ONE_COIN_UNIT;
+    pub const SpendPeriod: u64 = 2;
+    pub const MaxApprovals: u32 = 100;
+    pub const Burn: Permill = Permill::from_percent(50);
+    pub const MaxPaymentsPerBlock: u32 = 2;
 }
 
 impl Config for Test {
     type PalletId = TreasuryPalletId;
     type Currency = Balances;
+    type ApproveOrigin = EnsureRoot<AccountId>;
+    type RejectOrigin = EnsureRoot<AccountId>;
     type Event = Event;
+    type ProposalBond = ProposalBond;
+    type ProposalBondMinimum = ProposalBondMinimum;
+    type SpendPeriod = SpendPeriod;
+    type MaxApprovals = MaxApprovals;
+    type Burn = Burn;
+    type BurnDestination = ();
+    type SpendFunds = SpendFundsMock;
+    type MaxPaymentsPerBlock = MaxPaymentsPerBlock;
+    type Assets = Assets;
+    type Erc20 = Erc20Mock;
+    type AddressMapping = TestAddressMapping;
+    type WeightInfo = ();
+}
+
+/// Maps an Ethereum address to the `u64` read big-endian from its first 8 bytes, so the
+/// treasury account differs from the little-endian `PalletId` account as it does on chain.
+pub struct TestAddressMapping;
+
+impl pallet_evm::AddressMapping<AccountId> for TestAddressMapping {
+    fn into_account_id(address: H160) -> AccountId {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&address.as_bytes()[0..8]);
+        AccountId::from_be_bytes(bytes)
+    }
+}
+
+pub const HOOK_BENEFICIARY: AccountId = 9;
+
+thread_local! {
+    static HOOK_SPEND: RefCell<Balance> = RefCell::new(0);
+}
+
+/// Set how much `SpendFundsMock` wants from each spend period.
+pub(crate) fn set_hook_spend(amount: Balance) {
+    HOOK_SPEND.with(|v| *v.borrow_mut() = amount);
+}
+
+/// Pays `HOOK_BENEFICIARY` what it asked for if the budget allows it.
+pub struct SpendFundsMock;
+impl SpendFunds<Test> for SpendFundsMock {
+    fn spend_funds(
+        budget_remaining: &mut BalanceOf<Test>,
+        imbalance: &mut PositiveImbalanceOf<Test>,
+        _total_weight: &mut Weight,
+        missed_any: &mut bool,
+    ) {
+        let amount = HOOK_SPEND.with(|v| *v.borrow());
+        if amount.is_zero() {
+            return;
+        }
+        if amount > *budget_remaining {
+            *missed_any = true;
+            return;
+        }
+        *budget_remaining -= amount;
+        imbalance.subsume(Balances::deposit_creating(&HOOK_BENEFICIARY, amount));
+    }
+}
+
+thread_local! {
+    static ERC20_TOKENS: RefCell<Vec<H160>> = RefCell::new(Vec::new());
+}
+
+/// Make `token` transferable through `Erc20Mock`.
+pub(crate) fn add_erc20_token(token: H160) {
+    ERC20_TOKENS.with(|v| v.borrow_mut().push(token));
+}
+
+/// Transfers succeed for the tokens added with `add_erc20_token`, there is no EVM.
+pub struct Erc20Mock;
+impl Erc20Transactor for Erc20Mock {
+    fn transfer_weight(_gas_limit: u64) -> Weight {
+        0
+    }
+
+    fn transfer(
+        _source: H160,
+        token: H160,
+        _dest: H160,
+        _amount: U256,
+        _gas_limit: u64,
+    ) -> frame_support::dispatch::DispatchResult {
+        if ERC20_TOKENS.with(|v| v.borrow().contains(&token)) {
+            Ok(())
+        } else {
+            Err(frame_support::dispatch::DispatchError::Other("unknown token"))
+        }
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::BenchmarkHelper for Test {
+    fn create_asset(who: &AccountId, amount: Balance) -> u32 {
+        Assets::force_create(Origin::root(), 0, *who, true, 1).unwrap();
+        Assets::mint(Origin::signed(*who), 0, *who, amount).unwrap();
+        0
+    }
+
+    fn create_erc20(_amount: U256) -> H160 {
+        let token = H160::repeat_byte(1);
+        add_erc20_token(token);
+        token
+    }
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -78,6 +221,7 @@ construct_runtime!(
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
         Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
     }
 );
@@ -86,6 +230,7 @@ pub const ONE_COIN_UNIT: Balance = 1_000_000_000_000;
 pub const MINIMUM_BALANCE: Balance = 500;
 
 pub fn mock_test() -> sp_io::TestExternalities {
+    ERC20_TOKENS.with(|v| v.borrow_mut().clear());
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
@@ -102,6 +247,13 @@ pub fn mock_test() -> sp_io::TestExternalities {
     ext
 }
 
+pub(crate) fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        Treasury::on_initialize(System::block_number());
+    }
+}
+
 pub(crate) fn events() -> Vec<super::Event<Test>> {
     System::events()
         .into_iter()
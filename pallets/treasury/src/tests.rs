@@ -1,5 +1,6 @@
 use crate::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::{Currency, Get}};
+use sp_runtime::traits::BadOrigin;
 use mock::*;
 
 #[test]
@@ -75,3 +76,494 @@ fn donate_error() {
         assert_noop!(Treasury::donate(donor, fund), Error::<Test>::FailedDonation);
     })
 }
+
+#[test]
+fn pay_out_is_capped_by_pot() {
+    mock_test().execute_with(|| {
+        let fund = 100 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::donate(Origin::signed(1), fund + MINIMUM_BALANCE));
+
+        assert_eq!(Treasury::pay_out(&3, 40 * ONE_COIN_UNIT), 40 * ONE_COIN_UNIT);
+        assert_eq!(Treasury::pay_out(&3, fund), 60 * ONE_COIN_UNIT);
+        assert_eq!(Balances::free_balance(3), fund);
+        // The treasury account itself is kept alive.
+        assert_eq!(Treasury::pot(), 0);
+        assert_eq!(Balances::free_balance(Treasury::account_id()), MINIMUM_BALANCE);
+    })
+}
+
+#[test]
+fn reclaim_empties_the_account_into_the_pot() {
+    mock_test().execute_with(|| {
+        let fund = 10 * ONE_COIN_UNIT;
+        Balances::make_free_balance_be(&3, fund);
+
+        assert_eq!(Treasury::reclaim(&3), fund);
+        assert_eq!(Balances::free_balance(3), 0);
+        assert_eq!(Treasury::pot(), fund - MINIMUM_BALANCE);
+    })
+}
+
+#[test]
+fn note_recovered_adds_up() {
+    mock_test().execute_with(|| {
+        assert_eq!(Treasury::recovered(), 0);
+
+        Treasury::note_recovered(30);
+        Treasury::note_recovered(70);
+        assert_eq!(Treasury::recovered(), 100);
+        assert_eq!(events(), vec![
+            crate::Event::FundsRecovered(30, 0),
+            crate::Event::FundsRecovered(70, 0),
+        ]);
+    })
+}
+
+#[test]
+fn propose_spend_work() {
+    mock_test().execute_with(|| {
+        let amount = 100 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), amount, 3));
+
+        // 5% of the amount is above the minimum bond.
+        assert_eq!(Balances::reserved_balance(1), 5 * ONE_COIN_UNIT);
+        assert_eq!(Treasury::proposal_count(), 1);
+        assert!(Treasury::proposals(0).is_some());
+        assert_eq!(events(), vec![crate::Event::Proposed(0)]);
+    })
+}
+
+#[test]
+fn propose_spend_takes_minimum_bond() {
+    mock_test().execute_with(|| {
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), 1, 3));
+        assert_eq!(Balances::reserved_balance(1), ONE_COIN_UNIT);
+    })
+}
+
+#[test]
+fn propose_spend_with_insufficient_balance() {
+    mock_test().execute_with(|| {
+        assert_noop!(
+            Treasury::propose_spend(Origin::signed(3), 100 * ONE_COIN_UNIT, 3),
+            Error::<Test>::InsufficientProposersBalance,
+        );
+    })
+}
+
+#[test]
+fn reject_proposal_slashes_bond() {
+    mock_test().execute_with(|| {
+        let amount = 100 * ONE_COIN_UNIT;
+        let bond = 5 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), amount, 3));
+        assert_noop!(Treasury::reject_proposal(Origin::signed(2), 0), BadOrigin);
+        assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1000 * ONE_COIN_UNIT - bond);
+        assert_eq!(Balances::free_balance(Treasury::account_id()), bond);
+        assert!(Treasury::proposals(0).is_none());
+        assert_eq!(
+            events(),
+            vec![crate::Event::Proposed(0), crate::Event::Rejected(0, bond)]
+        );
+        assert_noop!(
+            Treasury::reject_proposal(Origin::root(), 0),
+            Error::<Test>::InvalidIndex,
+        );
+    })
+}
+
+#[test]
+fn approve_proposal_work() {
+    mock_test().execute_with(|| {
+        assert_noop!(
+            Treasury::approve_proposal(Origin::root(), 0),
+            Error::<Test>::InvalidIndex,
+        );
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), 100 * ONE_COIN_UNIT, 3));
+        assert_noop!(Treasury::approve_proposal(Origin::signed(2), 0), BadOrigin);
+        assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+        assert_eq!(Treasury::approvals(), vec![0]);
+        assert_noop!(
+            Treasury::approve_proposal(Origin::root(), 0),
+            Error::<Test>::AlreadyApproved,
+        );
+    })
+}
+
+#[test]
+fn approve_proposal_with_full_queue() {
+    mock_test().execute_with(|| {
+        let max = MaxApprovals::get();
+        for index in 0..=max {
+            assert_ok!(Treasury::propose_spend(Origin::signed(1), ONE_COIN_UNIT, 3));
+            if index < max {
+                assert_ok!(Treasury::approve_proposal(Origin::root(), index));
+            }
+        }
+        assert_noop!(
+            Treasury::approve_proposal(Origin::root(), max),
+            Error::<Test>::TooManyApprovals,
+        );
+    })
+}
+
+#[test]
+fn spend_period_pays_approved_proposals() {
+    mock_test().execute_with(|| {
+        let amount = 100 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::donate(Origin::signed(2), 2 * amount));
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), amount, 3));
+        assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+        // Nothing happens until the spend period comes around.
+        assert_eq!(Balances::free_balance(3), 0);
+        run_to_block(2);
+
+        assert_eq!(Balances::free_balance(3), amount);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1000 * ONE_COIN_UNIT);
+        assert!(Treasury::approvals().is_empty());
+        assert!(Treasury::proposals(0).is_none());
+
+        // Half of what is left over is burnt, the rest rolls over.
+        let left = amount - MINIMUM_BALANCE;
+        let expected = vec![
+            crate::Event::Awarded(0, amount, 3),
+            crate::Event::Spent(amount),
+            crate::Event::Burnt(left / 2),
+            crate::Event::Rollover(left - left / 2),
+        ];
+        assert_eq!(events()[events().len() - 4..].to_vec(), expected);
+        assert_eq!(Treasury::pot(), left - left / 2);
+    })
+}
+
+#[test]
+fn spend_period_keeps_unaffordable_proposals_queued() {
+    mock_test().execute_with(|| {
+        let amount = 100 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::donate(Origin::signed(2), amount + MINIMUM_BALANCE));
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), 2 * amount, 3));
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), amount, 4));
+        assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+        assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+
+        run_to_block(2);
+        // The first proposal does not fit but does not block the second.
+        assert_eq!(Balances::free_balance(3), 0);
+        assert_eq!(Balances::free_balance(4), amount);
+        assert_eq!(Treasury::approvals(), vec![0]);
+
+        assert_ok!(Treasury::donate(Origin::signed(2), 2 * amount));
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(3), 2 * amount);
+        assert!(Treasury::approvals().is_empty());
+    })
+}
+
+#[test]
+fn spend_period_drops_rejected_approvals() {
+    mock_test().execute_with(|| {
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), ONE_COIN_UNIT, 3));
+        assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+        assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+
+        run_to_block(2);
+        assert!(Treasury::approvals().is_empty());
+        assert_eq!(Balances::free_balance(3), 0);
+    })
+}
+
+#[test]
+fn spend_period_burns_unspent_funds() {
+    mock_test().execute_with(|| {
+        let fund = 100 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::donate(Origin::signed(1), fund + MINIMUM_BALANCE));
+        let issuance = Balances::total_issuance();
+
+        run_to_block(2);
+        assert_eq!(Treasury::pot(), fund / 2);
+        assert_eq!(Balances::total_issuance(), issuance - fund / 2);
+        assert_eq!(
+            events()[1..].to_vec(),
+            vec![
+                crate::Event::Spent(0),
+                crate::Event::Burnt(fund / 2),
+                crate::Event::Rollover(fund / 2),
+            ]
+        );
+    })
+}
+
+#[test]
+fn spend_period_does_not_burn_when_approvals_are_missed() {
+    mock_test().execute_with(|| {
+        let fund = 100 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::donate(Origin::signed(1), fund + MINIMUM_BALANCE));
+        assert_ok!(Treasury::propose_spend(Origin::signed(1), 2 * fund, 3));
+        assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+        run_to_block(2);
+        assert_eq!(Treasury::pot(), fund);
+        assert_eq!(events().last(), Some(&crate::Event::Rollover(fund)));
+        assert!(!events().iter().any(|e| matches!(e, crate::Event::Burnt(_))));
+    })
+}
+
+#[test]
+fn spend_funds_hook_draws_from_budget() {
+    mock_test().execute_with(|| {
+        let fund = 100 * ONE_COIN_UNIT;
+        let hook = 40 * ONE_COIN_UNIT;
+        assert_ok!(Treasury::donate(Origin::signed(1), fund + MINIMUM_BALANCE));
+        set_hook_spend(hook);
+
+        run_to_block(2);
+        assert_eq!(Balances::free_balance(HOOK_BENEFICIARY), hook);
+        let left = fund - hook;
+        assert_eq!(Treasury::pot(), left - left / 2);
+        assert_eq!(
+            events()[1..].to_vec(),
+            vec![
+                crate::Event::Spent(hook),
+                crate::Event::Burnt(left / 2),
+                crate::Event::Rollover(left - left / 2),
+            ]
+        );
+    })
+}
+
+#[test]
+fn schedule_payment_pays_every_period() {
+    mock_test().execute_with(|| {
+        assert_ok!(Treasury::donate(Origin::signed(1), 100 * ONE_COIN_UNIT));
+        assert_ok!(Treasury::schedule_payment(Origin::root(), 3, ONE_COIN_UNIT, 3, 2));
+        assert_eq!(Treasury::payment_schedules(0).map(|s| s.next_payment), Some(4));
+
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(3), 0);
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(3), ONE_COIN_UNIT);
+        assert_eq!(Treasury::payment_schedules(0).map(|s| s.next_payment), Some(7));
+
+        run_to_block(10);
+        assert_eq!(Balances::free_balance(3), 2 * ONE_COIN_UNIT);
+        assert!(Treasury::payment_schedules(0).is_none());
+        let executed = events()
+            .into_iter()
+            .filter(|e| *e == crate::Event::PaymentExecuted(0, ONE_COIN_UNIT, 3))
+            .count();
+        assert_eq!(executed, 2);
+    })
+}
+
+#[test]
+fn schedule_payment_checks_origin_and_schedule() {
+    mock_test().execute_with(|| {
+        assert_noop!(
+            Treasury::schedule_payment(Origin::signed(1), 3, ONE_COIN_UNIT, 3, 2),
+            BadOrigin,
+        );
+        assert_noop!(
+            Treasury::schedule_payment(Origin::root(), 3, 1, 3, 2),
+            Error::<Test>::ScantyAmount,
+        );
+        assert_noop!(
+            Treasury::schedule_payment(Origin::root(), 3, ONE_COIN_UNIT, 0, 2),
+            Error::<Test>::InvalidSchedule,
+        );
+        assert_noop!(
+            Treasury::schedule_payment(Origin::root(), 3, ONE_COIN_UNIT, 3, 0),
+            Error::<Test>::InvalidSchedule,
+        );
+    })
+}
+
+#[test]
+fn payment_is_skipped_when_pot_is_short() {
+    mock_test().execute_with(|| {
+        assert_ok!(Treasury::schedule_payment(Origin::root(), 3, ONE_COIN_UNIT, 3, 2));
+
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(3), 0);
+        assert!(events().contains(&crate::Event::PaymentSkipped(0, ONE_COIN_UNIT, 3)));
+        assert_eq!(Treasury::payment_schedules(0).map(|s| s.remaining), Some(1));
+
+        assert_ok!(Treasury::donate(Origin::signed(1), 100 * ONE_COIN_UNIT));
+        run_to_block(7);
+        assert_eq!(Balances::free_balance(3), ONE_COIN_UNIT);
+        assert!(Treasury::payment_schedules(0).is_none());
+    })
+}
+
+#[test]
+fn cancel_payment_stops_payments() {
+    mock_test().execute_with(|| {
+        assert_ok!(Treasury::donate(Origin::signed(1), 100 * ONE_COIN_UNIT));
+        assert_ok!(Treasury::schedule_payment(Origin::root(), 3, ONE_COIN_UNIT, 3, 2));
+
+        assert_noop!(Treasury::cancel_payment(Origin::signed(1), 0), BadOrigin);
+        assert_ok!(Treasury::cancel_payment(Origin::root(), 0));
+        assert_noop!(
+            Treasury::cancel_payment(Origin::root(), 0),
+            Error::<Test>::InvalidPaymentIndex,
+        );
+
+        run_to_block(10);
+        assert_eq!(Balances::free_balance(3), 0);
+        assert!(!events().iter().any(|e| matches!(e, crate::Event::PaymentExecuted(..))));
+    })
+}
+
+#[test]
+fn payments_beyond_block_limit_are_carried_over() {
+    mock_test().execute_with(|| {
+        assert_ok!(Treasury::donate(Origin::signed(1), 100 * ONE_COIN_UNIT));
+        for dest in 3..6 {
+            assert_ok!(Treasury::schedule_payment(Origin::root(), dest, ONE_COIN_UNIT, 4, 2));
+        }
+
+        run_to_block(5);
+        assert_eq!(Balances::free_balance(3), ONE_COIN_UNIT);
+        assert_eq!(Balances::free_balance(4), ONE_COIN_UNIT);
+        assert_eq!(Balances::free_balance(5), 0);
+
+        run_to_block(6);
+        assert_eq!(Balances::free_balance(5), ONE_COIN_UNIT);
+        // The carried over payment keeps its schedule.
+        assert_eq!(Treasury::payment_schedules(2).map(|s| s.next_payment), Some(9));
+    })
+}
+
+#[test]
+fn carried_over_payments_are_charged() {
+    mock_test().execute_with(|| {
+        let max = MaxPaymentsPerBlock::get();
+        PaymentAgenda::<Test>::insert(5, (0..max + 3).collect::<Vec<_>>());
+
+        assert_eq!(
+            Treasury::execute_payments(5),
+            <() as WeightInfo>::carry_over_payments(3)
+                + <() as WeightInfo>::on_initialize_payments(max),
+        );
+        assert_eq!(PaymentAgenda::<Test>::get(6).len(), 3);
+    })
+}
+
+#[test]
+fn scheduled_payments_lists_active_schedules() {
+    mock_test().execute_with(|| {
+        assert_ok!(Treasury::schedule_payment(Origin::root(), 3, ONE_COIN_UNIT, 3, 2));
+        assert_ok!(Treasury::schedule_payment(Origin::root(), 4, ONE_COIN_UNIT, 5, 1));
+        assert_ok!(Treasury::cancel_payment(Origin::root(), 0));
+
+        let payments = Treasury::scheduled_payments();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].index, 1);
+        assert_eq!(payments[0].dest, 4);
+        assert_eq!(payments[0].next_payment, 6);
+    })
+}
+
+fn create_asset(id: u32, holder: AccountId, amount: Balance) {
+    assert_ok!(Assets::force_create(Origin::root(), id, 1, true, MINIMUM_BALANCE));
+    assert_ok!(Assets::mint(Origin::signed(1), id, holder, amount));
+}
+
+#[test]
+fn donate_asset_and_allocate_asset_work() {
+    mock_test().execute_with(|| {
+        create_asset(0, 2, 100 * ONE_COIN_UNIT);
+
+        assert_ok!(Treasury::donate_asset(Origin::signed(2), 0, 40 * ONE_COIN_UNIT));
+        assert_eq!(Treasury::asset_pot(0), 40 * ONE_COIN_UNIT);
+        // The native pot is not affected.
+        assert_eq!(Treasury::pot(), 0);
+
+        assert_noop!(Treasury::allocate_asset(Origin::signed(1), 0, 3, ONE_COIN_UNIT), BadOrigin);
+        assert_ok!(Treasury::allocate_asset(Origin::root(), 0, 3, 10 * ONE_COIN_UNIT));
+        assert_eq!(Assets::balance(0, 3), 10 * ONE_COIN_UNIT);
+        assert_eq!(Treasury::asset_pot(0), 30 * ONE_COIN_UNIT);
+        assert_eq!(
+            events(),
+            vec![
+                crate::Event::AssetDonationReceived(0, 2, 40 * ONE_COIN_UNIT, 40 * ONE_COIN_UNIT),
+                crate::Event::AssetFundsAllocated(0, 3, 10 * ONE_COIN_UNIT, 30 * ONE_COIN_UNIT),
+            ]
+        );
+    })
+}
+
+#[test]
+fn donate_asset_checks_amount_and_balance() {
+    mock_test().execute_with(|| {
+        create_asset(0, 2, 100 * ONE_COIN_UNIT);
+
+        assert_noop!(Treasury::donate_asset(Origin::signed(2), 0, 1), Error::<Test>::ScantyAmount);
+        assert_noop!(
+            Treasury::donate_asset(Origin::signed(3), 0, ONE_COIN_UNIT),
+            Error::<Test>::FailedDonation,
+        );
+        assert_noop!(
+            Treasury::allocate_asset(Origin::root(), 0, 3, ONE_COIN_UNIT),
+            Error::<Test>::FailedAllocation,
+        );
+    })
+}
+
+#[test]
+fn allocate_erc20_goes_through_erc20_transactor() {
+    mock_test().execute_with(|| {
+        let token = sp_core::H160::repeat_byte(1);
+        let dest = sp_core::H160::repeat_byte(2);
+
+        assert_noop!(
+            Treasury::allocate_erc20(Origin::signed(1), token, dest, 1.into(), 100_000),
+            BadOrigin,
+        );
+        assert_noop!(
+            Treasury::allocate_erc20(Origin::root(), token, dest, 1.into(), 100_000),
+            Error::<Test>::FailedErc20Allocation,
+        );
+        assert_eq!(&Treasury::evm_address()[..12], b"modlTreasury");
+
+        add_erc20_token(token);
+        assert_ok!(Treasury::allocate_erc20(Origin::root(), token, dest, 1.into(), 100_000));
+        assert_eq!(events(), vec![crate::Event::Erc20FundsAllocated(token, dest, 1.into())]);
+    })
+}
+
+#[test]
+fn evm_address_maps_to_account_id() {
+    mock_test().execute_with(|| {
+        use pallet_evm::AddressMapping;
+
+        assert_eq!(
+            <Test as Config>::AddressMapping::into_account_id(Treasury::evm_address()),
+            Treasury::account_id(),
+        );
+    })
+}
+
+#[test]
+fn runtime_upgrade_moves_the_legacy_pot() {
+    mock_test().execute_with(|| {
+        use frame_support::traits::OnRuntimeUpgrade;
+        use sp_runtime::traits::AccountIdConversion;
+
+        let legacy: AccountId = TreasuryPalletId::get().into_account();
+        assert_ne!(legacy, Treasury::account_id());
+        Balances::make_free_balance_be(&legacy, 100);
+
+        Treasury::on_runtime_upgrade();
+        assert_eq!(Balances::free_balance(legacy), 0);
+        assert_eq!(Balances::free_balance(Treasury::account_id()), 100);
+
+        // Nothing is left to move on later upgrades.
+        Treasury::on_runtime_upgrade();
+        assert_eq!(Balances::free_balance(Treasury::account_id()), 100);
+    })
+}
@@ -0,0 +1,177 @@
+//! Weights for pallet_treasury
+//!
+//! Estimated from the storage accesses of each call, not benchmarked yet.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_treasury.
+pub trait WeightInfo {
+    fn donate() -> Weight;
+    fn allocate() -> Weight;
+    fn propose_spend() -> Weight;
+    fn reject_proposal() -> Weight;
+    fn approve_proposal(p: u32, ) -> Weight;
+    fn on_initialize_proposals(p: u32, ) -> Weight;
+    fn schedule_payment() -> Weight;
+    fn cancel_payment() -> Weight;
+    fn on_initialize_payments(p: u32, ) -> Weight;
+    fn carry_over_payments(c: u32, ) -> Weight;
+    fn donate_asset() -> Weight;
+    fn allocate_asset() -> Weight;
+    fn allocate_erc20() -> Weight;
+}
+
+/// Weights for pallet_treasury using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn donate() -> Weight {
+        (74_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn allocate() -> Weight {
+        (72_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn propose_spend() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn reject_proposal() -> Weight {
+        (78_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn approve_proposal(p: u32, ) -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add((250_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn on_initialize_proposals(p: u32, ) -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add((86_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+    }
+    fn schedule_payment() -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_payment() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn on_initialize_payments(p: u32, ) -> Weight {
+        (6_000_000 as Weight)
+            .saturating_add((68_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(p as Weight)))
+    }
+    fn carry_over_payments(c: u32, ) -> Weight {
+        (4_000_000 as Weight)
+            .saturating_add((150_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn donate_asset() -> Weight {
+        (62_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn allocate_asset() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn allocate_erc20() -> Weight {
+        (12_000_000 as Weight)
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn donate() -> Weight {
+        (74_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn allocate() -> Weight {
+        (72_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn propose_spend() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn reject_proposal() -> Weight {
+        (78_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn approve_proposal(p: u32, ) -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add((250_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn on_initialize_proposals(p: u32, ) -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add((86_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+    }
+    fn schedule_payment() -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_payment() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn on_initialize_payments(p: u32, ) -> Weight {
+        (6_000_000 as Weight)
+            .saturating_add((68_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(p as Weight)))
+    }
+    fn carry_over_payments(c: u32, ) -> Weight {
+        (4_000_000 as Weight)
+            .saturating_add((150_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn donate_asset() -> Weight {
+        (62_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn allocate_asset() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn allocate_erc20() -> Weight {
+        (12_000_000 as Weight)
+    }
+}
@@ -0,0 +1,145 @@
+//! Handlers for the chain's income: transaction fees, EVM gas fees and dust, for reporting the
+//! block author to the EVM, and for weighing EVM gas.
+
+use author_inherent::AccountToEvmAddress;
+use codec::{Decode, Encode};
+use frame_support::traits::{Currency, FindAuthor, Get, Imbalance, OnUnbalanced};
+use frame_support::weights::Weight;
+use pallet_evm::GasWeightMapping;
+use sp_core::H160;
+use sp_runtime::{ConsensusEngineId, Perbill};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+pub type NegativeImbalance<R> = <pallet_balances::Pallet<R> as Currency<
+	<R as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Finds the author of the current block from its Aura pre-runtime digest. Collators author with
+/// an Aura key whose public key is also their account id.
+pub struct AuraAccountAuthor<R>(PhantomData<R>);
+impl<R: pallet_aura::Config> FindAuthor<R::AccountId> for AuraAccountAuthor<R> {
+	fn find_author<'a, I>(digests: I) -> Option<R::AccountId>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		let index = pallet_aura::Pallet::<R>::find_author(digests)?;
+		let authority = pallet_aura::Pallet::<R>::authorities().get(index as usize)?.encode();
+		R::AccountId::decode(&mut &authority[..]).ok()
+	}
+}
+
+/// The accounts of the Aura authorities, for an author filter to pick the eligible authors from
+/// while the chain runs without staking.
+pub struct AuraAccounts<R>(PhantomData<R>);
+impl<R: pallet_aura::Config> Get<Vec<R::AccountId>> for AuraAccounts<R> {
+	fn get() -> Vec<R::AccountId> {
+		pallet_aura::Pallet::<R>::authorities()
+			.iter()
+			.filter_map(|authority| R::AccountId::decode(&mut &authority.encode()[..]).ok())
+			.collect()
+	}
+}
+
+/// The accounts `A` gives, or the ones `B` gives while `A` has none, e.g. the Aura authorities
+/// until staking elects its first collators.
+pub struct OrWhileEmpty<A, B>(PhantomData<(A, B)>);
+impl<AccountId, A, B> Get<Vec<AccountId>> for OrWhileEmpty<A, B>
+where
+	A: Get<Vec<AccountId>>,
+	B: Get<Vec<AccountId>>,
+{
+	fn get() -> Vec<AccountId> {
+		let accounts = A::get();
+		if accounts.is_empty() {
+			B::get()
+		} else {
+			accounts
+		}
+	}
+}
+
+/// Reports the author found by `A` as the EVM block coinbase, using the address `M` maps it to.
+pub struct EvmAuthor<R, A, M>(PhantomData<(R, A, M)>);
+impl<R, A, M> FindAuthor<H160> for EvmAuthor<R, A, M>
+where
+	R: frame_system::Config,
+	A: FindAuthor<R::AccountId>,
+	M: AccountToEvmAddress<R::AccountId>,
+{
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		A::find_author(digests).map(|author| M::evm_address(&author))
+	}
+}
+
+/// Pays imbalances to the author of the current block, as found by `A`. They are burnt when no
+/// author is found.
+pub struct ToAuthor<R, A>(PhantomData<(R, A)>);
+impl<R, A> OnUnbalanced<NegativeImbalance<R>> for ToAuthor<R, A>
+where
+	R: pallet_balances::Config,
+	A: FindAuthor<R::AccountId>,
+{
+	fn on_nonzero_unbalanced(amount: NegativeImbalance<R>) {
+		let digest = frame_system::Pallet::<R>::digest();
+		let pre_runtime_digests = digest.logs().iter().filter_map(|d| d.as_pre_runtime());
+		if let Some(author) = A::find_author(pre_runtime_digests) {
+			pallet_balances::Pallet::<R>::resolve_creating(&author, amount);
+		}
+	}
+}
+
+/// Splits fees: `TreasuryCut` of them goes to `Treasury`, `AuthorCut` to the block author found by
+/// `A` and the rest is burnt. Tips go to the author in full.
+///
+/// The cuts are taken in that order, so if they add up to more than 100% the author gets what is
+/// left after the treasury.
+pub struct DealWithFees<R, Treasury, A, TreasuryCut, AuthorCut>(
+	PhantomData<(R, Treasury, A, TreasuryCut, AuthorCut)>,
+);
+impl<R, Treasury, A, TreasuryCut, AuthorCut> OnUnbalanced<NegativeImbalance<R>>
+	for DealWithFees<R, Treasury, A, TreasuryCut, AuthorCut>
+where
+	R: pallet_balances::Config,
+	Treasury: OnUnbalanced<NegativeImbalance<R>>,
+	A: FindAuthor<R::AccountId>,
+	TreasuryCut: Get<Perbill>,
+	AuthorCut: Get<Perbill>,
+{
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance<R>>)
+	where
+		NegativeImbalance<R>: Imbalance<B>,
+	{
+		if let Some(fees) = fees_then_tips.next() {
+			Self::on_unbalanced(fees);
+			if let Some(tips) = fees_then_tips.next() {
+				ToAuthor::<R, A>::on_unbalanced(tips);
+			}
+		}
+	}
+
+	fn on_nonzero_unbalanced(amount: NegativeImbalance<R>) {
+		let total = amount.peek();
+		let (to_treasury, rest) = amount.split(TreasuryCut::get() * total);
+		let (to_author, burnt) = rest.split(AuthorCut::get() * total);
+		Treasury::on_unbalanced(to_treasury);
+		ToAuthor::<R, A>::on_unbalanced(to_author);
+		// Dropping the imbalance reduces the total issuance.
+		drop(burnt);
+	}
+}
+
+/// Converts EVM gas to weight and back at `WeightPerGas`, so EVM execution fills blocks as much as
+/// the extrinsics taking the same time would.
+pub struct PolkafoundryGasWeightMapping<WeightPerGas>(PhantomData<WeightPerGas>);
+impl<WeightPerGas: Get<u64>> GasWeightMapping for PolkafoundryGasWeightMapping<WeightPerGas> {
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas.saturating_mul(WeightPerGas::get())
+	}
+
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight / WeightPerGas::get().max(1)
+	}
+}
@@ -1,5 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod impls;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 use frame_support::{
 	parameter_types, traits::{Currency, OneSessionHandler},
 	weights::{Weight, constants::WEIGHT_PER_SECOND, DispatchClass},
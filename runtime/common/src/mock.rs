@@ -0,0 +1,166 @@
+use crate::impls::{NegativeImbalance, PolkafoundryGasWeightMapping};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Currency, FindAuthor, OnUnbalanced},
+	weights::constants::WEIGHT_PER_SECOND,
+};
+use pallet_evm::{AddressMapping, EnsureAddressNever, EnsureAddressRoot};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	ConsensusEngineId,
+};
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+pub type Balance = u128;
+
+pub const TREASURY: AccountId = 100;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	// The runtimes' limits, which EVM transactions are weighed against.
+	type BlockWeights = crate::BlockWeights;
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+/// Maps an Ethereum address to the `u64` built from its last 8 bytes.
+pub struct TestAddressMapping;
+
+impl AddressMapping<AccountId> for TestAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		address.to_low_u64_be()
+	}
+}
+
+parameter_types! {
+	// As in the runtimes, which execute 40M gas per second.
+	pub const WeightPerGas: u64 = WEIGHT_PER_SECOND / 40_000_000;
+	pub const ChainId: u64 = 11;
+	pub BlockGasLimit: U256 = U256::from(u32::max_value());
+}
+
+impl pallet_evm::Config for Test {
+	type FeeCalculator = ();
+	type GasWeightMapping = PolkafoundryGasWeightMapping<WeightPerGas>;
+	type CallOrigin = EnsureAddressRoot<AccountId>;
+	type WithdrawOrigin = EnsureAddressNever<AccountId>;
+	type AddressMapping = TestAddressMapping;
+	type Currency = Balances;
+	type Event = Event;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Precompiles = ();
+	type ChainId = ChainId;
+	type BlockGasLimit = BlockGasLimit;
+	type OnChargeTransaction = ();
+}
+
+impl pallet_ethereum::Config for Test {
+	type Event = Event;
+	type FindAuthor = ();
+	type StateRoot = pallet_ethereum::IntermediateStateRoot;
+}
+
+/// Pays imbalances to `TREASURY`.
+pub struct ToTreasury;
+impl OnUnbalanced<NegativeImbalance<Test>> for ToTreasury {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance<Test>) {
+		Balances::resolve_creating(&TREASURY, amount);
+	}
+}
+
+thread_local! {
+	static AUTHOR: RefCell<Option<AccountId>> = RefCell::new(None);
+}
+
+/// Make `author` the block author `MockAuthor` finds.
+pub fn set_author(author: Option<AccountId>) {
+	AUTHOR.with(|v| *v.borrow_mut() = author);
+}
+
+/// Finds the author set with `set_author`, whatever the digests.
+pub struct MockAuthor;
+impl FindAuthor<AccountId> for MockAuthor {
+	fn find_author<'a, I>(_digests: I) -> Option<AccountId>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		AUTHOR.with(|v| *v.borrow())
+	}
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		EVM: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
+		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, ValidateUnsigned},
+	}
+);
+
+/// Externalities without any balance, the block authored by `author`.
+pub fn new_test_ext(author: Option<AccountId>) -> sp_io::TestExternalities {
+	set_author(author);
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::from(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,140 @@
+use crate::{impls::{DealWithFees, OrWhileEmpty, PolkafoundryGasWeightMapping}, mock::*, BlockWeights};
+use codec::Encode;
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{Currency, Get, OnUnbalanced},
+	weights::{DispatchClass, GetDispatchInfo},
+};
+use frame_system::CheckWeight;
+use pallet_ethereum::{Transaction, TransactionAction, TransactionSignature};
+use pallet_evm::GasWeightMapping;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	Perbill,
+};
+
+parameter_types! {
+	pub const TreasuryCut: Perbill = Perbill::from_percent(60);
+	pub const AuthorCut: Perbill = Perbill::from_percent(30);
+	pub const LargeTreasuryCut: Perbill = Perbill::from_percent(70);
+	pub const LargeAuthorCut: Perbill = Perbill::from_percent(50);
+	pub Elected: Vec<AccountId> = vec![1, 2];
+	pub NoneElected: Vec<AccountId> = vec![];
+	pub Authorities: Vec<AccountId> = vec![3];
+}
+
+const BLOCK_AUTHOR: AccountId = 1;
+
+/// 60% to the treasury, 30% to the author, 10% burnt.
+type Fees = DealWithFees<Test, ToTreasury, MockAuthor, TreasuryCut, AuthorCut>;
+/// 70% to the treasury and the remaining 30% to the author, as the cuts add up to 120%.
+type LargeCutFees = DealWithFees<Test, ToTreasury, MockAuthor, LargeTreasuryCut, LargeAuthorCut>;
+
+type Mapping = PolkafoundryGasWeightMapping<WeightPerGas>;
+
+#[test]
+fn gas_and_weight_convert_both_ways() {
+	assert_eq!(Mapping::gas_to_weight(21_000), 21_000 * WeightPerGas::get());
+	assert_eq!(Mapping::weight_to_gas(Mapping::gas_to_weight(21_000)), 21_000);
+	// Weight too small to pay for a unit of gas buys none.
+	assert_eq!(Mapping::weight_to_gas(WeightPerGas::get() - 1), 0);
+	assert_eq!(Mapping::gas_to_weight(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn block_gas_limit_fits_a_single_extrinsic() {
+	let max_extrinsic = BlockWeights::get().get(DispatchClass::Normal).max_extrinsic.unwrap();
+	let block_gas_limit = Mapping::weight_to_gas(max_extrinsic);
+
+	assert!(Mapping::gas_to_weight(block_gas_limit) <= max_extrinsic);
+	assert!(Mapping::gas_to_weight(block_gas_limit + 1) > max_extrinsic);
+}
+
+/// An Ethereum `transact` call asking for `gas_limit`. Only its weight matters, so it isn't
+/// signed by anyone in particular.
+fn transact(gas_limit: u64) -> Call {
+	Call::Ethereum(pallet_ethereum::Call::transact(Transaction {
+		nonce: U256::zero(),
+		gas_price: U256::zero(),
+		gas_limit: gas_limit.into(),
+		action: TransactionAction::Call(H160::repeat_byte(0x11)),
+		value: U256::zero(),
+		input: Vec::new(),
+		signature: TransactionSignature::new(27, H256::repeat_byte(1), H256::repeat_byte(1)).unwrap(),
+	}))
+}
+
+/// Account for `call` in the current block the way `CheckWeight` does before dispatching it.
+fn include(call: Call) -> Result<(), TransactionValidityError> {
+	CheckWeight::<Test>::do_pre_dispatch(&call.get_dispatch_info(), call.encode().len())
+}
+
+#[test]
+fn transact_is_weighed_by_its_gas_limit() {
+	assert_eq!(transact(21_000).get_dispatch_info().weight, Mapping::gas_to_weight(21_000));
+}
+
+#[test]
+fn heavy_contract_calls_are_bounded_per_block() {
+	let max_extrinsic = BlockWeights::get().get(DispatchClass::Normal).max_extrinsic.unwrap();
+	let max_gas = Mapping::weight_to_gas(max_extrinsic);
+
+	new_test_ext(None).execute_with(|| {
+		// A transaction asking for more gas than an extrinsic may weigh can't be included at all...
+		assert_eq!(include(transact(max_gas + 1)), Err(InvalidTransaction::ExhaustsResources.into()));
+		// ...the heaviest one allowed is...
+		assert_ok!(include(transact(max_gas)));
+		// ...but leaves no room for a second one in the same block.
+		assert_eq!(include(transact(max_gas)), Err(InvalidTransaction::ExhaustsResources.into()));
+	});
+}
+
+#[test]
+fn fees_are_split_between_treasury_author_and_burn() {
+	new_test_ext(Some(BLOCK_AUTHOR)).execute_with(|| {
+		Fees::on_unbalanceds(vec![Balances::issue(1000)].into_iter());
+
+		assert_eq!(Balances::free_balance(TREASURY), 600);
+		assert_eq!(Balances::free_balance(BLOCK_AUTHOR), 300);
+		assert_eq!(Balances::total_issuance(), 900);
+	});
+}
+
+#[test]
+fn cuts_above_100_percent_leave_the_author_the_rest() {
+	new_test_ext(Some(BLOCK_AUTHOR)).execute_with(|| {
+		LargeCutFees::on_unbalanceds(vec![Balances::issue(1000)].into_iter());
+
+		assert_eq!(Balances::free_balance(TREASURY), 700);
+		assert_eq!(Balances::free_balance(BLOCK_AUTHOR), 300);
+		assert_eq!(Balances::total_issuance(), 1000);
+	});
+}
+
+#[test]
+fn tips_go_to_the_author() {
+	new_test_ext(Some(BLOCK_AUTHOR)).execute_with(|| {
+		Fees::on_unbalanceds(vec![Balances::issue(1000), Balances::issue(50)].into_iter());
+
+		assert_eq!(Balances::free_balance(TREASURY), 600);
+		assert_eq!(Balances::free_balance(BLOCK_AUTHOR), 350);
+		assert_eq!(Balances::total_issuance(), 950);
+	});
+}
+
+#[test]
+fn author_share_is_burnt_without_an_author() {
+	new_test_ext(None).execute_with(|| {
+		Fees::on_unbalanceds(vec![Balances::issue(1000), Balances::issue(50)].into_iter());
+
+		assert_eq!(Balances::free_balance(TREASURY), 600);
+		assert_eq!(Balances::total_issuance(), 600);
+	});
+}
+
+#[test]
+fn potential_authors_fall_back_while_none_are_elected() {
+	assert_eq!(OrWhileEmpty::<Elected, Authorities>::get(), vec![1, 2]);
+	assert_eq!(OrWhileEmpty::<NoneElected, Authorities>::get(), vec![3]);
+}
@@ -28,8 +28,8 @@ pub mod weights {
 	use frame_support::weights::constants::WEIGHT_PER_SECOND;
 	/// Current approximation of the gas/s consumption considering
 	/// EVM execution over compiled WASM (on 4.4Ghz CPU).
-	/// Given the 500ms Weight, from which 75% only are used for transactions,
-	/// the total EVM execution gas limit is: GAS_PER_SECOND * 0.500 * 0.75 ~= 15_000_000.
+	/// Given the 2s Weight, from which 75% only are used for transactions,
+	/// the total EVM execution gas limit is: GAS_PER_SECOND * 2 * 0.75 ~= 60_000_000.
 	pub const GAS_PER_SECOND: u64 = 40_000_000;
 
 	/// Approximate ratio of the amount of Weight per Gas.
@@ -45,7 +45,7 @@ pub mod version {
 		spec_name: create_runtime_str!("halongbay"),
 		impl_name: create_runtime_str!("halongbay"),
 		authoring_version: 1,
-		spec_version: 1,
+		spec_version: 2,
 		impl_version: 1,
 		apis: crate::RUNTIME_API_VERSIONS,
 		transaction_version: 1,
@@ -14,8 +14,9 @@ use sp_runtime::{
 	AccountId32
 };
 use sp_runtime::traits::{
-	AccountIdLookup, BlakeTwo256, Block as BlockT,
+	AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto,
 };
+use sp_runtime::{DispatchError, DispatchResult};
 use sp_api::impl_runtime_apis;
 use sp_version::RuntimeVersion;
 #[cfg(feature = "std")]
@@ -26,7 +27,8 @@ use codec::{Encode, Decode};
 use fp_rpc::TransactionStatus;
 
 use pallet_evm::{
-	Account as EVMAccount, FeeCalculator, EnsureAddressTruncated, HashedAddressMapping, Runner
+	Account as EVMAccount, FeeCalculator, EnsureAddressTruncated, HashedAddressMapping, Runner,
+	GasWeightMapping,
 };
 use polkadot_parachain::primitives::Sibling;
 
@@ -37,9 +39,10 @@ use xcm_builder::{
 	SiblingParachainAsNative, SiblingParachainConvertsVia, SignedAccountId32AsNative,
 	SovereignSignedViaLocation, EnsureXcmOrigin,
 	AllowTopLevelPaidExecutionFrom, TakeWeightCredit, FixedWeightBounds, IsConcrete, NativeAsset,
-	AllowUnpaidExecutionFrom, ParentAsSuperuser, UsingComponents
+	AllowUnpaidExecutionFrom, ParentAsSuperuser, UsingComponents, FungiblesAdapter,
+	ConvertedConcreteAssetId, AsPrefixedGeneralIndex,
 };
-use xcm_executor::{Config, XcmExecutor};
+use xcm_executor::{Config, XcmExecutor, traits::JustTry};
 use xcm::v0::Xcm;
 
 // A few exports that help ease life for downstream crates.
@@ -50,7 +53,7 @@ pub use pallet_balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue, PalletId, match_type,
-	traits::{Randomness, IsInVec, All},
+	traits::{Randomness, IsInVec, All, Contains},
 	weights::{
 		Weight, IdentityFee, DispatchClass,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -62,6 +65,7 @@ pub use runtime_primitives::{
 };
 
 use runtime_common::{
+	impls::{AuraAccountAuthor, AuraAccounts, EvmAuthor, OrWhileEmpty, PolkafoundryGasWeightMapping},
 	BlockHashCount, BlockWeights, BlockLength,
 	OffchainSolutionWeightLimit, OffchainSolutionLengthLimit,
 	NORMAL_DISPATCH_RATIO, MAXIMUM_BLOCK_WEIGHT
@@ -172,7 +176,7 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	type DustRemoval = DealWithFees;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
@@ -180,10 +184,22 @@ impl pallet_balances::Config for Runtime {
 
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1;
+	pub const FeesToTreasury: Perbill = Perbill::from_percent(80);
+	pub const FeesToAuthor: Perbill = Perbill::from_percent(20);
 }
 
+/// Fees are split between the treasury and the block author.
+/// Transaction fees, EVM gas fees and dust all go through it.
+pub type DealWithFees = runtime_common::impls::DealWithFees<
+	Runtime,
+	Treasury,
+	AuraAccountAuthor<Runtime>,
+	FeesToTreasury,
+	FeesToAuthor,
+>;
+
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
@@ -249,6 +265,45 @@ type LocalAssetTransactor = CurrencyAdapter<
 	(),
 >;
 
+parameter_types! {
+	/// Assets of the `Assets` pallet are identified by their id under its pallet index.
+	pub const AssetsPalletLocation: MultiLocation = X1(PalletInstance(ASSETS_PALLET_INDEX));
+	pub CheckingAccount: AccountId = PalletId(*b"py/xcmch").into_account();
+}
+
+/// Teleports of `Assets` are not tracked.
+pub struct NoTeleportChecks;
+impl Contains<u32> for NoTeleportChecks {
+	fn contains(_: &u32) -> bool {
+		false
+	}
+}
+
+/// Means for transacting assets of the `Assets` pallet, so deposits of them (e.g. to the
+/// treasury) are visible on chain.
+type FungiblesTransactor = FungiblesAdapter<
+	// Use this fungibles implementation:
+	Assets,
+	// Use this asset when its location is an asset id under the `Assets` pallet:
+	ConvertedConcreteAssetId<
+		u32,
+		Balance,
+		AsPrefixedGeneralIndex<AssetsPalletLocation, u32, JustTry>,
+		JustTry,
+	>,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// We don't track any teleports of `Assets`.
+	NoTeleportChecks,
+	// The account used to check teleports, unused.
+	CheckingAccount,
+>;
+
+/// Means for transacting assets on this chain.
+type AssetTransactors = (LocalAssetTransactor, FungiblesTransactor);
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -295,7 +350,7 @@ impl Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
 	// How to withdraw and deposit an asset.
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
 	type IsReserve = NativeAsset;
 	type IsTeleporter = NativeAsset;	// <- should be enough to allow teleportation of ROC
@@ -353,12 +408,21 @@ impl cumulus_ping::Config for Runtime {
 }
 
 parameter_types! {
-	pub BlockGasLimit: U256 = U256::from(u32::max_value());
+	pub const WeightPerGas: u64 = WEIGHT_PER_GAS;
+	/// The gas equivalent of the weight a single normal extrinsic may have, so that a transaction
+	/// within the gas limit is never too heavy to be included.
+	pub BlockGasLimit: U256 = U256::from(
+		BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.expect("Normal extrinsics have weight limit configured by default; qed")
+			/ WEIGHT_PER_GAS
+	);
 }
 
 impl pallet_ethereum::Config for Runtime {
 	type Event = Event;
-	type FindAuthor = ();
+	type FindAuthor = EvmAuthor<Runtime, AuraAccountAuthor<Runtime>, AuthorMapping>;
 	type StateRoot = pallet_ethereum::IntermediateStateRoot;
 }
 
@@ -366,34 +430,281 @@ parameter_types! {
 	pub const ChainId: u64 = CHAIN_ID;
 }
 
+parameter_types! {
+	// EVM gas starts at the floor of the base fee, which busy blocks can raise a thousandfold.
+	pub DefaultBaseFeePerGas: U256 = U256::from(HLB / 1_000_000);
+	pub DefaultMinBaseFee: U256 = U256::from(HLB / 1_000_000);
+	pub DefaultMaxBaseFee: U256 = U256::from(HLB / 1_000);
+	pub const TargetBlockFullness: Permill = Permill::from_percent(50);
+}
+
+impl pallet_base_fee::Config for Runtime {
+	type Event = Event;
+	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+	type DefaultMinBaseFee = DefaultMinBaseFee;
+	type DefaultMaxBaseFee = DefaultMaxBaseFee;
+	type TargetBlockFullness = TargetBlockFullness;
+	type WeightInfo = weights::pallet_base_fee::WeightInfo<Runtime>;
+}
+
 impl pallet_evm::Config for Runtime {
-	type FeeCalculator = ();
-	type GasWeightMapping = ();
+	type FeeCalculator = BaseFee;
+	type GasWeightMapping = PolkafoundryGasWeightMapping<WeightPerGas>;
 	type CallOrigin = EnsureAddressTruncated;
 	type WithdrawOrigin = EnsureAddressTruncated;
 	type AddressMapping = HashedAddressMapping<BlakeTwo256>;
 	type Currency = Balances;
 	type Event = Event;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
-	type Precompiles = precompiles::PolkafoundryPrecompiles<Self>;
+	type Precompiles = precompiles::PolkafoundryPrecompiles<
+		Self,
+		precompiles::Combine<precompiles::StakingPrecompiles<Self>, precompiles::CrowdloanPrecompiles<Self>>,
+	>;
 	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
-	type OnChargeTransaction = ();
+	type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<Balances, DealWithFees>;
+}
+
+/// The native token as seen through the ERC-20 precompile, matching the chain spec properties.
+impl precompiles::Erc20Metadata for Runtime {
+	fn name() -> &'static str {
+		"Halongbay"
+	}
+
+	fn symbol() -> &'static str {
+		"HLB"
+	}
+
+	fn decimals() -> u8 {
+		12
+	}
+}
+
+/// Calls EVM contracts may make through the dispatch precompile. `Sudo`, the XCM pallets and
+/// the EVM pallets themselves stay out of reach.
+impl precompiles::DispatchAllowlist for Runtime {
+	fn pallets() -> &'static [&'static str] {
+		&["Balances"]
+	}
+
+	fn calls() -> &'static [(&'static str, &'static str)] {
+		&[
+			("Treasury", "donate"),
+			("Crowdloan", "associate_account"),
+			("Crowdloan", "claim"),
+			("Crowdloan", "claim_all"),
+			("Assets", "transfer"),
+			("Assets", "transfer_keep_alive"),
+		]
+	}
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: Balance = HLB;
+}
+
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ClaimDeadline: BlockNumber = 30 * DAYS;
+	pub const MaxSweepPerCall: u32 = 100;
+	pub const DefaultPayoutMode: pallet_crowdloan_rewards::PayoutMode =
+		pallet_crowdloan_rewards::PayoutMode::Transfer;
 }
 
 impl pallet_crowdloan_rewards::Config for Runtime {
 	type Event = Event;
 	type RelayChainAccountId = AccountId32;
+	type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type ChainId = ChainId;
+	type ClaimDeadline = ClaimDeadline;
+	type MaxSweepPerCall = MaxSweepPerCall;
+	type Vesting = Vesting;
+	type DefaultPayoutMode = DefaultPayoutMode;
+	type WeightInfo = weights::pallet_crowdloan_rewards::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"Treasury");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 100 * HLB;
+	pub const SpendPeriod: BlockNumber = 6 * DAYS;
+	pub const MaxApprovals: u32 = 100;
+	pub const Burn: Permill = Permill::from_percent(1);
+	pub const MaxPaymentsPerBlock: u32 = 50;
+}
+
+/// Transfers ERC-20 tokens out of the treasury by calling the token contract from its EVM address.
+pub struct TreasuryErc20;
+impl pallet_treasury::Erc20Transactor for TreasuryErc20 {
+	fn transfer_weight(gas_limit: u64) -> Weight {
+		<<Runtime as pallet_evm::Config>::GasWeightMapping as GasWeightMapping>::gas_to_weight(gas_limit)
+	}
+
+	fn transfer(source: H160, token: H160, dest: H160, amount: U256, gas_limit: u64) -> DispatchResult {
+		// `transfer(address,uint256)`
+		let mut input = sp_io::hashing::keccak_256(b"transfer(address,uint256)")[..4].to_vec();
+		input.extend_from_slice(H256::from(dest).as_bytes());
+		let mut value = [0u8; 32];
+		amount.to_big_endian(&mut value);
+		input.extend_from_slice(&value);
+
+		let info = <Runtime as pallet_evm::Config>::Runner::call(
+			source,
+			token,
+			input,
+			U256::zero(),
+			gas_limit,
+			None,
+			None,
+			<Runtime as pallet_evm::Config>::config(),
+		).map_err(|err| -> DispatchError { err.into() })?;
+
+		// Tokens that return nothing are taken to have succeeded, the others must return `true`.
+		let returned_true = info.value.is_empty() || info.value == H256::from_low_u64_be(1).as_bytes();
+		if info.exit_reason.is_succeed() && returned_true {
+			Ok(())
+		} else {
+			Err(DispatchError::Other("ERC-20 transfer failed"))
+		}
+	}
 }
 
 impl pallet_treasury::Config for Runtime {
 	type PalletId = TreasuryPalletId;
 	type Currency = Balances;
+	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+	type RejectOrigin = frame_system::EnsureRoot<AccountId>;
 	type Event = Event;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type MaxApprovals = MaxApprovals;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = Bounties;
+	type MaxPaymentsPerBlock = MaxPaymentsPerBlock;
+	type Assets = Assets;
+	type Erc20 = TreasuryErc20;
+	type AddressMapping = <Runtime as pallet_evm::Config>::AddressMapping;
+	type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
+}
+
+/// Index of the `Assets` pallet in `construct_runtime!`, part of the XCM location of its assets.
+pub const ASSETS_PALLET_INDEX: u8 = 50;
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * HLB;
+	pub const ApprovalDeposit: Balance = HLB;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * HLB;
+	pub const MetadataDepositPerByte: Balance = HLB / 10;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const BountyDepositBase: Balance = HLB;
+	pub const DataDepositPerByte: Balance = HLB / 100;
+	pub const BountyDepositPayoutDelay: BlockNumber = 1 * DAYS;
+	pub const BountyUpdatePeriod: BlockNumber = 14 * DAYS;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: Balance = 5 * HLB;
+	pub const MaximumReasonLength: u32 = 16384;
+}
+
+impl pallet_bounties::Config for Runtime {
+	type Event = Event;
+	type BountyDepositBase = BountyDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type MaximumReasonLength = MaximumReasonLength;
+	type WeightInfo = weights::pallet_bounties::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxTippers: u32 = 100;
+}
+
+/// The governance members allowed to tip, managed by root.
+type TipperMembershipInstance = pallet_membership::Instance1;
+impl pallet_membership::Config<TipperMembershipInstance> for Runtime {
+	type Event = Event;
+	type AddOrigin = frame_system::EnsureRoot<AccountId>;
+	type RemoveOrigin = frame_system::EnsureRoot<AccountId>;
+	type SwapOrigin = frame_system::EnsureRoot<AccountId>;
+	type ResetOrigin = frame_system::EnsureRoot<AccountId>;
+	type PrimeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MembershipInitialized = ();
+	type MembershipChanged = ();
+	type MaxMembers = MaxTippers;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TipCountdown: BlockNumber = 1 * DAYS;
+}
+
+impl pallet_tips::Config for Runtime {
+	type Event = Event;
+	type Tippers = TipperMembership;
+	type TipCountdown = TipCountdown;
+	type MaximumReasonLength = MaximumReasonLength;
+	type WeightInfo = weights::pallet_tips::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const AuthorMappingDeposit: Balance = 100 * HLB;
+}
+
+impl pallet_author_mapping::Config for Runtime {
+	type Event = Event;
+	type AuthorId = author_inherent::NimbusId;
+	type DepositCurrency = Balances;
+	type DepositAmount = AuthorMappingDeposit;
+	type WeightInfo = weights::pallet_author_mapping::WeightInfo<Runtime>;
+}
+
+impl author_inherent::Config for Runtime {
+	type Event = Event;
+	type AuthorId = author_inherent::NimbusId;
+	type AccountLookup = AuthorMapping;
+	type EventHandler = Staking;
+	type CanAuthor = AuthorFilter;
+	type EvmAddress = AuthorMapping;
+	type SlotProvider = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>;
+	type WeightInfo = weights::author_inherent::WeightInfo<Runtime>;
+}
+
+impl pallet_author_filter::Config for Runtime {
+	type Event = Event;
+	type RandomnessSource = RandomnessCollectiveFlip;
+	type PotentialAuthors = OrWhileEmpty<Staking, AuraAccounts<Runtime>>;
+	type SlotProvider = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>;
+	type WeightInfo = weights::pallet_author_filter::WeightInfo<Runtime>;
 }
 
 // parameter_types! {
@@ -450,34 +761,36 @@ parameter_types! {
 	pub const MaxCollatorsPerNominator: u32 = 5;
 	pub const MaxNominationsPerCollator: u32 = 2;
 	pub const BondDuration: u32 = 2;
-	pub const MinCollatorStake: u32 = 500;
-	pub const MinNominatorStake: u32 = 100;
+	pub const MinCollatorStake: Balance = 500 * HLB;
+	pub const MinNominatorStake: Balance = 100 * HLB;
 	pub const PayoutDuration: u32 = 2;
 	pub const DesiredTarget: u32 = 2;
 }
 
-// impl frame_election_provider_support::onchain::Config for Runtime {
-// 	type AccountId = AccountId32;
-// 	type BlockNumber = BlockNumber;
-// 	type BlockWeights = BlockWeights;
-// 	type Accuracy = Perbill;
-// 	type DataProvider = Staking;
-// }
+impl frame_election_provider_support::onchain::Config for Runtime {
+	type AccountId = AccountId32;
+	type BlockNumber = BlockNumber;
+	type BlockWeights = BlockWeights;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
 
-// impl polkafoundry_staking::Config for Runtime {
-// 	const MAX_COLLATORS_PER_NOMINATOR: u32 = 5u32;
-// 	type Event = Event;
-// 	type Currency = Balances;
-// 	type BlocksPerRound = BlocksPerRound;
-// 	type MaxNominationsPerCollator = MaxNominationsPerCollator;
-// 	type BondDuration = BondDuration;
-// 	type MinCollatorStake = MinCollatorStake;
-// 	type MinNominatorStake = MinNominatorStake;
-// 	type PayoutDuration = PayoutDuration;
-// 	type ElectionProvider = frame_election_provider_support::onchain::OnChainSequentialPhragmen<Self>;
-// 	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
-// 	type DesiredTarget = DesiredTarget;
-// }
+impl polkafoundry_staking::Config for Runtime {
+	const MAX_COLLATORS_PER_NOMINATOR: u32 = 5u32;
+	type Event = Event;
+	type Currency = Balances;
+	type BlocksPerRound = BlocksPerRound;
+	type MaxNominationsPerCollator = MaxNominationsPerCollator;
+	type BondDuration = BondDuration;
+	type MinCollatorStake = MinCollatorStake;
+	type MinNominatorStake = MinNominatorStake;
+	type PayoutDuration = PayoutDuration;
+	type ElectionProvider = frame_election_provider_support::onchain::OnChainSequentialPhragmen<Self>;
+	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
+	type DesiredTarget = DesiredTarget;
+	type RewardRemainder = Treasury;
+	type WeightInfo = weights::polkafoundry_staking::WeightInfo<Runtime>;
+}
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
@@ -498,9 +811,13 @@ construct_runtime!(
 		// ElectionProviderMultiPhase: pallet_election_provider_multi_phase::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
 		EVM: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
 		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, ValidateUnsigned},
+		BaseFee: pallet_base_fee::{Pallet, Call, Storage, Event<T>},
 		Crowdloan: pallet_crowdloan_rewards::{Pallet, Call, Storage, Event<T>},
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
-		// Staking: polkafoundry_staking::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Bounties: pallet_bounties::{Pallet, Call, Storage, Event<T>},
+		TipperMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>},
+		Tips: pallet_tips::{Pallet, Call, Storage, Event<T>},
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>},
 		Aura: pallet_aura::{Pallet, Config<T>},
 		AuraExt: cumulus_pallet_aura_ext::{Pallet, Config},
 		// XCM helpers.
@@ -508,6 +825,13 @@ construct_runtime!(
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Call, Event<T>, Origin},
 		DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>},
+		AuthorMapping: pallet_author_mapping::{Pallet, Call, Storage, Config<T>, Event<T>},
+		AuthorInherent: author_inherent::{Pallet, Call, Storage, Config, Event<T>, Inherent},
+		AuthorFilter: pallet_author_filter::{Pallet, Call, Storage, Config, Event<T>},
+		// Last of the implicitly indexed pallets, not to shift the indices of the ones before it.
+		Staking: polkafoundry_staking::{Pallet, Call, Storage, Event<T>, Config<T>},
+
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>} = 50,
 
 		Spambot: cumulus_ping::{Pallet, Call, Storage, Event<T>} = 99,
 	}
@@ -784,6 +1108,50 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_crowdloan_rewards_rpc_runtime_api::CrowdloanRewardsApi<
+		Block,
+		AccountId,
+		AccountId32,
+		Balance,
+		BlockNumber,
+	> for Runtime {
+		fn associated_relay_account(account: AccountId) -> Option<AccountId32> {
+			Crowdloan::associated_account(account)
+		}
+
+		fn associated_accounts(relay_account: AccountId32) -> Vec<AccountId> {
+			Crowdloan::associated_accounts(&relay_account)
+		}
+
+		fn reward_status(
+			campaign_id: u32,
+			relay_account: AccountId32,
+			at: BlockNumber,
+		) -> Option<pallet_crowdloan_rewards_rpc_runtime_api::RewardStatus<Balance, BlockNumber>> {
+			Crowdloan::reward_status(campaign_id, &relay_account, at)
+		}
+	}
+
+	impl pallet_author_filter_runtime_api::AuthorFilterApi<Block, AccountId, u32> for Runtime {
+		fn can_author(author: AccountId, slot: u32) -> bool {
+			AuthorFilter::can_author_at(&author, slot)
+		}
+	}
+
+	impl pallet_author_mapping_runtime_api::AuthorMappingApi<Block, author_inherent::NimbusId, AccountId> for Runtime {
+		fn account_id(author_id: author_inherent::NimbusId) -> Option<AccountId> {
+			use author_inherent::AccountLookup;
+			AuthorMapping::lookup_account(&author_id)
+		}
+	}
+
+	impl pallet_treasury_rpc_runtime_api::TreasuryApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn scheduled_payments(
+		) -> Vec<pallet_treasury_rpc_runtime_api::ScheduledPayment<AccountId, Balance, BlockNumber>> {
+			Treasury::scheduled_payments()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -794,6 +1162,25 @@ impl_runtime_apis! {
 			use frame_system_benchmarking::Pallet as SystemBench;
 			impl frame_system_benchmarking::Config for Runtime {}
 
+			impl pallet_treasury::benchmarking::BenchmarkHelper for Runtime {
+				fn create_asset(who: &AccountId, amount: Balance) -> u32 {
+					// An id genesis does not take.
+					let id = u32::max_value();
+					let owner = sp_runtime::MultiAddress::Id(who.clone());
+					Assets::force_create(Origin::root(), id, owner.clone(), true, 1)
+						.expect("the id is free; qed");
+					Assets::mint(Origin::signed(who.clone()), id, owner, amount)
+						.expect("`who` is the issuer; qed");
+					id
+				}
+
+				fn create_erc20(_amount: U256) -> H160 {
+					// Calling an address without code succeeds and returns nothing, which leaves
+					// the treasury's own work to be measured.
+					H160::repeat_byte(0xee)
+				}
+			}
+
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac")
@@ -818,6 +1205,15 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_crowdloan_rewards, Crowdloan);
+			add_benchmark!(params, batches, pallet_treasury, Treasury);
+			add_benchmark!(params, batches, pallet_bounties, Bounties);
+			add_benchmark!(params, batches, pallet_tips, Tips);
+			add_benchmark!(params, batches, pallet_author_mapping, AuthorMapping);
+			add_benchmark!(params, batches, author_inherent, AuthorInherent);
+			add_benchmark!(params, batches, pallet_author_filter, AuthorFilter);
+			add_benchmark!(params, batches, pallet_base_fee, BaseFee);
+			add_benchmark!(params, batches, polkafoundry_staking, Staking);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -0,0 +1,19 @@
+//! Weights for author_inherent
+//!
+//! Estimated from the storage accesses of each call, not benchmarked yet.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for author_inherent.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> author_inherent::WeightInfo for WeightInfo<T> {
+	fn set_author() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
@@ -14,4 +14,13 @@
 // limitations under the License.
 
 //! A list of the different weight modules for our runtime.
+pub mod author_inherent;
+pub mod pallet_author_filter;
+pub mod pallet_author_mapping;
+pub mod pallet_base_fee;
+pub mod pallet_bounties;
+pub mod pallet_crowdloan_rewards;
 pub mod pallet_election_provider_multi_phase;
+pub mod pallet_tips;
+pub mod pallet_treasury;
+pub mod polkafoundry_staking;
@@ -23,8 +23,8 @@ pub mod weights {
 	use frame_support::weights::constants::WEIGHT_PER_SECOND;
 	/// Current approximation of the gas/s consumption considering
 	/// EVM execution over compiled WASM (on 4.4Ghz CPU).
-	/// Given the 500ms Weight, from which 75% only are used for transactions,
-	/// the total EVM execution gas limit is: GAS_PER_SECOND * 0.500 * 0.75 ~= 15_000_000.
+	/// Given the 2s Weight, from which 75% only are used for transactions,
+	/// the total EVM execution gas limit is: GAS_PER_SECOND * 2 * 0.75 ~= 60_000_000.
 	pub const GAS_PER_SECOND: u64 = 40_000_000;
 
 	/// Approximate ratio of the amount of Weight per Gas.
@@ -62,6 +62,7 @@ pub use runtime_primitives::{
 };
 
 use runtime_common::{
+	impls::{AuraAccountAuthor, EvmAuthor, PolkafoundryGasWeightMapping},
 	BlockHashCount, BlockWeights, BlockLength,
 	OffchainSolutionWeightLimit, OffchainSolutionLengthLimit,
 	NORMAL_DISPATCH_RATIO, MAXIMUM_BLOCK_WEIGHT
@@ -170,7 +171,7 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	type DustRemoval = DealWithFees;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
@@ -178,10 +179,23 @@ impl pallet_balances::Config for Runtime {
 
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1;
+	// No treasury runs on this chain to take the 80% halongbay pays into one.
+	pub const FeesToTreasury: Perbill = Perbill::from_percent(0);
+	pub const FeesToAuthor: Perbill = Perbill::from_percent(20);
 }
 
+/// The block author gets 20% of the fees and the other 80% is burnt, where all of them were burnt
+/// before authors were paid. Transaction fees, EVM gas fees and dust all go through it.
+pub type DealWithFees = runtime_common::impls::DealWithFees<
+	Runtime,
+	(),
+	AuraAccountAuthor<Runtime>,
+	FeesToTreasury,
+	FeesToAuthor,
+>;
+
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
@@ -351,12 +365,21 @@ impl cumulus_ping::Config for Runtime {
 }
 
 parameter_types! {
-	pub BlockGasLimit: U256 = U256::from(u32::max_value());
+	pub const WeightPerGas: u64 = WEIGHT_PER_GAS;
+	/// The gas equivalent of the weight a single normal extrinsic may have, so that a transaction
+	/// within the gas limit is never too heavy to be included.
+	pub BlockGasLimit: U256 = U256::from(
+		BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.expect("Normal extrinsics have weight limit configured by default; qed")
+			/ WEIGHT_PER_GAS
+	);
 }
 
 impl pallet_ethereum::Config for Runtime {
 	type Event = Event;
-	type FindAuthor = ();
+	type FindAuthor = EvmAuthor<Runtime, AuraAccountAuthor<Runtime>, author_inherent::TruncateAccountId>;
 	type StateRoot = pallet_ethereum::IntermediateStateRoot;
 }
 
@@ -364,9 +387,26 @@ parameter_types! {
 	pub const ChainId: u64 = CHAIN_ID;
 }
 
+parameter_types! {
+	// EVM gas starts at the floor of the base fee, which busy blocks can raise a thousandfold.
+	pub DefaultBaseFeePerGas: U256 = U256::from(PKS / 1_000_000_000);
+	pub DefaultMinBaseFee: U256 = U256::from(PKS / 1_000_000_000);
+	pub DefaultMaxBaseFee: U256 = U256::from(PKS / 1_000_000);
+	pub const TargetBlockFullness: Permill = Permill::from_percent(50);
+}
+
+impl pallet_base_fee::Config for Runtime {
+	type Event = Event;
+	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+	type DefaultMinBaseFee = DefaultMinBaseFee;
+	type DefaultMaxBaseFee = DefaultMaxBaseFee;
+	type TargetBlockFullness = TargetBlockFullness;
+	type WeightInfo = ();
+}
+
 impl pallet_evm::Config for Runtime {
-	type FeeCalculator = ();
-	type GasWeightMapping = ();
+	type FeeCalculator = BaseFee;
+	type GasWeightMapping = PolkafoundryGasWeightMapping<WeightPerGas>;
 	type CallOrigin = EnsureAddressTruncated;
 	type WithdrawOrigin = EnsureAddressTruncated;
 	type AddressMapping = HashedAddressMapping<BlakeTwo256>;
@@ -376,7 +416,34 @@ impl pallet_evm::Config for Runtime {
 	type Precompiles = precompiles::PolkafoundryPrecompiles<Self>;
 	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
-	type OnChargeTransaction = ();
+	type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<Balances, DealWithFees>;
+}
+
+/// The native token as seen through the ERC-20 precompile, matching the chain spec properties.
+impl precompiles::Erc20Metadata for Runtime {
+	fn name() -> &'static str {
+		"PolkaSmith"
+	}
+
+	fn symbol() -> &'static str {
+		"PKS"
+	}
+
+	fn decimals() -> u8 {
+		18
+	}
+}
+
+/// Calls EVM contracts may make through the dispatch precompile. `Sudo`, the XCM pallets and
+/// the EVM pallets themselves stay out of reach.
+impl precompiles::DispatchAllowlist for Runtime {
+	fn pallets() -> &'static [&'static str] {
+		&["Balances"]
+	}
+
+	fn calls() -> &'static [(&'static str, &'static str)] {
+		&[]
+	}
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -398,6 +465,7 @@ construct_runtime!(
 		// ElectionProviderMultiPhase: pallet_election_provider_multi_phase::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
 		EVM: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
 		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, ValidateUnsigned},
+		BaseFee: pallet_base_fee::{Pallet, Call, Storage, Event<T>},
 		Aura: pallet_aura::{Pallet, Config<T>},
 		AuraExt: cumulus_pallet_aura_ext::{Pallet, Config},
 
@@ -682,6 +750,46 @@ impl_runtime_apis! {
 		}
 	}
 
+	// Authors are not filtered in this runtime, the api is implemented so that the node can run
+	// every runtime with the same collator service.
+	impl pallet_author_filter_runtime_api::AuthorFilterApi<Block, AccountId, u32> for Runtime {
+		fn can_author(_author: AccountId, _slot: u32) -> bool {
+			true
+		}
+	}
+
+	impl pallet_author_mapping_runtime_api::AuthorMappingApi<Block, author_inherent::NimbusId, AccountId> for Runtime {
+		fn account_id(_author_id: author_inherent::NimbusId) -> Option<AccountId> {
+			None
+		}
+	}
+
+	// Crowdloan rewards are not part of this runtime, the api is implemented so that the node can
+	// serve every runtime with the same RPC extensions.
+	impl pallet_crowdloan_rewards_rpc_runtime_api::CrowdloanRewardsApi<
+		Block,
+		AccountId,
+		AccountId32,
+		Balance,
+		BlockNumber,
+	> for Runtime {
+		fn associated_relay_account(_account: AccountId) -> Option<AccountId32> {
+			None
+		}
+
+		fn associated_accounts(_relay_account: AccountId32) -> Vec<AccountId> {
+			Vec::new()
+		}
+
+		fn reward_status(
+			_campaign_id: u32,
+			_relay_account: AccountId32,
+			_at: BlockNumber,
+		) -> Option<pallet_crowdloan_rewards_rpc_runtime_api::RewardStatus<Balance, BlockNumber>> {
+			None
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -0,0 +1,124 @@
+//! Minimal Solidity ABI decoding and encoding for the precompiles.
+
+use evm::ExitError;
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::Bounded;
+use sp_std::{convert::TryFrom, vec::Vec};
+
+/// Reads the 32-byte words of an ABI-encoded call.
+pub struct AbiReader<'a> {
+	input: &'a [u8],
+	cursor: usize,
+}
+
+impl<'a> AbiReader<'a> {
+	/// Split `input` into the function selector and a reader over the arguments.
+	pub fn new_with_selector(input: &'a [u8]) -> Result<(u32, Self), ExitError> {
+		if input.len() < 4 {
+			return Err(ExitError::Other("tried to parse selector out of bounds".into()));
+		}
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&input[..4]);
+
+		Ok((u32::from_be_bytes(selector), Self { input: &input[4..], cursor: 0 }))
+	}
+
+	/// Read the next word.
+	pub fn read_word(&mut self) -> Result<&'a [u8], ExitError> {
+		let end = self.cursor + 32;
+		let word = self.input.get(self.cursor..end)
+			.ok_or_else(|| ExitError::Other("tried to parse word out of bounds".into()))?;
+		self.cursor = end;
+		Ok(word)
+	}
+
+	/// Read the next word as a `uint256`.
+	pub fn read_u256(&mut self) -> Result<U256, ExitError> {
+		Ok(U256::from_big_endian(self.read_word()?))
+	}
+
+	/// Read the next word as an amount, rejecting values `Balance` can't hold.
+	pub fn read_balance<Balance: TryFrom<u128>>(&mut self) -> Result<Balance, ExitError> {
+		let amount = self.read_u256()?;
+		if amount > U256::from(u128::max_value()) {
+			return Err(ExitError::Other("amount is too large".into()));
+		}
+		Balance::try_from(amount.low_u128())
+			.map_err(|_| ExitError::Other("amount is too large".into()))
+	}
+
+	/// Read the next word as an amount, capping values `Balance` can't hold at its maximum.
+	pub fn read_balance_saturating<Balance: TryFrom<u128> + Bounded>(
+		&mut self,
+	) -> Result<Balance, ExitError> {
+		let amount = self.read_u256()?;
+		if amount > U256::from(u128::max_value()) {
+			return Ok(Balance::max_value());
+		}
+		Ok(Balance::try_from(amount.low_u128()).unwrap_or_else(|_| Balance::max_value()))
+	}
+
+	/// Read the next word as a `uint32`, rejecting larger values.
+	pub fn read_u32(&mut self) -> Result<u32, ExitError> {
+		let value = self.read_u256()?;
+		if value > U256::from(u32::max_value()) {
+			return Err(ExitError::Other("value is too large for uint32".into()));
+		}
+		Ok(value.low_u32())
+	}
+
+	/// Read the next word as the offset of a dynamic `bytes`, and return the bytes found there.
+	pub fn read_bytes(&mut self) -> Result<Vec<u8>, ExitError> {
+		let out_of_bounds = || ExitError::Other("tried to parse bytes out of bounds".into());
+
+		let offset = self.read_u256()?;
+		if offset > U256::from(self.input.len()) {
+			return Err(out_of_bounds());
+		}
+		let mut data = Self { input: self.input, cursor: offset.low_u64() as usize };
+
+		let len = data.read_u256()?;
+		if len > U256::from(self.input.len()) {
+			return Err(out_of_bounds());
+		}
+		let start = data.cursor;
+		let end = start + len.low_u64() as usize;
+		self.input.get(start..end).map(|bytes| bytes.to_vec()).ok_or_else(out_of_bounds)
+	}
+
+	/// Read the next word as a `bytes32`.
+	pub fn read_bytes32(&mut self) -> Result<H256, ExitError> {
+		Ok(H256::from_slice(self.read_word()?))
+	}
+
+	/// Read the next word as an `address`, rejecting dirty upper bytes.
+	pub fn read_address(&mut self) -> Result<H160, ExitError> {
+		let word = self.read_word()?;
+		if word[..12].iter().any(|byte| *byte != 0) {
+			return Err(ExitError::Other("address has dirty upper bytes".into()));
+		}
+		Ok(H160::from_slice(&word[12..]))
+	}
+}
+
+/// Encode a `uint256` return value.
+pub fn encode_u256(value: U256) -> Vec<u8> {
+	let mut word = [0u8; 32];
+	value.to_big_endian(&mut word);
+	word.to_vec()
+}
+
+/// Encode a `bool` return value.
+pub fn encode_bool(value: bool) -> Vec<u8> {
+	encode_u256(if value { U256::one() } else { U256::zero() })
+}
+
+/// Encode a `string` return value.
+pub fn encode_string(value: &str) -> Vec<u8> {
+	let mut output = encode_u256(U256::from(32));
+	output.extend(encode_u256(U256::from(value.len())));
+	output.extend_from_slice(value.as_bytes());
+	// Pad the string to a whole number of words.
+	output.resize(output.len() + (32 - value.len() % 32) % 32, 0);
+	output
+}
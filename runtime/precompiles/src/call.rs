@@ -0,0 +1,93 @@
+//! Helpers of the precompiles wrapping pallet calls and storage.
+
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::weights::Weight;
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use sp_core::H160;
+use sp_std::vec::Vec;
+
+environmental::environmental!(dispatching: bool);
+
+/// Dispatch `call` from the caller's account, charging the gas equivalent of its weight.
+///
+/// `address` is the one of the precompile dispatching. The call is refused when the precompile
+/// doesn't run in its own context, and when it is dispatched from within another dispatch, e.g.
+/// through `EVM::call`.
+pub fn dispatch<Runtime>(
+	address: u64,
+	call: <Runtime as frame_system::Config>::Call,
+	target_gas: Option<u64>,
+	context: &Context,
+) -> Result<PrecompileOutput, ExitError>
+where
+	Runtime: pallet_evm::Config,
+	<Runtime as frame_system::Config>::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<<Runtime as frame_system::Config>::Call as Dispatchable>::Origin:
+		From<Option<Runtime::AccountId>>,
+{
+	ensure_own_context(address, context)?;
+	let cost = charge::<Runtime>(call.get_dispatch_info().weight, target_gas)?;
+	let origin = Runtime::AddressMapping::into_account_id(context.caller);
+
+	non_reentrant(|| {
+		call.dispatch(Some(origin).into())
+			.map_err(|e| ExitError::Other(<&'static str>::from(e.error).into()))
+	})?;
+
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Stopped,
+		cost,
+		output: Vec::new(),
+		logs: Vec::new(),
+	})
+}
+
+/// Fail unless the precompile at `address` runs in its own context. A contract reaching it with
+/// `DELEGATECALL` or `CALLCODE` would otherwise act for whoever called the contract, and the
+/// precompile's substrate writes would outlive a revert of the contract's frame.
+pub fn ensure_own_context(address: u64, context: &Context) -> Result<(), ExitError> {
+	if context.address != H160::from_low_u64_be(address) {
+		return Err(ExitError::Other("cannot be called with DELEGATECALL or CALLCODE".into()));
+	}
+	Ok(())
+}
+
+/// Run `f`, unless this is already within a dispatch.
+pub(crate) fn non_reentrant<T>(f: impl FnOnce() -> Result<T, ExitError>) -> Result<T, ExitError> {
+	if dispatching::with(|_| ()).is_some() {
+		return Err(ExitError::Other("nested dispatch is not allowed".into()));
+	}
+	dispatching::using(&mut true, f)
+}
+
+/// Return `output` of a getter doing `reads` storage reads.
+#[cfg(any(feature = "staking", feature = "crowdloan"))]
+pub fn view<Runtime: pallet_evm::Config>(
+	reads: u64,
+	target_gas: Option<u64>,
+	output: Vec<u8>,
+) -> Result<PrecompileOutput, ExitError> {
+	use frame_support::traits::Get;
+
+	let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(reads);
+
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		cost: charge::<Runtime>(weight, target_gas)?,
+		output,
+		logs: Vec::new(),
+	})
+}
+
+/// The gas equivalent of `weight`, provided the caller supplied enough of it.
+pub fn charge<Runtime: pallet_evm::Config>(
+	weight: Weight,
+	target_gas: Option<u64>,
+) -> Result<u64, ExitError> {
+	let cost = Runtime::GasWeightMapping::weight_to_gas(weight);
+	match target_gas {
+		Some(gas) if cost > gas => Err(ExitError::OutOfGas),
+		_ => Ok(cost),
+	}
+}
@@ -0,0 +1,80 @@
+//! Lets EVM accounts claim their crowdloan rewards.
+//!
+//! The caller is mapped to its substrate account with the runtime's `AddressMapping`, and that
+//! account is the one associated with the relay chain account and paid the rewards. The Solidity
+//! interface is `tests/contracts/CrowdloanInterface.sol`.
+
+use crate::abi::{encode_u256, AbiReader};
+use crate::call::{dispatch, view};
+use crate::CROWDLOAN_PRECOMPILE;
+use codec::Decode;
+use evm::{executor::PrecompileOutput, Context, ExitError};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use pallet_crowdloan_rewards::Call as CrowdloanCall;
+use pallet_evm::{AddressMapping, Precompile};
+use sp_runtime::traits::{Saturating, UniqueSaturatedInto, Zero};
+use sp_runtime::MultiSignature;
+use sp_std::marker::PhantomData;
+
+// Selectors of the functions in `CrowdloanInterface.sol`.
+const SELECTOR_ASSOCIATE_ACCOUNT: u32 = 0x0baf2f76;
+const SELECTOR_CLAIM: u32 = 0x04951891;
+const SELECTOR_CLAIMABLE: u32 = 0x402914f5;
+
+/// Wraps the calls and storage of `pallet_crowdloan_rewards` in a Solidity ABI.
+pub struct CrowdloanWrapper<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for CrowdloanWrapper<Runtime>
+where
+	Runtime: pallet_crowdloan_rewards::Config + pallet_evm::Config,
+	<Runtime as frame_system::Config>::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<CrowdloanCall<Runtime>>,
+	<<Runtime as frame_system::Config>::Call as Dispatchable>::Origin:
+		From<Option<Runtime::AccountId>>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		let (selector, mut args) = AbiReader::new_with_selector(input)?;
+
+		let call = match selector {
+			SELECTOR_ASSOCIATE_ACCOUNT => {
+				let relay_account = args.read_bytes32()?;
+				let relay_account = Runtime::RelayChainAccountId::decode(&mut relay_account.as_bytes())
+					.map_err(|_| ExitError::Other("invalid relay chain account".into()))?;
+				let proof = MultiSignature::decode(&mut &args.read_bytes()?[..])
+					.map_err(|_| ExitError::Other("invalid proof".into()))?;
+				CrowdloanCall::<Runtime>::associate_account(relay_account, proof)
+			},
+			SELECTOR_CLAIM => CrowdloanCall::<Runtime>::claim(args.read_u32()?),
+			SELECTOR_CLAIMABLE => {
+				let who = Runtime::AddressMapping::into_account_id(args.read_address()?);
+				let relay_account = match pallet_crowdloan_rewards::Pallet::<Runtime>::associated_account(&who) {
+					Some(relay_account) => relay_account,
+					None => return view::<Runtime>(1, target_gas, encode_u256(0.into())),
+				};
+
+				let campaigns = pallet_crowdloan_rewards::Pallet::<Runtime>::next_campaign_id();
+				let now = frame_system::Pallet::<Runtime>::block_number();
+				let claimable = (0..campaigns)
+					.filter_map(|campaign_id| {
+						pallet_crowdloan_rewards::Pallet::<Runtime>::reward_status(campaign_id, &relay_account, now)
+					})
+					.fold(Zero::zero(), |total: pallet_treasury::BalanceOf<Runtime>, status| {
+						total.saturating_add(status.claimable_reward)
+					});
+				let claimable: u128 = claimable.unique_saturated_into();
+				// Besides the association, campaign count and block number, each campaign reads its
+				// info and the contribution of the relay account.
+				let reads = 3 + 2 * campaigns as u64;
+				return view::<Runtime>(reads, target_gas, encode_u256(claimable.into()));
+			},
+			_ => return Err(ExitError::Other("unknown crowdloan selector".into())),
+		};
+
+		dispatch::<Runtime>(CROWDLOAN_PRECOMPILE, call.into(), target_gas, context)
+	}
+}
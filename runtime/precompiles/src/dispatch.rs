@@ -0,0 +1,63 @@
+//! Lets EVM accounts dispatch the runtime calls the runtime allows.
+//!
+//! The input is a SCALE-encoded runtime `Call`, dispatched from the substrate account the caller
+//! maps to. Unlike frontier's `Dispatch`, only calls named in the runtime's `DispatchAllowlist` go
+//! through, and the gas equivalent of the call's weight is charged before it runs. Like the other
+//! dispatching precompiles, it refuses `DELEGATECALL`s and nested dispatches.
+
+use crate::{call::dispatch, DISPATCH_PRECOMPILE};
+use codec::Decode;
+use evm::{executor::PrecompileOutput, Context, ExitError};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::traits::{CallMetadata, GetCallMetadata};
+use frame_support::weights::{DispatchClass, Pays};
+use pallet_evm::Precompile;
+use sp_std::marker::PhantomData;
+
+/// Runtime calls EVM accounts may dispatch. Calls dispatching other calls should not be allowed,
+/// as only the outer call is checked.
+pub trait DispatchAllowlist {
+	/// Pallets, by their name in `construct_runtime!`, all of whose calls are allowed.
+	fn pallets() -> &'static [&'static str];
+	/// Single calls allowed, as `(pallet, call)` names.
+	fn calls() -> &'static [(&'static str, &'static str)];
+
+	/// Whether the call `metadata` describes is allowed.
+	fn allows(metadata: &CallMetadata) -> bool {
+		Self::pallets().contains(&metadata.pallet_name) ||
+			Self::calls().contains(&(metadata.pallet_name, metadata.function_name))
+	}
+}
+
+/// Dispatches the runtime calls `DispatchAllowlist` allows.
+pub struct FilteredDispatch<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for FilteredDispatch<Runtime>
+where
+	Runtime: pallet_evm::Config + DispatchAllowlist,
+	<Runtime as frame_system::Config>::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ GetCallMetadata
+		+ Decode,
+	<<Runtime as frame_system::Config>::Call as Dispatchable>::Origin:
+		From<Option<Runtime::AccountId>>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		let call = <Runtime as frame_system::Config>::Call::decode(&mut &input[..])
+			.map_err(|_| ExitError::Other("failed to decode call".into()))?;
+
+		if !Runtime::allows(&call.get_call_metadata()) {
+			return Err(ExitError::Other("call is not allowed by the dispatch filter".into()));
+		}
+		let info = call.get_dispatch_info();
+		if info.class != DispatchClass::Normal || info.pays_fee != Pays::Yes {
+			return Err(ExitError::Other("only normal calls paying fees can be dispatched".into()));
+		}
+
+		dispatch::<Runtime>(DISPATCH_PRECOMPILE, call, target_gas, context)
+	}
+}
@@ -0,0 +1,247 @@
+//! Exposes the native token as an ERC-20.
+//!
+//! Balances are those the runtime's EVM `Currency` holds for the account an address maps to, so
+//! the token is the same one MetaMask shows as the chain's balance. The currency knows nothing of
+//! approvals, so they are kept in `Approves`, next to the pallets' storage.
+//!
+//! Approvals above what a balance can hold, such as the `type(uint256).max` infinite approval, are
+//! stored as the largest balance, which `transferFrom` never spends.
+//!
+//! The calls moving or approving funds act for the caller, so they fail when a contract reaches
+//! the precompile with `DELEGATECALL` or `CALLCODE`, which would make them act for whoever called
+//! the contract.
+
+use crate::abi::{encode_bool, encode_string, encode_u256, AbiReader};
+use crate::call::ensure_own_context;
+use crate::ERC20_PRECOMPILE;
+use evm::{backend::Log, executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::storage::types::{StorageDoubleMap, ValueQuery};
+use frame_support::traits::{Currency, ExistenceRequirement, Get, StorageInstance};
+use frame_support::Blake2_128Concat;
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{Bounded, UniqueSaturatedInto};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+// Selectors of the ERC-20 functions.
+const SELECTOR_NAME: u32 = 0x06fdde03;
+const SELECTOR_SYMBOL: u32 = 0x95d89b41;
+const SELECTOR_DECIMALS: u32 = 0x313ce567;
+const SELECTOR_TOTAL_SUPPLY: u32 = 0x18160ddd;
+const SELECTOR_BALANCE_OF: u32 = 0x70a08231;
+const SELECTOR_TRANSFER: u32 = 0xa9059cbb;
+const SELECTOR_APPROVE: u32 = 0x095ea7b3;
+const SELECTOR_ALLOWANCE: u32 = 0xdd62ed3e;
+const SELECTOR_TRANSFER_FROM: u32 = 0x23b872dd;
+
+/// `keccak256("Transfer(address,address,uint256)")`
+const TRANSFER_TOPIC: [u8; 32] = [
+	0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+	0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+];
+/// `keccak256("Approval(address,address,uint256)")`
+const APPROVAL_TOPIC: [u8; 32] = [
+	0x8c, 0x5b, 0xe1, 0xe5, 0xeb, 0xec, 0x7d, 0x5b, 0xd1, 0x4f, 0x71, 0x42, 0x7d, 0x1e, 0x84, 0xf3,
+	0xdd, 0x03, 0x14, 0xc0, 0xf7, 0xb2, 0x29, 0x1b, 0x5e, 0x20, 0x0a, 0xc8, 0xc7, 0xc3, 0xb9, 0x25,
+];
+
+// Gas of the `LOG` opcodes emitting the events.
+const LOG_GAS: u64 = 375;
+const LOG_TOPIC_GAS: u64 = 375;
+const LOG_DATA_GAS: u64 = 8;
+
+/// Metadata of the native token. The runtime can't read the chain spec properties, so it repeats
+/// them here.
+pub trait Erc20Metadata {
+	/// The token name, e.g. `PolkaFoundry`.
+	fn name() -> &'static str;
+	/// The token symbol, e.g. `PKF`.
+	fn symbol() -> &'static str;
+	/// The token decimals.
+	fn decimals() -> u8;
+}
+
+pub type BalanceOf<Runtime> = <<Runtime as pallet_evm::Config>::Currency as Currency<
+	<Runtime as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// Storage prefix of `Approves`.
+pub struct ApprovesPrefix;
+impl StorageInstance for ApprovesPrefix {
+	const STORAGE_PREFIX: &'static str = "Approves";
+
+	fn pallet_prefix() -> &'static str {
+		"Erc20Balances"
+	}
+}
+
+/// Amounts owners allowed spenders to transfer on their behalf. \[owner, spender\]
+pub type Approves<Runtime> = StorageDoubleMap<
+	ApprovesPrefix,
+	Blake2_128Concat,
+	H160,
+	Blake2_128Concat,
+	H160,
+	BalanceOf<Runtime>,
+	ValueQuery,
+>;
+
+/// The native token behind an ERC-20 ABI.
+pub struct Erc20Balances<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for Erc20Balances<Runtime>
+where
+	Runtime: pallet_evm::Config + Erc20Metadata,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		let (selector, mut args) = AbiReader::new_with_selector(input)?;
+
+		match selector {
+			SELECTOR_NAME => Self::returned(0, target_gas, encode_string(Runtime::name())),
+			SELECTOR_SYMBOL => Self::returned(0, target_gas, encode_string(Runtime::symbol())),
+			SELECTOR_DECIMALS =>
+				Self::returned(0, target_gas, encode_u256(Runtime::decimals().into())),
+			SELECTOR_TOTAL_SUPPLY => {
+				let supply = Runtime::Currency::total_issuance();
+				Self::returned(1, target_gas, encode_balance::<Runtime>(supply))
+			},
+			SELECTOR_BALANCE_OF => {
+				let who = Runtime::AddressMapping::into_account_id(args.read_address()?);
+				let balance = Runtime::Currency::free_balance(&who);
+				Self::returned(1, target_gas, encode_balance::<Runtime>(balance))
+			},
+			SELECTOR_ALLOWANCE => {
+				let owner = args.read_address()?;
+				let spender = args.read_address()?;
+				let allowance = Approves::<Runtime>::get(owner, spender);
+				Self::returned(1, target_gas, encode_balance::<Runtime>(allowance))
+			},
+			SELECTOR_APPROVE => {
+				ensure_own_context(ERC20_PRECOMPILE, context)?;
+				let spender = args.read_address()?;
+				let amount = args.read_balance_saturating()?;
+				let log = Self::log(context, APPROVAL_TOPIC, context.caller, spender, amount);
+				let cost = Self::charge(0, 1, &log, target_gas)?;
+
+				Approves::<Runtime>::insert(context.caller, spender, amount);
+				Self::succeeded(cost, log)
+			},
+			SELECTOR_TRANSFER => {
+				ensure_own_context(ERC20_PRECOMPILE, context)?;
+				let to = args.read_address()?;
+				let amount = args.read_balance()?;
+				let log = Self::log(context, TRANSFER_TOPIC, context.caller, to, amount);
+				let cost = Self::charge(2, 2, &log, target_gas)?;
+
+				Self::transfer(context.caller, to, amount)?;
+				Self::succeeded(cost, log)
+			},
+			SELECTOR_TRANSFER_FROM => {
+				ensure_own_context(ERC20_PRECOMPILE, context)?;
+				let from = args.read_address()?;
+				let to = args.read_address()?;
+				let amount = args.read_balance()?;
+				let log = Self::log(context, TRANSFER_TOPIC, from, to, amount);
+				let cost = Self::charge(3, 3, &log, target_gas)?;
+
+				if from == context.caller {
+					Self::transfer(from, to, amount)?;
+					return Self::succeeded(cost, log);
+				}
+
+				// Only spend the allowance once the transfer went through, as the precompile's
+				// storage changes are not reverted with the EVM state.
+				let allowance = Approves::<Runtime>::get(from, context.caller);
+				if allowance < amount {
+					return Err(ExitError::Other("amount exceeds allowance".into()));
+				}
+				Self::transfer(from, to, amount)?;
+				if allowance != BalanceOf::<Runtime>::max_value() {
+					Approves::<Runtime>::insert(from, context.caller, allowance - amount);
+				}
+				Self::succeeded(cost, log)
+			},
+			_ => Err(ExitError::Other("unknown erc20 selector".into())),
+		}
+	}
+}
+
+impl<Runtime> Erc20Balances<Runtime>
+where
+	Runtime: pallet_evm::Config + Erc20Metadata,
+{
+	/// Move `amount` between the accounts `from` and `to` map to.
+	fn transfer(from: H160, to: H160, amount: BalanceOf<Runtime>) -> Result<(), ExitError> {
+		Runtime::Currency::transfer(
+			&Runtime::AddressMapping::into_account_id(from),
+			&Runtime::AddressMapping::into_account_id(to),
+			amount,
+			ExistenceRequirement::AllowDeath,
+		).map_err(|e| ExitError::Other(<&'static str>::from(e).into()))
+	}
+
+	/// A `Transfer` or `Approval` log of `amount` between `from` and `to`.
+	fn log(
+		context: &Context,
+		topic: [u8; 32],
+		from: H160,
+		to: H160,
+		amount: BalanceOf<Runtime>,
+	) -> Log {
+		Log {
+			address: context.address,
+			topics: vec![H256::from(topic), H256::from(from), H256::from(to)],
+			data: encode_balance::<Runtime>(amount),
+		}
+	}
+
+	/// The gas of `reads` and `writes` plus emitting `log`, provided the caller supplied enough of
+	/// it.
+	fn charge(reads: u64, writes: u64, log: &Log, target_gas: Option<u64>) -> Result<u64, ExitError> {
+		let weight = <Runtime as frame_system::Config>::DbWeight::get().reads_writes(reads, writes);
+		let cost = Runtime::GasWeightMapping::weight_to_gas(weight)
+			.saturating_add(LOG_GAS)
+			.saturating_add(LOG_TOPIC_GAS * log.topics.len() as u64)
+			.saturating_add(LOG_DATA_GAS * log.data.len() as u64);
+
+		match target_gas {
+			Some(gas) if cost > gas => Err(ExitError::OutOfGas),
+			_ => Ok(cost),
+		}
+	}
+
+	/// Return `output` of a getter doing `reads` storage reads.
+	fn returned(reads: u64, target_gas: Option<u64>, output: Vec<u8>) -> Result<PrecompileOutput, ExitError> {
+		let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(reads);
+		let cost = Runtime::GasWeightMapping::weight_to_gas(weight);
+		if target_gas.map_or(false, |gas| cost > gas) {
+			return Err(ExitError::OutOfGas);
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost,
+			output,
+			logs: Vec::new(),
+		})
+	}
+
+	/// Return `true` from a call that emitted `log`.
+	fn succeeded(cost: u64, log: Log) -> Result<PrecompileOutput, ExitError> {
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost,
+			output: encode_bool(true),
+			logs: vec![log],
+		})
+	}
+}
+
+fn encode_balance<Runtime: pallet_evm::Config>(amount: BalanceOf<Runtime>) -> Vec<u8> {
+	let amount: u128 = amount.unique_saturated_into();
+	encode_u256(U256::from(amount))
+}
@@ -1,17 +1,198 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use evm::{executor::PrecompileOutput, Context, ExitError};
+use pallet_evm::{Precompile, PrecompileSet};
+use pallet_evm_precompile_blake2::Blake2F;
+use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
 use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
-use pallet_evm_precompile_dispatch::Dispatch;
 use pallet_evm_precompile_modexp::Modexp;
+use sp_core::H160;
+use sp_std::marker::PhantomData;
 
-// TODO: Make other precompiles work ...
+pub mod abi;
+mod call;
+#[cfg(feature = "crowdloan")]
+mod crowdloan;
+mod dispatch;
+mod erc20;
+#[cfg(all(test, feature = "staking", feature = "crowdloan"))]
+mod mock;
+#[cfg(feature = "staking")]
+mod staking;
+#[cfg(all(test, feature = "staking", feature = "crowdloan"))]
+mod tests;
+#[cfg(feature = "crowdloan")]
+mod treasury;
+
+#[cfg(feature = "crowdloan")]
+pub use crowdloan::CrowdloanWrapper;
+pub use dispatch::{DispatchAllowlist, FilteredDispatch};
+pub use erc20::{Approves, Erc20Balances, Erc20Metadata};
+#[cfg(feature = "staking")]
+pub use staking::StakingWrapper;
+#[cfg(feature = "crowdloan")]
+pub use treasury::TreasuryWrapper;
+
+// Addresses of the Ethereum precompiles.
 // https://ethereum.stackexchange.com/questions/15479/list-of-pre-compiled-contracts
+pub const ECRECOVER_PRECOMPILE: u64 = 0x01;
+pub const SHA256_PRECOMPILE: u64 = 0x02;
+pub const RIPEMD160_PRECOMPILE: u64 = 0x03;
+pub const IDENTITY_PRECOMPILE: u64 = 0x04;
+pub const MODEXP_PRECOMPILE: u64 = 0x05;
+pub const BN128_ADD_PRECOMPILE: u64 = 0x06;
+pub const BN128_MUL_PRECOMPILE: u64 = 0x07;
+pub const BN128_PAIRING_PRECOMPILE: u64 = 0x08;
+pub const BLAKE2F_PRECOMPILE: u64 = 0x09;
+
+/// Addresses from here on are ours, so they never collide with precompiles Ethereum adds.
+pub const CUSTOM_PRECOMPILES_START: u64 = 0x400;
+pub const DISPATCH_PRECOMPILE: u64 = 0x401;
+pub const ERC20_PRECOMPILE: u64 = 0x402;
+pub const CROWDLOAN_PRECOMPILE: u64 = 0x403;
+pub const TREASURY_PRECOMPILE: u64 = 0x404;
+pub const STAKING_PRECOMPILE: u64 = 0x405;
+
+/// Addresses below this one are reserved for precompiles. Calling one that has no precompile
+/// fails instead of executing an empty account.
+pub const PRECOMPILES_END: u64 = 0x1000;
+
+/// Whether `address` is reserved for a precompile.
+pub fn is_reserved(address: H160) -> bool {
+	address.as_bytes()[..12].iter().all(|byte| *byte == 0) && {
+		let index = address.to_low_u64_be();
+		index > 0 && index < PRECOMPILES_END
+	}
+}
+
+/// Fail the calls to reserved addresses that no precompile answered.
+fn unassigned(address: H160) -> Option<Result<PrecompileOutput, ExitError>> {
+	if is_reserved(address) {
+		Some(Err(ExitError::Other("call to unassigned precompile".into())))
+	} else {
+		None
+	}
+}
+
+/// The Ethereum precompiles, at their canonical addresses.
+pub struct EthereumPrecompiles;
+
+impl PrecompileSet for EthereumPrecompiles {
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Option<Result<PrecompileOutput, ExitError>> {
+		if address.as_bytes()[..12].iter().any(|byte| *byte != 0) {
+			return None;
+		}
+
+		match address.to_low_u64_be() {
+			ECRECOVER_PRECOMPILE => Some(ECRecover::execute(input, target_gas, context)),
+			SHA256_PRECOMPILE => Some(Sha256::execute(input, target_gas, context)),
+			RIPEMD160_PRECOMPILE => Some(Ripemd160::execute(input, target_gas, context)),
+			IDENTITY_PRECOMPILE => Some(Identity::execute(input, target_gas, context)),
+			MODEXP_PRECOMPILE => Some(Modexp::execute(input, target_gas, context)),
+			BN128_ADD_PRECOMPILE => Some(Bn128Add::execute(input, target_gas, context)),
+			BN128_MUL_PRECOMPILE => Some(Bn128Mul::execute(input, target_gas, context)),
+			BN128_PAIRING_PRECOMPILE => Some(Bn128Pairing::execute(input, target_gas, context)),
+			BLAKE2F_PRECOMPILE => Some(Blake2F::execute(input, target_gas, context)),
+			_ => None,
+		}
+	}
+}
+
+/// `EthereumPrecompiles` plus `FilteredDispatch` and `Erc20Balances` in our own range, and the
+/// precompiles of the pallets a runtime runs in `Extra`.
+pub struct PolkafoundryPrecompiles<Runtime, Extra = ()>(PhantomData<(Runtime, Extra)>);
+
+impl<Runtime, Extra> PrecompileSet for PolkafoundryPrecompiles<Runtime, Extra>
+where
+	FilteredDispatch<Runtime>: Precompile,
+	Erc20Balances<Runtime>: Precompile,
+	Extra: PrecompileSet,
+{
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Option<Result<PrecompileOutput, ExitError>> {
+		if address == H160::from_low_u64_be(DISPATCH_PRECOMPILE) {
+			Some(FilteredDispatch::<Runtime>::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(ERC20_PRECOMPILE) {
+			Some(Erc20Balances::<Runtime>::execute(input, target_gas, context))
+		} else {
+			Extra::execute(address, input, target_gas, context)
+				.or_else(|| EthereumPrecompiles::execute(address, input, target_gas, context))
+				.or_else(|| unassigned(address))
+		}
+	}
+}
+
+/// Answers with `A`, and with `B` at the addresses `A` doesn't handle, so a runtime can put the
+/// precompiles of several pallets in `PolkafoundryPrecompiles`.
+pub struct Combine<A, B>(PhantomData<(A, B)>);
+
+impl<A: PrecompileSet, B: PrecompileSet> PrecompileSet for Combine<A, B> {
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Option<Result<PrecompileOutput, ExitError>> {
+		A::execute(address, input, target_gas, context)
+			.or_else(|| B::execute(address, input, target_gas, context))
+	}
+}
+
+/// `StakingWrapper`, for runtimes that run the staking pallet.
+#[cfg(feature = "staking")]
+pub struct StakingPrecompiles<Runtime>(PhantomData<Runtime>);
+
+#[cfg(feature = "staking")]
+impl<Runtime> PrecompileSet for StakingPrecompiles<Runtime>
+where
+	StakingWrapper<Runtime>: Precompile,
+{
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Option<Result<PrecompileOutput, ExitError>> {
+		if address == H160::from_low_u64_be(STAKING_PRECOMPILE) {
+			Some(StakingWrapper::<Runtime>::execute(input, target_gas, context))
+		} else {
+			None
+		}
+	}
+}
+
+/// `CrowdloanWrapper` and `TreasuryWrapper`, for runtimes that run the crowdloan rewards and
+/// treasury pallets.
+#[cfg(feature = "crowdloan")]
+pub struct CrowdloanPrecompiles<Runtime>(PhantomData<Runtime>);
 
-pub type PolkafoundryPrecompiles<Runtime> = (
-	ECRecover,
-	Sha256,
-	Ripemd160,
-	Identity,
-	Modexp,
-	Dispatch<Runtime>,
-);
+#[cfg(feature = "crowdloan")]
+impl<Runtime> PrecompileSet for CrowdloanPrecompiles<Runtime>
+where
+	CrowdloanWrapper<Runtime>: Precompile,
+	TreasuryWrapper<Runtime>: Precompile,
+{
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Option<Result<PrecompileOutput, ExitError>> {
+		if address == H160::from_low_u64_be(CROWDLOAN_PRECOMPILE) {
+			Some(CrowdloanWrapper::<Runtime>::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(TREASURY_PRECOMPILE) {
+			Some(TreasuryWrapper::<Runtime>::execute(input, target_gas, context))
+		} else {
+			None
+		}
+	}
+}
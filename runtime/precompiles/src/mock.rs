@@ -0,0 +1,302 @@
+use crate::{
+	Combine, CrowdloanPrecompiles, DispatchAllowlist, Erc20Metadata, PolkafoundryPrecompiles,
+	StakingPrecompiles,
+};
+use frame_election_provider_support::onchain;
+use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild, PalletId};
+use frame_system::EnsureRoot;
+use pallet_evm::{AddressMapping, EnsureAddressNever, EnsureAddressRoot};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+	Perbill, Permill,
+};
+
+pub type AccountId = u64;
+pub type Balance = u128;
+
+pub const ALICE: AccountId = 1;
+/// Bonds as a collator in the staking tests.
+pub const COLLATOR: AccountId = 3;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type OnSetCode = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+impl pallet_sudo::Config for Test {
+	type Event = Event;
+	type Call = Call;
+}
+
+parameter_types! {
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(1024);
+}
+
+impl onchain::Config for Test {
+	type AccountId = AccountId;
+	type BlockNumber = u64;
+	type BlockWeights = BlockWeights;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
+
+parameter_types! {
+	pub const BlocksPerRound: u32 = 10;
+	pub const MaxNominationsPerCollator: u32 = 2;
+	pub const BondDuration: u32 = 2;
+	pub const MinCollatorStake: u32 = 500;
+	pub const MinNominatorStake: u32 = 100;
+	pub const PayoutDuration: u32 = 2;
+	pub const DesiredTarget: u32 = 2;
+}
+
+impl polkafoundry_staking::Config for Test {
+	const MAX_COLLATORS_PER_NOMINATOR: u32 = 5u32;
+	type Event = Event;
+	type Currency = Balances;
+	type BlocksPerRound = BlocksPerRound;
+	type MaxNominationsPerCollator = MaxNominationsPerCollator;
+	type BondDuration = BondDuration;
+	type MinCollatorStake = MinCollatorStake;
+	type MinNominatorStake = MinNominatorStake;
+	type PayoutDuration = PayoutDuration;
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
+	type DesiredTarget = DesiredTarget;
+	type RewardRemainder = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 1;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 1;
+	pub const MetadataDepositPerByte: Balance = 1;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"Treasury");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: u128 = 1;
+	pub const SpendPeriod: u64 = 100;
+	pub const MaxApprovals: u32 = 100;
+	pub const Burn: Permill = Permill::from_percent(0);
+	pub const MaxPaymentsPerBlock: u32 = 50;
+}
+
+impl pallet_treasury::Config for Test {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type Event = Event;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type MaxApprovals = MaxApprovals;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type MaxPaymentsPerBlock = MaxPaymentsPerBlock;
+	type Assets = Assets;
+	type Erc20 = ();
+	type AddressMapping = TestAddressMapping;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: u128 = 1;
+}
+
+impl pallet_vesting::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ClaimDeadline: u64 = 100;
+	pub const MaxSweepPerCall: u32 = 10;
+	pub const DefaultPayoutMode: pallet_crowdloan_rewards::PayoutMode =
+		pallet_crowdloan_rewards::PayoutMode::Transfer;
+}
+
+impl pallet_crowdloan_rewards::Config for Test {
+	type Event = Event;
+	type RelayChainAccountId = [u8; 32];
+	type AddressMapping = TestAddressMapping;
+	type ChainId = ChainId;
+	type ClaimDeadline = ClaimDeadline;
+	type MaxSweepPerCall = MaxSweepPerCall;
+	type Vesting = Vesting;
+	type DefaultPayoutMode = DefaultPayoutMode;
+	type WeightInfo = ();
+}
+
+/// Maps an Ethereum address to the `u64` built from its last 8 bytes.
+pub struct TestAddressMapping;
+
+impl AddressMapping<AccountId> for TestAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		address.to_low_u64_be()
+	}
+}
+
+parameter_types! {
+	pub const ChainId: u64 = 11;
+	pub BlockGasLimit: U256 = U256::from(u32::max_value());
+}
+
+impl pallet_evm::Config for Test {
+	type FeeCalculator = ();
+	type GasWeightMapping = ();
+	type CallOrigin = EnsureAddressRoot<AccountId>;
+	type WithdrawOrigin = EnsureAddressNever<AccountId>;
+	type AddressMapping = TestAddressMapping;
+	type Currency = Balances;
+	type Event = Event;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Precompiles =
+		PolkafoundryPrecompiles<Self, Combine<StakingPrecompiles<Self>, CrowdloanPrecompiles<Self>>>;
+	type ChainId = ChainId;
+	type BlockGasLimit = BlockGasLimit;
+	type OnChargeTransaction = ();
+}
+
+impl Erc20Metadata for Test {
+	fn name() -> &'static str {
+		"Test"
+	}
+
+	fn symbol() -> &'static str {
+		"TST"
+	}
+
+	fn decimals() -> u8 {
+		12
+	}
+}
+
+impl DispatchAllowlist for Test {
+	fn pallets() -> &'static [&'static str] {
+		&["Balances"]
+	}
+
+	fn calls() -> &'static [(&'static str, &'static str)] {
+		&[("System", "remark")]
+	}
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
+		EVM: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
+		Staking: polkafoundry_staking::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>},
+		Crowdloan: pallet_crowdloan_rewards::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+/// Externalities in which `ALICE` and `COLLATOR` hold some funds and `ALICE` is the sudo key.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 1_000_000), (COLLATOR, 1_000_000)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	pallet_sudo::GenesisConfig::<Test> { key: ALICE }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	<polkafoundry_staking::GenesisConfig<Test> as GenesisBuild<Test>>::assimilate_storage(
+		&polkafoundry_staking::GenesisConfig { stakers: vec![] },
+		&mut storage,
+	)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::from(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,112 @@
+//! Lets EVM accounts bond and nominate through the staking pallet.
+//!
+//! The caller is mapped to its substrate account with the runtime's `AddressMapping`, so a MetaMask
+//! user stakes from the same account its EVM balance lives in. Collators are referred to by their
+//! 32-byte account id, since substrate accounts have no EVM address to map from. The Solidity
+//! interface is `tests/contracts/StakingInterface.sol`.
+
+use crate::abi::{encode_bool, encode_u256, AbiReader};
+use crate::call::{dispatch, view};
+use crate::STAKING_PRECOMPILE;
+use codec::Decode;
+use evm::{executor::PrecompileOutput, Context, ExitError};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::traits::Get;
+use pallet_evm::{AddressMapping, Precompile};
+use polkafoundry_staking::Call as StakingCall;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::marker::PhantomData;
+
+// Selectors of the functions in `StakingInterface.sol`.
+const SELECTOR_BOND: u32 = 0x9940686e;
+const SELECTOR_BOND_EXTRA: u32 = 0xc0f07e6d;
+const SELECTOR_BOND_LESS: u32 = 0x9b5572dc;
+const SELECTOR_NOMINATE: u32 = 0x38e04dba;
+const SELECTOR_NOMINATE_EXTRA: u32 = 0x217c02ed;
+const SELECTOR_NOMINATE_LESS: u32 = 0x581d4e76;
+const SELECTOR_LEAVE: u32 = 0xd66d9e19;
+const SELECTOR_LEAVE_NOMINATION: u32 = 0xe3cb5ea7;
+const SELECTOR_IS_COLLATOR: u32 = 0x5eeb39b0;
+const SELECTOR_NOMINATION_AMOUNT: u32 = 0x92598ae2;
+const SELECTOR_CURRENT_ROUND: u32 = 0x319c068c;
+const SELECTOR_MIN_NOMINATION: u32 = 0xc9f593b2;
+
+/// Wraps the calls and storage of `polkafoundry_staking` in a Solidity ABI.
+pub struct StakingWrapper<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for StakingWrapper<Runtime>
+where
+	Runtime: polkafoundry_staking::Config + pallet_evm::Config,
+	<Runtime as frame_system::Config>::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<StakingCall<Runtime>>,
+	<<Runtime as frame_system::Config>::Call as Dispatchable>::Origin:
+		From<Option<Runtime::AccountId>>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		let (selector, mut args) = AbiReader::new_with_selector(input)?;
+
+		let call = match selector {
+			SELECTOR_BOND => StakingCall::<Runtime>::bond(args.read_balance()?),
+			SELECTOR_BOND_EXTRA =>
+				StakingCall::<Runtime>::bond_extra(args.read_balance()?),
+			SELECTOR_BOND_LESS =>
+				StakingCall::<Runtime>::bond_less(args.read_balance()?),
+			SELECTOR_NOMINATE => StakingCall::<Runtime>::nominate(
+				read_account::<Runtime>(&mut args)?,
+				args.read_balance()?,
+			),
+			SELECTOR_NOMINATE_EXTRA => StakingCall::<Runtime>::nominate_extra(
+				read_account::<Runtime>(&mut args)?,
+				args.read_balance()?,
+			),
+			SELECTOR_NOMINATE_LESS => StakingCall::<Runtime>::nominate_less(
+				read_account::<Runtime>(&mut args)?,
+				args.read_balance()?,
+			),
+			SELECTOR_LEAVE => StakingCall::<Runtime>::collator_unbond(),
+			SELECTOR_LEAVE_NOMINATION => StakingCall::<Runtime>::nominator_leave_collator(
+				read_account::<Runtime>(&mut args)?,
+			),
+			SELECTOR_IS_COLLATOR => {
+				let collator = read_account::<Runtime>(&mut args)?;
+				let is_collator = polkafoundry_staking::Pallet::<Runtime>::collators(&collator).is_some();
+				return view::<Runtime>(1, target_gas, encode_bool(is_collator));
+			},
+			SELECTOR_NOMINATION_AMOUNT => {
+				let nominator = Runtime::AddressMapping::into_account_id(args.read_address()?);
+				let collator = read_account::<Runtime>(&mut args)?;
+				let amount: u128 = polkafoundry_staking::Pallet::<Runtime>::nominators(&nominator)
+					.and_then(|nominator| {
+						nominator.nominations.into_iter().find(|bond| bond.owner == collator)
+					})
+					.map_or(0, |bond| bond.amount.unique_saturated_into());
+				return view::<Runtime>(1, target_gas, encode_u256(amount.into()));
+			},
+			SELECTOR_CURRENT_ROUND => {
+				let round = polkafoundry_staking::Pallet::<Runtime>::current_round().index();
+				return view::<Runtime>(1, target_gas, encode_u256(round.into()));
+			},
+			SELECTOR_MIN_NOMINATION => {
+				let min: u128 = Runtime::MinNominatorStake::get().unique_saturated_into();
+				return view::<Runtime>(0, target_gas, encode_u256(min.into()));
+			},
+			_ => return Err(ExitError::Other("unknown staking selector".into())),
+		};
+
+		dispatch::<Runtime>(STAKING_PRECOMPILE, call.into(), target_gas, context)
+	}
+}
+
+/// Read a `bytes32` account id.
+fn read_account<Runtime: frame_system::Config>(
+	args: &mut AbiReader,
+) -> Result<Runtime::AccountId, ExitError> {
+	let id = args.read_bytes32()?;
+	Runtime::AccountId::decode(&mut id.as_bytes())
+		.map_err(|_| ExitError::Other("invalid account id".into()))
+}
@@ -0,0 +1,906 @@
+//! Conformance vectors for the Ethereum precompiles, the reserved address range, ABI decoding,
+//! the dispatch filter, the native ERC-20 and the staking, crowdloan and treasury wrappers.
+
+use crate::mock::{
+	new_test_ext, AccountId, Balances, Call, Crowdloan, Origin, Staking, System, Test, Treasury, ALICE, COLLATOR,
+	EVM,
+};
+use crate::{
+	abi::{encode_bool, encode_u256, AbiReader}, call::non_reentrant, is_reserved, Combine,
+	Erc20Balances, EthereumPrecompiles, FilteredDispatch, PolkafoundryPrecompiles, StakingPrecompiles,
+	BLAKE2F_PRECOMPILE, BN128_ADD_PRECOMPILE, BN128_MUL_PRECOMPILE, BN128_PAIRING_PRECOMPILE, CROWDLOAN_PRECOMPILE, DISPATCH_PRECOMPILE,
+	ECRECOVER_PRECOMPILE, ERC20_PRECOMPILE, IDENTITY_PRECOMPILE, MODEXP_PRECOMPILE, PRECOMPILES_END,
+	RIPEMD160_PRECOMPILE, SHA256_PRECOMPILE, STAKING_PRECOMPILE, TREASURY_PRECOMPILE,
+};
+use codec::Encode;
+use evm::{backend::Log, executor::PrecompileOutput, Context, ExitError};
+use frame_support::{assert_ok, weights::GetDispatchInfo};
+use sp_core::{sr25519, Pair};
+use sp_runtime::{MultiSignature, Perbill};
+use pallet_evm::{Precompile, PrecompileSet};
+use rustc_hex::FromHex;
+use sp_core::{H160, H256, U256};
+
+fn bytes(hex: &str) -> Vec<u8> {
+	hex.from_hex().expect("test vectors are valid hex")
+}
+
+/// Run `input` through the Ethereum precompile at `address`.
+fn call(address: u64, input: &[u8]) -> Result<Vec<u8>, ExitError> {
+	let context = Context {
+		address: H160::from_low_u64_be(address),
+		caller: H160::repeat_byte(1),
+		apparent_value: U256::zero(),
+	};
+	EthereumPrecompiles::execute(H160::from_low_u64_be(address), input, None, &context)
+		.expect("a precompile is assigned to the address")
+		.map(|output| output.output)
+}
+
+#[test]
+fn ecrecover_recovers_the_signer() {
+	// keccak256("PolkaFoundry") signed by key 0x1234567890abcdef...
+	let input = bytes(concat!(
+		"739eaf6f65858610483cf08e3f49aeaa14f845c26ae271821f26fc75b87efc52",
+		"000000000000000000000000000000000000000000000000000000000000001c",
+		"8bf5447ae65c5ebbeb7e474cf4e8a5c255dbfd33763d93535bfb4d970de72fc4",
+		"34daab4bff8ec304e93c2f4efb1b82bc783bb566b8b1a5615ca5ba38e7a3fd78",
+	));
+
+	assert_eq!(
+		call(ECRECOVER_PRECOMPILE, &input),
+		Ok(bytes("0000000000000000000000001be31a94361a391bbafb2a4ccd704f57dc04d4bb")),
+	);
+}
+
+#[test]
+fn sha256_hashes_the_input() {
+	assert_eq!(
+		call(SHA256_PRECOMPILE, b"Hello world!"),
+		Ok(bytes("c0535e4be2b79ffd93291305436bf889314e4a3faec05ecffcbb7df31ad9e51a")),
+	);
+}
+
+#[test]
+fn ripemd160_hashes_the_input() {
+	assert_eq!(
+		call(RIPEMD160_PRECOMPILE, b"Hello world!"),
+		Ok(bytes("0000000000000000000000007f772647d88750add82d8e1a7a3e5c0902a346a3")),
+	);
+}
+
+#[test]
+fn identity_returns_the_input() {
+	assert_eq!(call(IDENTITY_PRECOMPILE, b"Hello world!"), Ok(b"Hello world!".to_vec()));
+}
+
+#[test]
+fn modexp_follows_eip198() {
+	// 3 ^ (p - 1) mod p, for the secp256k1 field prime p.
+	let input = bytes(concat!(
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"03fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc",
+		"2efffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc",
+		"2f",
+	));
+
+	assert_eq!(
+		call(MODEXP_PRECOMPILE, &input),
+		Ok(bytes("0000000000000000000000000000000000000000000000000000000000000001")),
+	);
+}
+
+#[test]
+fn bn128_add_adds_points() {
+	// G + 2G
+	let input = bytes(concat!(
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000000000000000000000000000000000000000000002",
+		"030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3",
+		"15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+	));
+
+	assert_eq!(
+		call(BN128_ADD_PRECOMPILE, &input),
+		Ok(bytes(concat!(
+			"0769bf9ac56bea3ff40232bcb1b6bd159315d84715b8e679f2d355961915abf0",
+			"2ab799bee0489429554fdb7c8d086475319e63b40b9c5b57cdf1ff3dd9fe2261",
+		))),
+	);
+}
+
+#[test]
+fn bn128_add_rejects_points_off_the_curve() {
+	let mut input = vec![0u8; 128];
+	input[31] = 1;
+	input[63] = 3;
+
+	assert!(call(BN128_ADD_PRECOMPILE, &input).is_err());
+}
+
+#[test]
+fn bn128_mul_multiplies_points() {
+	// 2G * 0x0123456789abcdef
+	let input = bytes(concat!(
+		"030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3",
+		"15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+		"0000000000000000000000000000000000000000000000000123456789abcdef",
+	));
+
+	assert_eq!(
+		call(BN128_MUL_PRECOMPILE, &input),
+		Ok(bytes(concat!(
+			"0d93c9ef16882a665e74890805cae7c485d4e707f66112bd1d01ab7a187347d2",
+			"174e1511aea8d3447f10c033e90b999ae3084f27f3ebba7f38345b7966833cbe",
+		))),
+	);
+}
+
+#[test]
+fn bn128_pairing_checks_pairings() {
+	// e(G1, G2) * e(-G1, G2) == 1
+	let input = bytes(concat!(
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000000000000000000000000000000000000000000002",
+		"198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+		"1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+		"090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+		"12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45",
+		"198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+		"1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+		"090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+		"12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+	));
+
+	assert_eq!(call(BN128_PAIRING_PRECOMPILE, &input), Ok(bytes("0000000000000000000000000000000000000000000000000000000000000001")));
+}
+
+#[test]
+fn bn128_pairing_of_nothing_holds() {
+	assert_eq!(call(BN128_PAIRING_PRECOMPILE, &[]), Ok(bytes("0000000000000000000000000000000000000000000000000000000000000001")));
+}
+
+#[test]
+fn blake2f_follows_eip152() {
+	// EIP-152 vector 5, the compression of "abc".
+	let input = bytes(concat!(
+		"0000000c48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f",
+		"3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e13",
+		"19cde05b61626300000000000000000000000000000000000000000000000000",
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"000000000300000000000000000000000000000001",
+	));
+
+	assert_eq!(
+		call(BLAKE2F_PRECOMPILE, &input),
+		Ok(bytes(concat!(
+			"ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1",
+			"7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+		))),
+	);
+}
+
+#[test]
+fn blake2f_rejects_malformed_input() {
+	assert!(call(BLAKE2F_PRECOMPILE, &[0u8; 212]).is_err());
+}
+
+#[test]
+fn ethereum_precompiles_ignore_other_addresses() {
+	let context = Context {
+		address: H160::zero(),
+		caller: H160::zero(),
+		apparent_value: U256::zero(),
+	};
+
+	for address in &[H160::zero(), H160::from_low_u64_be(0x0a), H160::repeat_byte(1)] {
+		assert!(EthereumPrecompiles::execute(*address, &[], None, &context).is_none());
+	}
+}
+
+#[test]
+fn reserved_range_covers_all_precompiles() {
+	for address in &[
+		ECRECOVER_PRECOMPILE,
+		BLAKE2F_PRECOMPILE,
+		DISPATCH_PRECOMPILE,
+		CROWDLOAN_PRECOMPILE,
+		TREASURY_PRECOMPILE,
+		STAKING_PRECOMPILE,
+	] {
+		assert!(is_reserved(H160::from_low_u64_be(*address)));
+	}
+	assert!(is_reserved(H160::from_low_u64_be(PRECOMPILES_END - 1)));
+
+	assert!(!is_reserved(H160::zero()));
+	assert!(!is_reserved(H160::from_low_u64_be(PRECOMPILES_END)));
+	let mut address = H160::from_low_u64_be(ECRECOVER_PRECOMPILE);
+	address.0[0] = 1;
+	assert!(!is_reserved(address));
+}
+
+#[test]
+fn abi_reader_reads_dynamic_bytes() {
+	// `associate_account(bytes32,bytes)` with `0x11..11` and three bytes `0xaabbcc`.
+	let input = bytes(concat!(
+		"0baf2f76",
+		"1111111111111111111111111111111111111111111111111111111111111111",
+		"0000000000000000000000000000000000000000000000000000000000000040",
+		"0000000000000000000000000000000000000000000000000000000000000003",
+		"aabbcc0000000000000000000000000000000000000000000000000000000000",
+	));
+
+	let (selector, mut args) = AbiReader::new_with_selector(&input).unwrap();
+	assert_eq!(selector, 0x0baf2f76);
+	assert_eq!(args.read_bytes32().unwrap().as_bytes(), &[0x11; 32][..]);
+	assert_eq!(args.read_bytes().unwrap(), vec![0xaa, 0xbb, 0xcc]);
+}
+
+#[test]
+fn abi_reader_rejects_bytes_out_of_bounds() {
+	// The length claims more bytes than the input holds.
+	let input = bytes(concat!(
+		"0baf2f76",
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"0000000000000000000000000000000000000000000000000000000000000021",
+		"aabbcc0000000000000000000000000000000000000000000000000000000000",
+	));
+	let (_, mut args) = AbiReader::new_with_selector(&input).unwrap();
+	assert!(args.read_bytes().is_err());
+
+	// The offset points past the input.
+	let input = bytes(concat!(
+		"0baf2f76",
+		"0000000000000000000000000000000000000000000000000000000000000100",
+	));
+	let (_, mut args) = AbiReader::new_with_selector(&input).unwrap();
+	assert!(args.read_bytes().is_err());
+}
+
+#[test]
+fn abi_reader_rejects_oversized_u32() {
+	let input = bytes(concat!(
+		"04951891",
+		"0000000000000000000000000000000000000000000000000000000100000000",
+	));
+	let (_, mut args) = AbiReader::new_with_selector(&input).unwrap();
+	assert!(args.read_u32().is_err());
+}
+
+/// Run `input` through the dispatch precompile as `ALICE`, returning the gas charged.
+fn dispatch_input(input: &[u8], target_gas: Option<u64>) -> Result<u64, ExitError> {
+	let context = Context {
+		address: H160::from_low_u64_be(DISPATCH_PRECOMPILE),
+		caller: H160::from_low_u64_be(ALICE),
+		apparent_value: U256::zero(),
+	};
+	FilteredDispatch::<Test>::execute(input, target_gas, &context).map(|output| output.cost)
+}
+
+fn dispatch(call: Call, target_gas: Option<u64>) -> Result<u64, ExitError> {
+	dispatch_input(&call.encode(), target_gas)
+}
+
+fn transfer(value: u128) -> Call {
+	Call::Balances(pallet_balances::Call::transfer(2, value))
+}
+
+#[test]
+fn dispatch_runs_allowed_calls() {
+	new_test_ext().execute_with(|| {
+		let weight = transfer(100).get_dispatch_info().weight;
+
+		assert_eq!(dispatch(transfer(100), None), Ok(weight));
+		assert_eq!(Balances::free_balance(2), 100);
+
+		let remark = Call::System(frame_system::Call::remark(vec![1, 2, 3]));
+		assert!(dispatch(remark, None).is_ok());
+	});
+}
+
+#[test]
+fn dispatch_rejects_calls_outside_the_allowlist() {
+	new_test_ext().execute_with(|| {
+		let denied = ExitError::Other("call is not allowed by the dispatch filter".into());
+
+		let sudo = Call::Sudo(pallet_sudo::Call::sudo(Box::new(transfer(100))));
+		assert_eq!(dispatch(sudo, None), Err(denied.clone()));
+		let set_storage = Call::System(frame_system::Call::set_storage(vec![(vec![1], vec![2])]));
+		assert_eq!(dispatch(set_storage, None), Err(denied));
+
+		assert_eq!(Balances::free_balance(2), 0);
+	});
+}
+
+#[test]
+fn dispatch_rejects_undecodable_calls() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			dispatch_input(&[0xff, 0xff], None),
+			Err(ExitError::Other("failed to decode call".into())),
+		);
+	});
+}
+
+#[test]
+fn dispatch_charges_the_weight_before_execution() {
+	new_test_ext().execute_with(|| {
+		let weight = transfer(100).get_dispatch_info().weight;
+
+		assert_eq!(dispatch(transfer(100), Some(weight - 1)), Err(ExitError::OutOfGas));
+		assert_eq!(Balances::free_balance(2), 0);
+
+		assert_eq!(dispatch(transfer(100), Some(weight)), Ok(weight));
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn dispatch_refuses_nested_dispatch() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			non_reentrant(|| dispatch(transfer(100), None)),
+			Err(ExitError::Other("nested dispatch is not allowed".into())),
+		);
+		assert_eq!(Balances::free_balance(2), 0);
+
+		// The guard is lifted once the outer dispatch returns.
+		assert!(dispatch(transfer(100), None).is_ok());
+	});
+}
+
+/// The input of `selector` called with `args`.
+fn input(selector: u32, args: &[Vec<u8>]) -> Vec<u8> {
+	selector.to_be_bytes().iter().chain(args.concat().iter()).copied().collect()
+}
+
+/// Deploy a contract forwarding its input to the precompile at `precompile` with `DELEGATECALL`,
+/// and storing in its slot 0 whether that succeeded.
+fn delegating_proxy(precompile: u64) -> H160 {
+	let proxy = H160::repeat_byte(0xdd);
+	let [high, low] = (precompile as u16).to_be_bytes();
+	let code = vec![
+		// CALLDATACOPY(0, 0, CALLDATASIZE)
+		0x36, 0x60, 0x00, 0x60, 0x00, 0x37,
+		// DELEGATECALL(GAS, precompile, 0, CALLDATASIZE, 0, 0)
+		0x60, 0x00, 0x60, 0x00, 0x36, 0x60, 0x00, 0x61, high, low, 0x5a, 0xf4,
+		// SSTORE(0, success)
+		0x60, 0x00, 0x55, 0x00,
+	];
+	pallet_evm::AccountCodes::<Test>::insert(proxy, code);
+	proxy
+}
+
+/// Have `caller` call a contract that delegatecalls `precompile` with `input`, as a malicious
+/// contract would to act for its callers. Returns whether the precompile succeeded.
+fn delegatecall(caller: AccountId, precompile: u64, input: Vec<u8>) -> bool {
+	let proxy = delegating_proxy(precompile);
+	assert_ok!(EVM::call(
+		Origin::root(),
+		H160::from_low_u64_be(caller),
+		proxy,
+		input,
+		U256::zero(),
+		1_000_000_000,
+		U256::zero(),
+		None,
+	));
+	pallet_evm::AccountStorages::<Test>::get(proxy, H256::zero()) == H256::from_low_u64_be(1)
+}
+
+#[test]
+fn dispatch_refuses_delegatecall() {
+	new_test_ext().execute_with(|| {
+		// The proxy reaches precompiles that don't act for the caller...
+		assert!(delegatecall(ALICE, IDENTITY_PRECOMPILE, vec![1, 2, 3]));
+		// ...but can't dispatch from the account of whoever called it.
+		assert!(!delegatecall(ALICE, DISPATCH_PRECOMPILE, transfer(100).encode()));
+		assert_eq!(Balances::free_balance(2), 0);
+
+		let context = Context {
+			address: H160::repeat_byte(0xdd),
+			caller: H160::from_low_u64_be(ALICE),
+			apparent_value: U256::zero(),
+		};
+		assert_eq!(
+			FilteredDispatch::<Test>::execute(&transfer(100).encode(), None, &context).map(|output| output.cost),
+			Err(ExitError::Other("cannot be called with DELEGATECALL or CALLCODE".into())),
+		);
+	});
+}
+
+// Selectors of `StakingInterface.sol`.
+const BOND: u32 = 0x9940686e;
+const NOMINATE: u32 = 0x38e04dba;
+const LEAVE: u32 = 0xd66d9e19;
+const LEAVE_NOMINATION: u32 = 0xe3cb5ea7;
+const IS_COLLATOR: u32 = 0x5eeb39b0;
+const NOMINATION_AMOUNT: u32 = 0x92598ae2;
+const CURRENT_ROUND: u32 = 0x319c068c;
+const MIN_NOMINATION: u32 = 0xc9f593b2;
+
+/// The `bytes32` id of `account`.
+fn account_word(account: AccountId) -> Vec<u8> {
+	let mut word = account.encode();
+	word.resize(32, 0);
+	word
+}
+
+/// The `address` of `account`.
+fn address_word(account: AccountId) -> Vec<u8> {
+	H256::from(H160::from_low_u64_be(account)).as_bytes().to_vec()
+}
+
+/// Call the staking precompile as `caller`, through a set that also holds our other precompiles.
+fn staking(caller: AccountId, selector: u32, args: &[Vec<u8>]) -> Result<PrecompileOutput, ExitError> {
+	let address = H160::from_low_u64_be(STAKING_PRECOMPILE);
+	let context = Context {
+		address,
+		caller: H160::from_low_u64_be(caller),
+		apparent_value: U256::zero(),
+	};
+	PolkafoundryPrecompiles::<Test, StakingPrecompiles<Test>>::execute(address, &input(selector, args), None, &context)
+		.expect("the staking precompile is assigned")
+}
+
+/// Bond `COLLATOR` and make it active right away, instead of waiting for the next round.
+fn active_collator() {
+	assert_ok!(Staking::bond(Origin::signed(COLLATOR), 1_000));
+	polkafoundry_staking::Collators::<Test>::mutate(COLLATOR, |collator| {
+		collator.as_mut().unwrap().status = polkafoundry_staking::StakerStatus::Active;
+	});
+}
+
+#[test]
+fn staking_is_combined_with_other_sets() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: H160::zero(),
+			caller: H160::zero(),
+			apparent_value: U256::zero(),
+		};
+		let input = MIN_NOMINATION.to_be_bytes();
+
+		type Both = Combine<EthereumPrecompiles, StakingPrecompiles<Test>>;
+		assert!(Both::execute(H160::from_low_u64_be(STAKING_PRECOMPILE), &input, None, &context).is_some());
+		assert!(Both::execute(H160::from_low_u64_be(SHA256_PRECOMPILE), &input, None, &context).is_some());
+		assert!(Both::execute(H160::from_low_u64_be(TREASURY_PRECOMPILE), &input, None, &context).is_none());
+
+		// Without the staking set, its address is just an unassigned precompile.
+		assert!(PolkafoundryPrecompiles::<Test>::execute(
+			H160::from_low_u64_be(STAKING_PRECOMPILE),
+			&input,
+			None,
+			&context,
+		)
+		.unwrap()
+		.is_err());
+	});
+}
+
+#[test]
+fn staking_bonds_the_caller() {
+	new_test_ext().execute_with(|| {
+		assert!(staking(ALICE, BOND, &[encode_u256(400.into())]).is_err());
+
+		assert_ok!(staking(ALICE, BOND, &[encode_u256(600.into())]));
+		assert_eq!(Staking::collators(ALICE).unwrap().total, 600);
+		assert_eq!(Balances::reserved_balance(ALICE), 600);
+
+		// Amounts above u128 are rejected instead of being truncated.
+		assert!(staking(COLLATOR, BOND, &[encode_u256(U256::max_value())]).is_err());
+		assert!(Staking::collators(COLLATOR).is_none());
+	});
+}
+
+#[test]
+fn staking_nominates_collators() {
+	new_test_ext().execute_with(|| {
+		active_collator();
+
+		assert_ok!(staking(ALICE, NOMINATE, &[account_word(COLLATOR), encode_u256(200.into())]));
+		let nomination = &Staking::nominators(ALICE).unwrap().nominations[0];
+		assert_eq!((nomination.owner, nomination.amount), (COLLATOR, 200));
+		assert_eq!(Balances::reserved_balance(ALICE), 200);
+
+		// Nominating an account that has not bonded fails.
+		assert!(staking(COLLATOR, NOMINATE, &[account_word(ALICE), encode_u256(200.into())]).is_err());
+	});
+}
+
+#[test]
+fn staking_leaves_candidacy_and_nominations() {
+	new_test_ext().execute_with(|| {
+		active_collator();
+		assert_ok!(staking(ALICE, NOMINATE, &[account_word(COLLATOR), encode_u256(200.into())]));
+
+		assert_ok!(staking(ALICE, LEAVE_NOMINATION, &[account_word(COLLATOR)]));
+		assert!(Staking::nominators(ALICE).unwrap().nominations.is_empty());
+		assert!(Staking::collators(COLLATOR).unwrap().nominations.is_empty());
+		// Only nominations can be left this way.
+		assert!(staking(ALICE, LEAVE_NOMINATION, &[account_word(COLLATOR)]).is_err());
+
+		assert!(staking(ALICE, LEAVE, &[]).is_err());
+		assert_ok!(staking(COLLATOR, LEAVE, &[]));
+		assert!(Staking::collators(COLLATOR).is_none());
+	});
+}
+
+#[test]
+fn staking_refuses_delegatecall() {
+	new_test_ext().execute_with(|| {
+		let bond = input(BOND, &[encode_u256(600.into())]);
+		assert!(!delegatecall(ALICE, STAKING_PRECOMPILE, bond));
+		assert!(Staking::collators(ALICE).is_none());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn staking_is_not_reentrant() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			non_reentrant(|| staking(ALICE, BOND, &[encode_u256(600.into())]).map(|output| output.output)),
+			Err(ExitError::Other("nested dispatch is not allowed".into())),
+		);
+		assert!(Staking::collators(ALICE).is_none());
+	});
+}
+
+#[test]
+fn staking_getters_read_the_pallet() {
+	new_test_ext().execute_with(|| {
+		let output = |selector, args: &[Vec<u8>]| staking(ALICE, selector, args).unwrap().output;
+
+		assert_eq!(output(IS_COLLATOR, &[account_word(COLLATOR)]), encode_bool(false));
+		active_collator();
+		assert_eq!(output(IS_COLLATOR, &[account_word(COLLATOR)]), encode_bool(true));
+
+		let nomination_amount = || output(NOMINATION_AMOUNT, &[address_word(ALICE), account_word(COLLATOR)]);
+		assert_eq!(nomination_amount(), encode_u256(0.into()));
+		assert_ok!(Staking::nominate(Origin::signed(ALICE), COLLATOR, 300));
+		assert_eq!(nomination_amount(), encode_u256(300.into()));
+
+		assert_eq!(output(CURRENT_ROUND, &[]), encode_u256(1.into()));
+		assert_eq!(output(MIN_NOMINATION, &[]), encode_u256(100.into()));
+	});
+}
+
+// Selectors of the ERC-20 functions.
+const BALANCE_OF: u32 = 0x70a08231;
+const TRANSFER: u32 = 0xa9059cbb;
+const APPROVE: u32 = 0x095ea7b3;
+const ALLOWANCE: u32 = 0xdd62ed3e;
+const TRANSFER_FROM: u32 = 0x23b872dd;
+
+// Accounts without funds, apart from what the tests move to them.
+const SPENDER: AccountId = 4;
+const RECIPIENT: AccountId = 5;
+
+/// Call the ERC-20 precompile as `caller`.
+fn erc20(caller: AccountId, selector: u32, args: &[Vec<u8>]) -> Result<PrecompileOutput, ExitError> {
+	let context = Context {
+		address: H160::from_low_u64_be(ERC20_PRECOMPILE),
+		caller: H160::from_low_u64_be(caller),
+		apparent_value: U256::zero(),
+	};
+	Erc20Balances::<Test>::execute(&input(selector, args), None, &context)
+}
+
+/// The log of a `Transfer` or `Approval` event with `topic`.
+fn erc20_log(topic: &str, from: AccountId, to: AccountId, amount: U256) -> Log {
+	Log {
+		address: H160::from_low_u64_be(ERC20_PRECOMPILE),
+		topics: vec![
+			H256::from_slice(&bytes(topic)),
+			H256::from(H160::from_low_u64_be(from)),
+			H256::from(H160::from_low_u64_be(to)),
+		],
+		data: encode_u256(amount),
+	}
+}
+
+fn transfer_log(from: AccountId, to: AccountId, amount: U256) -> Log {
+	erc20_log("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef", from, to, amount)
+}
+
+fn approval_log(owner: AccountId, spender: AccountId, amount: U256) -> Log {
+	erc20_log("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291b5e200ac8c7c3b925", owner, spender, amount)
+}
+
+fn allowance(owner: AccountId, spender: AccountId) -> Vec<u8> {
+	erc20(ALICE, ALLOWANCE, &[address_word(owner), address_word(spender)]).unwrap().output
+}
+
+#[test]
+fn erc20_transfers_the_native_token() {
+	new_test_ext().execute_with(|| {
+		let output = erc20(ALICE, TRANSFER, &[address_word(RECIPIENT), encode_u256(100.into())]).unwrap();
+		assert_eq!(output.output, encode_bool(true));
+		assert_eq!(output.logs, vec![transfer_log(ALICE, RECIPIENT, 100.into())]);
+		assert_eq!(Balances::free_balance(RECIPIENT), 100);
+		assert_eq!(
+			erc20(ALICE, BALANCE_OF, &[address_word(RECIPIENT)]).unwrap().output,
+			encode_u256(100.into()),
+		);
+
+		// Transferring more than the balance fails without moving funds.
+		assert!(erc20(RECIPIENT, TRANSFER, &[address_word(ALICE), encode_u256(101.into())]).is_err());
+		assert_eq!(Balances::free_balance(RECIPIENT), 100);
+	});
+}
+
+#[test]
+fn erc20_approves_spenders() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(0.into()));
+
+		let output = erc20(ALICE, APPROVE, &[address_word(SPENDER), encode_u256(500.into())]).unwrap();
+		assert_eq!(output.output, encode_bool(true));
+		assert_eq!(output.logs, vec![approval_log(ALICE, SPENDER, 500.into())]);
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(500.into()));
+		// Approvals are per owner and spender.
+		assert_eq!(allowance(SPENDER, ALICE), encode_u256(0.into()));
+	});
+}
+
+#[test]
+fn erc20_infinite_approval_saturates() {
+	new_test_ext().execute_with(|| {
+		let output = erc20(ALICE, APPROVE, &[address_word(SPENDER), encode_u256(U256::max_value())]).unwrap();
+		let max = U256::from(u128::max_value());
+		assert_eq!(output.logs, vec![approval_log(ALICE, SPENDER, max)]);
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(max));
+
+		// An unlimited allowance is never spent.
+		let args = [address_word(ALICE), address_word(RECIPIENT), encode_u256(300.into())];
+		assert_ok!(erc20(SPENDER, TRANSFER_FROM, &args));
+		assert_eq!(Balances::free_balance(RECIPIENT), 300);
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(max));
+	});
+}
+
+#[test]
+fn erc20_transfer_from_spends_the_allowance() {
+	new_test_ext().execute_with(|| {
+		let args = |amount: u64| [address_word(ALICE), address_word(RECIPIENT), encode_u256(amount.into())];
+
+		assert_eq!(
+			erc20(SPENDER, TRANSFER_FROM, &args(100)).map(|output| output.output),
+			Err(ExitError::Other("amount exceeds allowance".into())),
+		);
+
+		assert_ok!(erc20(ALICE, APPROVE, &[address_word(SPENDER), encode_u256(500.into())]));
+		let output = erc20(SPENDER, TRANSFER_FROM, &args(200)).unwrap();
+		assert_eq!(output.output, encode_bool(true));
+		assert_eq!(output.logs, vec![transfer_log(ALICE, RECIPIENT, 200.into())]);
+		assert_eq!(Balances::free_balance(RECIPIENT), 200);
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(300.into()));
+
+		assert!(erc20(SPENDER, TRANSFER_FROM, &args(301)).is_err());
+		assert_eq!(Balances::free_balance(RECIPIENT), 200);
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(300.into()));
+
+		// The owner moves its own funds without an allowance.
+		assert_ok!(erc20(ALICE, TRANSFER_FROM, &args(50)));
+		assert_eq!(Balances::free_balance(RECIPIENT), 250);
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(300.into()));
+	});
+}
+
+#[test]
+fn erc20_refuses_delegatecall() {
+	new_test_ext().execute_with(|| {
+		// A contract ALICE calls can't move ALICE's funds...
+		let transfer = input(TRANSFER, &[address_word(RECIPIENT), encode_u256(100.into())]);
+		assert!(!delegatecall(ALICE, ERC20_PRECOMPILE, transfer));
+		assert_eq!(Balances::free_balance(RECIPIENT), 0);
+
+		// ...nor approve a spender of them.
+		let approve = input(APPROVE, &[address_word(SPENDER), encode_u256(500.into())]);
+		assert!(!delegatecall(ALICE, ERC20_PRECOMPILE, approve));
+		assert_eq!(allowance(ALICE, SPENDER), encode_u256(0.into()));
+
+		let args = [address_word(ALICE), address_word(RECIPIENT), encode_u256(100.into())];
+		assert!(!delegatecall(ALICE, ERC20_PRECOMPILE, input(TRANSFER_FROM, &args)));
+		assert_eq!(Balances::free_balance(RECIPIENT), 0);
+
+		// Getters don't act for anyone, so they can still be delegatecalled.
+		assert!(delegatecall(ALICE, ERC20_PRECOMPILE, input(BALANCE_OF, &[address_word(ALICE)])));
+	});
+}
+
+/// Call the precompile at `address` as `caller`, through the mock runtime's precompiles.
+fn execute(
+	address: u64,
+	caller: AccountId,
+	selector: u32,
+	args: &[Vec<u8>],
+) -> Result<PrecompileOutput, ExitError> {
+	let context = Context {
+		address: H160::from_low_u64_be(address),
+		caller: H160::from_low_u64_be(caller),
+		apparent_value: U256::zero(),
+	};
+	<Test as pallet_evm::Config>::Precompiles::execute(context.address, &input(selector, args), None, &context)
+		.expect("the precompile is assigned")
+}
+
+// Selectors of `TreasuryInterface.sol`.
+const DONATE: u32 = 0xf14faf6f;
+const POT: u32 = 0x4ba2363a;
+
+fn treasury(caller: AccountId, selector: u32, args: &[Vec<u8>]) -> Result<PrecompileOutput, ExitError> {
+	execute(TREASURY_PRECOMPILE, caller, selector, args)
+}
+
+#[test]
+fn treasury_takes_donations() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(treasury(ALICE, DONATE, &[encode_u256(1_000.into())]));
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 1_000);
+		assert_eq!(Balances::free_balance(ALICE), 999_000);
+
+		// The pot leaves out the existential deposit.
+		assert_eq!(treasury(ALICE, POT, &[]).unwrap().output, encode_u256(999.into()));
+	});
+}
+
+#[test]
+fn treasury_rejects_bad_donations() {
+	new_test_ext().execute_with(|| {
+		// Below the minimum balance.
+		assert!(treasury(ALICE, DONATE, &[encode_u256(0.into())]).is_err());
+		// More than the caller holds.
+		assert!(treasury(ALICE, DONATE, &[encode_u256(2_000_000.into())]).is_err());
+		// Above what a balance can hold.
+		assert!(treasury(ALICE, DONATE, &[encode_u256(U256::max_value())]).is_err());
+		// Missing the amount.
+		assert!(treasury(ALICE, DONATE, &[]).is_err());
+		assert_eq!(
+			treasury(ALICE, 0xdeadbeef, &[]).map(|output| output.output),
+			Err(ExitError::Other("unknown treasury selector".into())),
+		);
+
+		assert_eq!(Balances::free_balance(ALICE), 1_000_000);
+		assert_eq!(treasury(ALICE, POT, &[]).unwrap().output, encode_u256(0.into()));
+	});
+}
+
+#[test]
+fn treasury_refuses_delegatecall() {
+	new_test_ext().execute_with(|| {
+		assert!(!delegatecall(ALICE, TREASURY_PRECOMPILE, input(DONATE, &[encode_u256(1_000.into())])));
+		assert_eq!(Balances::free_balance(ALICE), 1_000_000);
+
+		assert!(delegatecall(ALICE, TREASURY_PRECOMPILE, input(POT, &[])));
+	});
+}
+
+// Selectors of `CrowdloanInterface.sol`.
+const ASSOCIATE_ACCOUNT: u32 = 0x0baf2f76;
+const CLAIM: u32 = 0x04951891;
+const CLAIMABLE: u32 = 0x402914f5;
+
+fn crowdloan(caller: AccountId, selector: u32, args: &[Vec<u8>]) -> Result<PrecompileOutput, ExitError> {
+	execute(CROWDLOAN_PRECOMPILE, caller, selector, args)
+}
+
+/// The relay chain account contributing to the campaign.
+fn relay_pair() -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[7; 32])
+}
+
+/// The arguments of `associate_account` associating the relay account with `who`, the proof
+/// signed by `signer`.
+fn association(who: AccountId, signer: &sr25519::Pair) -> Vec<Vec<u8>> {
+	let proof: MultiSignature = signer.sign(&Crowdloan::native_association_message(&who)).into();
+	let proof = proof.encode();
+	let mut padded = proof.clone();
+	padded.resize((proof.len() + 31) / 32 * 32, 0);
+
+	vec![relay_pair().public().0.to_vec(), encode_u256(64.into()), encode_u256(proof.len().into()), padded]
+}
+
+/// Start campaign 0, paying the relay account 1_000 by block 10 out of a funded treasury.
+fn campaign() {
+	assert_ok!(Crowdloan::initialize_reward(
+		Origin::root(),
+		vec![(relay_pair().public().0, 100)],
+		10,
+		10,
+		Perbill::zero(),
+		None,
+		None,
+		None,
+	));
+	assert_ok!(Treasury::donate(Origin::signed(COLLATOR), 10_000));
+}
+
+#[test]
+fn crowdloan_associates_the_caller() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(crowdloan(ALICE, ASSOCIATE_ACCOUNT, &association(ALICE, &relay_pair())));
+		assert_eq!(Crowdloan::associated_account(ALICE), Some(relay_pair().public().0));
+
+		// Only once.
+		assert!(crowdloan(ALICE, ASSOCIATE_ACCOUNT, &association(ALICE, &relay_pair())).is_err());
+	});
+}
+
+#[test]
+fn crowdloan_rejects_bad_proofs() {
+	new_test_ext().execute_with(|| {
+		// Signed by another account.
+		let other = sr25519::Pair::from_seed(&[8; 32]);
+		assert!(crowdloan(ALICE, ASSOCIATE_ACCOUNT, &association(ALICE, &other)).is_err());
+		// Signed for another account.
+		assert!(crowdloan(ALICE, ASSOCIATE_ACCOUNT, &association(COLLATOR, &relay_pair())).is_err());
+
+		// Not a `MultiSignature` at all.
+		let mut args = association(ALICE, &relay_pair());
+		args[2] = encode_u256(3.into());
+		args[3] = vec![0xff; 32];
+		assert_eq!(
+			crowdloan(ALICE, ASSOCIATE_ACCOUNT, &args).map(|output| output.output),
+			Err(ExitError::Other("invalid proof".into())),
+		);
+
+		assert_eq!(Crowdloan::associated_account(ALICE), None);
+	});
+}
+
+#[test]
+fn crowdloan_claims_rewards() {
+	new_test_ext().execute_with(|| {
+		campaign();
+		System::set_block_number(10);
+		let claimable = || crowdloan(ALICE, CLAIMABLE, &[address_word(ALICE)]).unwrap().output;
+
+		// Nothing is claimable before the association...
+		assert_eq!(claimable(), encode_u256(0.into()));
+		assert!(crowdloan(ALICE, CLAIM, &[encode_u256(0.into())]).is_err());
+
+		// ...and the whole reward once the campaign ended.
+		assert_ok!(crowdloan(ALICE, ASSOCIATE_ACCOUNT, &association(ALICE, &relay_pair())));
+		assert_eq!(claimable(), encode_u256(1_000.into()));
+
+		assert_ok!(crowdloan(ALICE, CLAIM, &[encode_u256(0.into())]));
+		assert_eq!(Balances::free_balance(ALICE), 1_001_000);
+		assert_eq!(claimable(), encode_u256(0.into()));
+
+		// The reward is paid once, and there is no other campaign.
+		assert!(crowdloan(ALICE, CLAIM, &[encode_u256(0.into())]).is_err());
+		assert!(crowdloan(ALICE, CLAIM, &[encode_u256(1.into())]).is_err());
+		assert_eq!(
+			crowdloan(ALICE, 0xdeadbeef, &[]).map(|output| output.output),
+			Err(ExitError::Other("unknown crowdloan selector".into())),
+		);
+	});
+}
+
+#[test]
+fn crowdloan_refuses_delegatecall() {
+	new_test_ext().execute_with(|| {
+		campaign();
+		System::set_block_number(10);
+
+		// A contract can't associate the relay account with whoever calls it...
+		let associate = input(ASSOCIATE_ACCOUNT, &association(ALICE, &relay_pair()));
+		assert!(!delegatecall(ALICE, CROWDLOAN_PRECOMPILE, associate));
+		assert_eq!(Crowdloan::associated_account(ALICE), None);
+
+		// ...nor claim its rewards.
+		assert_ok!(crowdloan(ALICE, ASSOCIATE_ACCOUNT, &association(ALICE, &relay_pair())));
+		assert!(!delegatecall(ALICE, CROWDLOAN_PRECOMPILE, input(CLAIM, &[encode_u256(0.into())])));
+		assert_eq!(Balances::free_balance(ALICE), 1_000_000);
+
+		assert!(delegatecall(ALICE, CROWDLOAN_PRECOMPILE, input(CLAIMABLE, &[address_word(ALICE)])));
+	});
+}
@@ -0,0 +1,50 @@
+//! Lets EVM accounts donate to the treasury.
+//!
+//! Donations are paid from the substrate account the caller maps to with the runtime's
+//! `AddressMapping`. The Solidity interface is `tests/contracts/TreasuryInterface.sol`.
+
+use crate::abi::{encode_u256, AbiReader};
+use crate::call::{dispatch, view};
+use crate::TREASURY_PRECOMPILE;
+use evm::{executor::PrecompileOutput, Context, ExitError};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::Precompile;
+use pallet_treasury::Call as TreasuryCall;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::marker::PhantomData;
+
+// Selectors of the functions in `TreasuryInterface.sol`.
+const SELECTOR_DONATE: u32 = 0xf14faf6f;
+const SELECTOR_POT: u32 = 0x4ba2363a;
+
+/// Wraps the calls and storage of `pallet_treasury` in a Solidity ABI.
+pub struct TreasuryWrapper<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for TreasuryWrapper<Runtime>
+where
+	Runtime: pallet_treasury::Config + pallet_evm::Config,
+	<Runtime as frame_system::Config>::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<TreasuryCall<Runtime>>,
+	<<Runtime as frame_system::Config>::Call as Dispatchable>::Origin:
+		From<Option<Runtime::AccountId>>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		let (selector, mut args) = AbiReader::new_with_selector(input)?;
+
+		let call = match selector {
+			SELECTOR_DONATE => TreasuryCall::<Runtime>::donate(args.read_balance()?),
+			SELECTOR_POT => {
+				let pot: u128 = pallet_treasury::Pallet::<Runtime>::pot().unique_saturated_into();
+				return view::<Runtime>(1, target_gas, encode_u256(pot.into()));
+			},
+			_ => return Err(ExitError::Other("unknown treasury selector".into())),
+		};
+
+		dispatch::<Runtime>(TREASURY_PRECOMPILE, call.into(), target_gas, context)
+	}
+}
@@ -0,0 +1,50 @@
+#!/usr/bin/env bash
+
+# Run the runtime benchmarks and write their results to the weights modules of the runtimes.
+#
+# Run from the repository root, on the reference machine the weights are meant for:
+# ./scripts/benchmark.sh [runtime...]
+#
+# Without arguments every runtime is benchmarked. Commit the files it writes as they are.
+
+set -e
+
+STEPS=${STEPS:-50}
+REPEAT=${REPEAT:-20}
+
+# The chain spec of each runtime, as `load_spec` names it.
+declare -A CHAINS=(
+    [halongbay]=halongbay
+    [polkafoundry]=polkafoundry-dev
+    [polkasmith]=polkasmith-dev
+)
+
+# The pallets of each runtime registered with `add_benchmark!`.
+declare -A PALLETS=(
+    [halongbay]="frame_system pallet_balances pallet_timestamp pallet_crowdloan_rewards pallet_treasury
+        pallet_bounties pallet_tips pallet_author_mapping author_inherent pallet_author_filter pallet_base_fee
+        polkafoundry_staking"
+    [polkafoundry]="frame_system pallet_balances pallet_timestamp"
+    [polkasmith]="frame_system pallet_balances pallet_timestamp"
+)
+
+RUNTIMES=${@:-halongbay polkafoundry polkasmith}
+
+for runtime in $RUNTIMES; do
+    echo "*** Building the node with the $runtime runtime and its benchmarks ***"
+    cargo build --release --no-default-features --features "$runtime,runtime-benchmarks"
+
+    mkdir -p "runtime/$runtime/src/weights"
+    for pallet in ${PALLETS[$runtime]}; do
+        echo "*** Benchmarking $pallet in $runtime ***"
+        ./target/release/polkafoundry benchmark \
+            --chain "${CHAINS[$runtime]}" \
+            --execution wasm \
+            --wasm-execution compiled \
+            --pallet "$pallet" \
+            --extrinsic '*' \
+            --steps "$STEPS" \
+            --repeat "$REPEAT" \
+            --output "runtime/$runtime/src/weights/$pallet.rs"
+    done
+done
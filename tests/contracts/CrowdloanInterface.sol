@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: GPL-3.0
+
+pragma solidity >=0.4.22 <0.9.0;
+
+/**
+ * @title CrowdloanInterface
+ * @dev The crowdloan rewards precompile at 0x0000000000000000000000000000000000000403.
+ * Rewards are paid to the substrate account the caller's address maps to.
+ */
+interface CrowdloanInterface {
+
+    /**
+     * @dev Associate the caller with `relay_account`, which signed
+     * "<pallet name> on chain <chain id>: associate relay account with 0x<hex of the
+     * caller's SCALE-encoded account id>". `proof` is the SCALE-encoded `MultiSignature`
+     */
+    function associate_account(bytes32 relay_account, bytes calldata proof) external;
+
+    /**
+     * @dev Claim the reward vested so far in `campaign_id`
+     */
+    function claim(uint32 campaign_id) external;
+
+    /**
+     * @dev The reward `contributor` can claim now, over all campaigns
+     */
+    function claimable(address contributor) external view returns (uint256);
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: GPL-3.0
+
+pragma solidity >=0.4.22 <0.9.0;
+
+/**
+ * @title StakingInterface
+ * @dev The staking precompile at 0x0000000000000000000000000000000000000405.
+ * The caller stakes from the substrate account its address maps to. Collators
+ * are referred to by their 32-byte account id.
+ */
+interface StakingInterface {
+
+    /**
+     * @dev Bond `amount` to become a collator candidate
+     */
+    function bond(uint256 amount) external;
+
+    /**
+     * @dev Bond `extra` on top of the caller's collator bond
+     */
+    function bond_extra(uint256 extra) external;
+
+    /**
+     * @dev Unbond `less` from the caller's collator bond
+     */
+    function bond_less(uint256 less) external;
+
+    /**
+     * @dev Nominate `collator` with `amount`
+     */
+    function nominate(bytes32 collator, uint256 amount) external;
+
+    /**
+     * @dev Add `extra` to the caller's nomination of `collator`
+     */
+    function nominate_extra(bytes32 collator, uint256 extra) external;
+
+    /**
+     * @dev Remove `less` from the caller's nomination of `collator`
+     */
+    function nominate_less(bytes32 collator, uint256 less) external;
+
+    /**
+     * @dev Leave the collator candidates, returning the bond after the bond duration
+     */
+    function leave() external;
+
+    /**
+     * @dev Withdraw the caller's nomination of `collator`, returning it after the bond duration
+     */
+    function leave_nomination(bytes32 collator) external;
+
+    /**
+     * @dev Whether `collator` has bonded as a collator
+     */
+    function is_collator(bytes32 collator) external view returns (bool);
+
+    /**
+     * @dev The amount `nominator` nominated `collator` with
+     */
+    function nomination_amount(address nominator, bytes32 collator) external view returns (uint256);
+
+    /**
+     * @dev Index of the current round
+     */
+    function current_round() external view returns (uint256);
+
+    /**
+     * @dev Minimum amount of a nomination
+     */
+    function min_nomination() external view returns (uint256);
+}
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: GPL-3.0
+
+pragma solidity >=0.4.22 <0.9.0;
+
+/**
+ * @title TreasuryInterface
+ * @dev The treasury precompile at 0x0000000000000000000000000000000000000404.
+ * Donations are paid from the substrate account the caller's address maps to.
+ */
+interface TreasuryInterface {
+
+    /**
+     * @dev Donate `amount` to the treasury
+     */
+    function donate(uint256 amount) external;
+
+    /**
+     * @dev Funds in the treasury pot
+     */
+    function pot() external view returns (uint256);
+}
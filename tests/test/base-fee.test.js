@@ -0,0 +1,47 @@
+const { createAndFinalizeBlock, customRequest, describeWithPolkafoundry } = require('./utils');
+const { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, GAS_PRICE } = require('./constants');
+const { expect } = require('chai');
+
+describeWithPolkafoundry('Polkafoundry Base Fee', 'polka-spec.json', (context) => {
+    const TO = '0x1111111111111111111111111111111111111111';
+
+    const transfer = async (gasPrice) => {
+        const tx = await context.web3.eth.accounts.signTransaction(
+            {
+                from: GENESIS_ACCOUNT,
+                to: TO,
+                value: '0x01',
+                gasPrice,
+                gas: '0x100000',
+            },
+            GENESIS_ACCOUNT_PRIVATE_KEY
+        );
+        return customRequest(context.web3, 'eth_sendRawTransaction', [tx.rawTransaction]);
+    };
+
+    it('Gas price should be the base fee', async () => {
+        const gasPrice = await context.web3.eth.getGasPrice();
+
+        expect(Number(gasPrice)).to.be.greaterThan(0);
+        expect(Number(gasPrice)).to.be.at.most(Number(GAS_PRICE));
+    });
+
+    it('Transactions below the base fee should be rejected', async () => {
+        const response = await transfer('0x01');
+
+        expect(response.error).to.not.be.undefined;
+    });
+
+    it('Gas should be charged at the gas price', async function () {
+        this.timeout(15000);
+        const before = context.web3.utils.toBN(await context.web3.eth.getBalance(GENESIS_ACCOUNT));
+
+        const { result } = await transfer(GAS_PRICE);
+        await createAndFinalizeBlock(context.web3);
+        const receipt = await context.web3.eth.getTransactionReceipt(result);
+
+        const after = context.web3.utils.toBN(await context.web3.eth.getBalance(GENESIS_ACCOUNT));
+        const fee = context.web3.utils.toBN(receipt.gasUsed).mul(context.web3.utils.toBN(GAS_PRICE));
+        expect(before.sub(after).toString()).equals(fee.addn(1).toString());
+    });
+})
@@ -9,6 +9,11 @@ const SPAWNING_TIME = 30000;
 const GENESIS_ACCOUNT = '0x6be02d1d3665660d22ff9624b7be0551ee1ac91b';
 const GENESIS_ACCOUNT_PRIVATE_KEY = '0x99B3C12287537E38C90A9219D4CB074A89A16E9CDB20BF85728EBD97C343E342'
 
+// The highest base fee the Halongbay runtime allows, so transactions never price out.
+const GAS_PRICE = '0x3B9ACA00';
+// Well within the block gas limit.
+const GAS_LIMIT = '0x1000000';
+
 const STORAGE_CONTRACT_ADDRESS = '0xC2Bf5F29a4384b1aB0C063e1c666f02121B6084a'
 
 module.exports = {
@@ -20,5 +25,7 @@ module.exports = {
     SPAWNING_TIME,
     GENESIS_ACCOUNT,
     GENESIS_ACCOUNT_PRIVATE_KEY,
+    GAS_PRICE,
+    GAS_LIMIT,
     STORAGE_CONTRACT_ADDRESS,
 }
@@ -0,0 +1,84 @@
+const { createAndFinalizeBlock, customRequest, describeWithPolkafoundry } = require('./utils');
+const { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, GAS_PRICE } = require('./constants');
+const { expect } = require('chai');
+
+const CROWDLOAN_PRECOMPILE = '0x0000000000000000000000000000000000000403';
+const TREASURY_PRECOMPILE = '0x0000000000000000000000000000000000000404';
+
+describeWithPolkafoundry('Polkafoundry Crowdloan and Treasury Precompiles', 'polka-spec.json', (context) => {
+    const call = async (to, data) => {
+        const tx = await customRequest(context.web3, 'eth_call', [
+            {
+                from: GENESIS_ACCOUNT,
+                value: '0x00',
+                gasPrice: GAS_PRICE,
+                gas: '0x100000',
+                to,
+                data,
+            },
+        ]);
+        return tx.result;
+    };
+
+    const send = async (to, data) => {
+        const tx = await context.web3.eth.accounts.signTransaction(
+            {
+                from: GENESIS_ACCOUNT,
+                to,
+                data,
+                value: '0x00',
+                gasPrice: GAS_PRICE,
+                gas: '0x100000',
+            },
+            GENESIS_ACCOUNT_PRIVATE_KEY
+        );
+        const { result } = await customRequest(context.web3, 'eth_sendRawTransaction', [tx.rawTransaction]);
+        await createAndFinalizeBlock(context.web3);
+        return context.web3.eth.getTransactionReceipt(result);
+    };
+
+    // pot()
+    const pot = async () => context.web3.utils.toBN(await call(TREASURY_PRECOMPILE, '0x4ba2363a'));
+
+    it('Treasury donate should grow the pot', async function () {
+        this.timeout(15000);
+        const amount = context.web3.utils.toBN('1000000000000000000');
+        const before = await pot();
+
+        // donate(uint256)
+        const receipt = await send(
+            TREASURY_PRECOMPILE,
+            `0xf14faf6f${amount.toString(16).padStart(64, '0')}`
+        );
+
+        expect(receipt.status).to.be.true;
+        // The pot also receives its cut of the gas fee.
+        expect((await pot()).sub(before).gte(amount)).to.be.true;
+    });
+
+    it('Treasury donate below the existential deposit should fail', async function () {
+        this.timeout(15000);
+        // donate(0)
+        const receipt = await send(TREASURY_PRECOMPILE, `0xf14faf6f${'0'.padStart(64, '0')}`);
+
+        expect(receipt.status).to.be.false;
+    });
+
+    it('Crowdloan claimable should be zero without an associated account', async () => {
+        // claimable(address)
+        const result = await call(
+            CROWDLOAN_PRECOMPILE,
+            `0x402914f5${GENESIS_ACCOUNT.slice(2).padStart(64, '0')}`
+        );
+
+        expect(context.web3.utils.hexToNumberString(result)).equals('0');
+    });
+
+    it('Crowdloan claim should fail without an associated account', async function () {
+        this.timeout(15000);
+        // claim(uint32)
+        const receipt = await send(CROWDLOAN_PRECOMPILE, `0x04951891${'0'.padStart(64, '0')}`);
+
+        expect(receipt.status).to.be.false;
+    });
+})
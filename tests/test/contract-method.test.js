@@ -1,6 +1,6 @@
 const Test = require('../build/contracts/Storage.json');
 const { createAndFinalizeBlock, customRequest, describeWithPolkafoundry } = require('./utils');
-const { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, STORAGE_CONTRACT_ADDRESS } = require('./constants');
+const { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, STORAGE_CONTRACT_ADDRESS, GAS_PRICE } = require('./constants');
 const { expect } = require('chai');
 
 describeWithPolkafoundry('Polkafoundry RPC Contract Method', 'polka-spec.json', (context) => {
@@ -15,7 +15,7 @@ describeWithPolkafoundry('Polkafoundry RPC Contract Method', 'polka-spec.json',
                 from: GENESIS_ACCOUNT,
                 data: TEST_CONTRACT_BYTECODE,
                 value: '0x00',
-                gasPrice: '0x01',
+                gasPrice: GAS_PRICE,
                 gas: '0x100000',
             },
             GENESIS_ACCOUNT_PRIVATE_KEY
@@ -33,7 +33,7 @@ describeWithPolkafoundry('Polkafoundry RPC Contract Method', 'polka-spec.json',
                 to: STORAGE_CONTRACT_ADDRESS,
                 data: encodeAbi,
                 value: '0x00',
-                gasPrice: '0x01',
+                gasPrice: GAS_PRICE,
                 gas: '0x100000',
             },
             GENESIS_ACCOUNT_PRIVATE_KEY
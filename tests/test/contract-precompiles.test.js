@@ -1,5 +1,5 @@
 const { customRequest, describeWithPolkafoundry } = require('./utils');
-const { GENESIS_ACCOUNT } = require('./constants');
+const { GENESIS_ACCOUNT, GAS_PRICE } = require('./constants');
 const { expect } = require('chai');
 
 describeWithPolkafoundry('Polkafoundry Precompiles', 'polka-spec.json', (context) => {
@@ -27,7 +27,7 @@ describeWithPolkafoundry('Polkafoundry Precompiles', 'polka-spec.json', (context
             {
                 from: GENESIS_ACCOUNT,
                 value: '0x00',
-                gasPrice: '0x01',
+                gasPrice: GAS_PRICE,
                 gas: '0x100000',
                 to: '0x0000000000000000000000000000000000000002',
                 data: `0x${Buffer.from('Hello world!').toString('hex')}`,
@@ -44,7 +44,7 @@ describeWithPolkafoundry('Polkafoundry Precompiles', 'polka-spec.json', (context
             {
                 from: GENESIS_ACCOUNT,
                 value: '0x00',
-                gasPrice: '0x01',
+                gasPrice: GAS_PRICE,
                 gas: '0x100000',
                 to: '0x0000000000000000000000000000000000000003',
                 data: `0x${Buffer.from('Hello world!').toString('hex')}`,
@@ -56,4 +56,21 @@ describeWithPolkafoundry('Polkafoundry Precompiles', 'polka-spec.json', (context
         );
     });
 
+    it('Erc20 balanceOf should match the native balance', async () => {
+        const tx = await customRequest(context.web3, 'eth_call', [
+            {
+                from: GENESIS_ACCOUNT,
+                value: '0x00',
+                gasPrice: GAS_PRICE,
+                gas: '0x100000',
+                to: '0x0000000000000000000000000000000000000402',
+                // balanceOf(address)
+                data: `0x70a08231${GENESIS_ACCOUNT.slice(2).padStart(64, '0')}`,
+            },
+        ]);
+        const balance = await context.web3.eth.getBalance(GENESIS_ACCOUNT);
+
+        expect(context.web3.utils.hexToNumberString(tx.result)).equals(balance);
+    });
+
 })
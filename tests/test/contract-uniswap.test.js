@@ -4,7 +4,7 @@ const UniswapV2Factory = require('@uniswap/v2-core/build/UniswapV2Factory.json')
 const UniswapV2Pair = require('@uniswap/v2-core/build/UniswapV2Pair.json');
 const UniswapRouter02 = require('@uniswap/v2-periphery/build/UniswapV2Router02.json');
 const { createAndFinalizeBlock, customRequest, describeWithPolkafoundry, deployContract } = require('./utils');
-const { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, } = require('./constants');
+const { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, GAS_PRICE, GAS_LIMIT } = require('./constants');
 const { expect } = require('chai');
 
 describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (context) => {
@@ -15,7 +15,7 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
 
     before('Create ERC20 contract', async function () {
         this.timeout(15000);
-        const tx = await deployContract(context.web3, PolkafoundryERC20, [BigInt(8000000000000000000000000)], '0x1000000', '0x01')
+        const tx = await deployContract(context.web3, PolkafoundryERC20, [BigInt(8000000000000000000000000)], GAS_LIMIT, GAS_PRICE)
         await createAndFinalizeBlock(context.web3);
         const recipe = await context.web3.eth.getTransactionReceipt(tx);
         erc20Address = recipe.contractAddress;
@@ -23,7 +23,7 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
 
     before('Create WETH contract', async function () {
         this.timeout(15000);
-        const tx = await deployContract(context.web3, WETH9, [BigInt(8000000000000000000000000)], '0x6000000', '0x01')
+        const tx = await deployContract(context.web3, WETH9, [BigInt(8000000000000000000000000)], GAS_LIMIT, GAS_PRICE)
         await createAndFinalizeBlock(context.web3);
         const recipe = await context.web3.eth.getTransactionReceipt(tx);
         wethAddress = recipe.contractAddress;
@@ -31,8 +31,7 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
 
     before('Create UniswapFactory contract', async function () {
         this.timeout(15000);
-        const gasLimit = (await context.web3.eth.getBlock("latest")).gasLimit;
-        const tx = await deployContract(context.web3, UniswapV2Factory, [GENESIS_ACCOUNT], gasLimit + 1000, '0x01')
+        const tx = await deployContract(context.web3, UniswapV2Factory, [GENESIS_ACCOUNT], GAS_LIMIT, GAS_PRICE)
         await createAndFinalizeBlock(context.web3);
         const recipe = await context.web3.eth.getTransactionReceipt(tx);
         uniswapFactoryAddress = recipe.contractAddress;
@@ -40,8 +39,7 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
 
     before('Create UniswapRouter contract', async function () {
         this.timeout(15000);
-        const gasLimit = (await context.web3.eth.getBlock("latest")).gasLimit;
-        const tx = await deployContract(context.web3, UniswapRouter02, [uniswapFactoryAddress, wethAddress], gasLimit + 1000, '0x01')
+        const tx = await deployContract(context.web3, UniswapRouter02, [uniswapFactoryAddress, wethAddress], GAS_LIMIT, GAS_PRICE)
         await createAndFinalizeBlock(context.web3);
         const recipe = await context.web3.eth.getTransactionReceipt(tx);
         uniswapRouter02Address = recipe.contractAddress;
@@ -59,7 +57,6 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
         const factoryContract = new context.web3.eth.Contract(UniswapV2Factory.abi, uniswapFactoryAddress);
         const router02contract = new context.web3.eth.Contract(UniswapRouter02.abi, uniswapRouter02Address);
         const erc20Contract = new context.web3.eth.Contract(PolkafoundryERC20.abi, erc20Address);
-        const gasLimit = (await context.web3.eth.getBlock("latest")).gasLimit;
 
         // add a pair for ERC20-WETH
         const txPair = await context.web3.eth.accounts.signTransaction(
@@ -71,8 +68,8 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
                     wethAddress,
                 ).encodeABI(),
                 value: '0x00',
-                gasPrice: '0x01',
-                gas: gasLimit + 10000,
+                gasPrice: GAS_PRICE,
+                gas: GAS_LIMIT,
             },
             GENESIS_ACCOUNT_PRIVATE_KEY
         );
@@ -94,8 +91,8 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
                     (500 * 10 ** 18).toString(),
                 ).encodeABI(),
                 value: '0x00',
-                gasPrice: '0x01',
-                gas: gasLimit + 10000,
+                gasPrice: GAS_PRICE,
+                gas: GAS_LIMIT,
             },
             GENESIS_ACCOUNT_PRIVATE_KEY
         );
@@ -116,8 +113,8 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
                     '2000000000',
                 ).encodeABI(),
                 value: (10 ** 18).toString(),
-                gasPrice: '0x01',
-                gas: gasLimit + 10000,
+                gasPrice: GAS_PRICE,
+                gas: GAS_LIMIT,
             },
             GENESIS_ACCOUNT_PRIVATE_KEY
         );
@@ -153,8 +150,8 @@ describeWithPolkafoundry('Polkafoundry Uniswap Contract', 'polka-spec.json', (co
                     '2000000000'
                 ).encodeABI(),
                 value: (0.1 * 10 ** 18),
-                gasPrice: '0x01',
-                gas: gasLimit + 10000,
+                gasPrice: GAS_PRICE,
+                gas: GAS_LIMIT,
             },
             GENESIS_ACCOUNT_PRIVATE_KEY
         );